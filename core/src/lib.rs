@@ -0,0 +1,521 @@
+//! Runtime types of the `enum_discrim` crate
+//!
+//! This crate has no dependencies and supports `#![no_std]`,
+//! so that it can be used without the procedural macros of `enum_discrim`.
+#![no_std]
+#![warn(
+	unused,
+	clippy::unused_self,
+	unused_crate_dependencies,
+	unused_import_braces,
+	unreachable_pub,
+	noop_method_call,
+	clippy::match_wildcard_for_single_variants,
+	clippy::rest_pat_in_fully_bound_structs,
+	clippy::match_on_vec_items,
+	clippy::imprecise_flops,
+	clippy::suboptimal_flops,
+	clippy::float_cmp,
+	clippy::float_cmp_const,
+	clippy::mem_forget,
+	clippy::filter_map_next,
+	clippy::verbose_file_reads,
+	clippy::inefficient_to_string,
+	clippy::str_to_string,
+	clippy::option_option,
+	clippy::dbg_macro,
+	clippy::print_stdout,
+	clippy::print_stderr,
+	missing_debug_implementations,
+	missing_copy_implementations,
+	clippy::missing_const_for_fn,
+	missing_docs,
+	clippy::missing_docs_in_private_items,
+	clippy::doc_link_with_quotes,
+	clippy::doc_markdown,
+	clippy::needless_continue,
+	clippy::manual_let_else,
+	clippy::unnested_or_patterns,
+	clippy::semicolon_if_nothing_returned,
+	clippy::empty_line_after_outer_attr,
+	clippy::empty_structs_with_brackets,
+	clippy::enum_glob_use,
+	clippy::macro_use_imports,
+	clippy::mod_module_files
+)]
+#![deny(
+	keyword_idents,
+	non_ascii_idents,
+	unused_must_use,
+	clippy::lossy_float_literal,
+	clippy::exit
+)]
+#![forbid(unsafe_code, clippy::missing_panics_doc, clippy::missing_errors_doc)]
+
+use core::{
+	error::Error,
+	fmt::{self, Debug, Display, Formatter},
+	marker::PhantomData,
+};
+
+/// Error returned by the `TryFrom` implementations generated by `enum_discrim`
+///
+/// The data of this error is exposed through accessors, which are part of the stable API:
+/// their return values never depend on the [`Display`] implementation, which may change.
+///
+/// The type parameter is the enum which the value was converted into, with `#[discrim(typed_error)]`,
+/// so that the errors of different enums have distinct types.
+/// It defaults to `()`, so that `TryFromError` names the error type shared by all other enums.
+pub struct TryFromError<E = ()> {
+	/// Enum identifier
+	ident: &'static str,
+	/// Reason why the value was rejected
+	kind: TryFromErrorKind,
+	/// Rejected value, if it is known
+	value: Option<RawValue>,
+	/// Values which would have been accepted, if they are known
+	expected: &'static [RawValue],
+	/// Enum which the value was converted into
+	marker: PhantomData<E>,
+}
+impl<E> TryFromError<E> {
+	#[doc(hidden)]
+	#[inline]
+	pub const fn new(ident: &'static str) -> Self {
+		Self {
+			ident,
+			kind: TryFromErrorKind::Unknown,
+			value: None,
+			expected: &[],
+			marker: PhantomData,
+		}
+	}
+
+	#[doc(hidden)]
+	#[inline]
+	pub const fn new_reserved(ident: &'static str) -> Self {
+		Self {
+			ident,
+			kind: TryFromErrorKind::Reserved,
+			value: None,
+			expected: &[],
+			marker: PhantomData,
+		}
+	}
+
+	#[doc(hidden)]
+	#[inline]
+	pub const fn new_skipped(ident: &'static str) -> Self {
+		Self {
+			ident,
+			kind: TryFromErrorKind::Skipped,
+			value: None,
+			expected: &[],
+			marker: PhantomData,
+		}
+	}
+
+	#[doc(hidden)]
+	#[inline]
+	pub const fn new_out_of_range(ident: &'static str) -> Self {
+		Self {
+			ident,
+			kind: TryFromErrorKind::OutOfRange,
+			value: None,
+			expected: &[],
+			marker: PhantomData,
+		}
+	}
+
+	#[doc(hidden)]
+	#[inline]
+	#[must_use]
+	pub fn with_value<V>(self, value: V) -> Self
+	where
+		V: Into<RawValue>,
+	{
+		Self {
+			value: Some(value.into()),
+			..self
+		}
+	}
+
+	#[doc(hidden)]
+	#[inline]
+	#[must_use]
+	pub const fn with_expected(self, expected: &'static [RawValue]) -> Self {
+		Self { expected, ..self }
+	}
+
+	/// Converts this error into the error type shared by all enums, keeping its data
+	#[inline]
+	pub const fn into_untyped(self) -> TryFromError {
+		TryFromError {
+			ident: self.ident,
+			kind: self.kind,
+			value: self.value,
+			expected: self.expected,
+			marker: PhantomData,
+		}
+	}
+
+	/// Returns the name of the enum which the value was converted into
+	#[inline]
+	pub const fn enum_name(&self) -> &'static str {
+		self.ident
+	}
+
+	/// Returns the reason why the value was rejected
+	#[inline]
+	pub const fn kind(&self) -> TryFromErrorKind {
+		self.kind
+	}
+
+	/// Returns the rejected value, if it is known
+	///
+	/// The value is known for the errors returned by the `TryFrom<repr>` implementations,
+	/// but not for the values which are out of the range of the primitive representation.
+	#[inline]
+	pub const fn value(&self) -> Option<RawValue> {
+		self.value
+	}
+
+	/// Returns the values which would have been accepted, if they are known
+	///
+	/// The discriminants of the variants are listed in declaration order,
+	/// followed by the values given with `#[discrim(alias = ...)]`.
+	/// The returned slice is empty if the error was not returned by the `TryFrom` derive macro.
+	#[inline]
+	pub const fn expected(&self) -> &'static [RawValue] {
+		self.expected
+	}
+
+	/// Returns whether the value is reserved by the enum, rather than unknown
+	#[inline]
+	pub const fn is_reserved(&self) -> bool {
+		matches!(self.kind, TryFromErrorKind::Reserved)
+	}
+}
+// These impl blocks are written by hand, as derived ones would require the enum to implement the trait as well
+impl<E> Debug for TryFromError<E> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("TryFromError")
+			.field("ident", &self.ident)
+			.field("kind", &self.kind)
+			.field("value", &self.value)
+			.field("expected", &self.expected)
+			.finish()
+	}
+}
+impl<E> Clone for TryFromError<E> {
+	#[inline]
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+impl<E> Copy for TryFromError<E> {}
+impl<E> Display for TryFromError<E> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self.kind {
+			TryFromErrorKind::Unknown => {
+				write!(f, "Tried to convert an invalid value into a {}", self.ident)
+			}
+			TryFromErrorKind::Reserved => {
+				write!(f, "Tried to convert a reserved value into a {}", self.ident)
+			}
+			TryFromErrorKind::Skipped => write!(
+				f,
+				"Tried to convert the discriminant of a skipped variant into a {}",
+				self.ident
+			),
+			TryFromErrorKind::OutOfRange => write!(
+				f,
+				"Tried to convert a value out of the range of the representation into a {}",
+				self.ident
+			),
+		}?;
+		if let Some(value) = self.value {
+			write!(f, " (got {value})")?;
+		}
+		if let Some((first, rest)) = self.expected.split_first() {
+			write!(f, ", expected one of {first}")?;
+			for value in rest {
+				write!(f, ", {value}")?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Rejected or expected value, returned by [`TryFromError::value`] and [`TryFromError::expected`]
+///
+/// Values are widened to 128 bits, keeping the signedness of the primitive representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RawValue {
+	/// Value of a signed integer type
+	Signed(i128),
+	/// Value of an unsigned integer type
+	Unsigned(u128),
+}
+impl Display for RawValue {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Signed(value) => Display::fmt(value, f),
+			Self::Unsigned(value) => Display::fmt(value, f),
+		}
+	}
+}
+/// Generates an impl [`From`] block into [`RawValue`] for each given type
+macro_rules! impl_from_raw_value {
+	($variant:ident: $wide:ty => $( $ty:ty ),* $(,)?) => {
+		$(
+			impl From<$ty> for RawValue {
+				#[inline]
+				#[allow(clippy::cast_lossless, clippy::unnecessary_cast)]
+				fn from(value: $ty) -> Self {
+					Self::$variant(value as $wide)
+				}
+			}
+		)*
+	};
+}
+impl_from_raw_value!(Signed: i128 => i8, i16, i32, i64, i128, isize);
+impl_from_raw_value!(Unsigned: u128 => u8, u16, u32, u64, u128, usize);
+
+/// Reason why a value was rejected, returned by [`TryFromError::kind`]
+///
+/// More reasons may be added in future versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TryFromErrorKind {
+	/// The value is not the discriminant of any variant
+	Unknown,
+	/// The value is within a range declared with `#[discrim(reserved = "...")]`
+	Reserved,
+	/// The value does not fit in the primitive representation of the enum
+	OutOfRange,
+	/// The value is the discriminant of a variant excluded with `#[discrim(skip)]`
+	Skipped,
+}
+impl<E> Error for TryFromError<E> {
+	#[inline]
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		None
+	}
+}
+
+/// Error returned by the `TryFrom<f64>` implementations generated by `enum_discrim` with `#[discrim(try_from_f64)]`
+#[derive(Debug, Clone, Copy)]
+pub enum TryFromFloatError {
+	/// The number is not an integer
+	NotIntegral,
+	/// The number is an integer, but not a valid discriminant
+	Invalid(TryFromError),
+}
+impl Display for TryFromFloatError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NotIntegral => write!(f, "Tried to convert a non-integral number into an enum"),
+			Self::Invalid(err) => Display::fmt(err, f),
+		}
+	}
+}
+impl Error for TryFromFloatError {
+	#[inline]
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::NotIntegral => None,
+			Self::Invalid(err) => Some(err),
+		}
+	}
+}
+
+/// Error returned by the `FromStr` implementations generated by `enum_discrim`
+#[derive(Debug, Clone, Copy)]
+pub struct FromStrError {
+	/// Enum identifier
+	ident: &'static str,
+}
+impl FromStrError {
+	#[doc(hidden)]
+	#[inline]
+	pub const fn new(ident: &'static str) -> Self {
+		Self { ident }
+	}
+}
+impl Display for FromStrError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "Tried to parse an invalid name into a {}", self.ident)
+	}
+}
+impl Error for FromStrError {
+	#[inline]
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		None
+	}
+}
+
+/// Position of the variants of an enum, in declaration order
+///
+/// This trait is implemented by the `Ordinal` derive macro of `enum_discrim`.
+pub trait Ordinal: Sized {
+	/// Number of variants
+	const COUNT: usize;
+
+	/// Returns the position of this variant in the enum declaration
+	///
+	/// The returned value is always less than [`COUNT`](Self::COUNT).
+	fn ordinal(&self) -> usize;
+	/// Returns the variant declared at the given position, if any
+	fn from_ordinal(ordinal: usize) -> Option<Self>;
+}
+
+/// Zero-sized type standing for a variant of an enum, carrying its discriminant as a const
+///
+/// This trait is implemented by the marker types generated by the `Discriminants` derive macro of `enum_discrim`
+/// with `#[discrim(markers)]`.
+pub trait VariantMarker {
+	/// Enum declaring the variant
+	type Enum;
+	/// Primitive representation of the enum
+	type Repr;
+
+	/// Discriminant of the variant
+	const DISCRIMINANT: Self::Repr;
+}
+
+/// Enum whose variants have a discriminant
+///
+/// This trait is implemented by the `Discriminants` derive macro of `enum_discrim`,
+/// so that generic code may get the discriminant of any such enum.
+pub trait Discriminant {
+	/// Type of the discriminants
+	type Repr;
+
+	/// Returns the discriminant of this variant
+	fn discriminant(&self) -> Self::Repr;
+}
+
+/// Static metadata of an enum, returned by [`Describe::enum_info`]
+#[derive(Debug, Clone, Copy)]
+pub struct EnumInfo {
+	/// Name of the enum
+	name: &'static str,
+	/// Primitive representation of the enum
+	repr: &'static str,
+	/// Metadata of each variant, in declaration order
+	variants: &'static [VariantInfo],
+}
+impl EnumInfo {
+	#[doc(hidden)]
+	#[inline]
+	pub const fn new(
+		name: &'static str,
+		repr: &'static str,
+		variants: &'static [VariantInfo],
+	) -> Self {
+		Self {
+			name,
+			repr,
+			variants,
+		}
+	}
+
+	/// Returns the name of the enum
+	#[inline]
+	pub const fn name(&self) -> &'static str {
+		self.name
+	}
+
+	/// Returns the primitive representation of the enum (e.g. `"u8"`, or `"c_int"` for `#[repr(C)]`)
+	#[inline]
+	pub const fn repr(&self) -> &'static str {
+		self.repr
+	}
+
+	/// Returns the metadata of each variant, in declaration order
+	#[inline]
+	pub const fn variants(&self) -> &'static [VariantInfo] {
+		self.variants
+	}
+
+	/// Returns the name of the variant with the given discriminant, if any
+	#[inline]
+	pub fn name_of(&self, value: i128) -> Option<&'static str> {
+		self.variants
+			.iter()
+			.find(|variant| variant.value == value)
+			.map(VariantInfo::name)
+	}
+}
+
+/// Static metadata of an enum variant, part of an [`EnumInfo`]
+#[derive(Debug, Clone, Copy)]
+pub struct VariantInfo {
+	/// Name of the variant
+	name: &'static str,
+	/// Discriminant of the variant
+	value: i128,
+}
+impl VariantInfo {
+	#[doc(hidden)]
+	#[inline]
+	pub const fn new(name: &'static str, value: i128) -> Self {
+		Self { name, value }
+	}
+
+	/// Returns the name of the variant, as used by the name-based derive macros
+	#[inline]
+	pub const fn name(&self) -> &'static str {
+		self.name
+	}
+
+	/// Returns the discriminant of the variant
+	#[inline]
+	pub const fn value(&self) -> i128 {
+		self.value
+	}
+}
+
+/// Static metadata of an enum
+///
+/// This trait is implemented by the `Describe` derive macro of `enum_discrim`.
+pub trait Describe {
+	/// Returns the metadata of the enum
+	fn enum_info() -> &'static EnumInfo;
+}
+
+/// Extension trait converting primitive values into enums with a method call
+///
+/// This trait is implemented for all integer types and `char`, so that parsers can chain conversions
+/// such as `byte.to_enum::<Opcode>()?` instead of `Opcode::try_from(byte)?`.
+pub trait TryFromReprExt: Sized + private::Sealed {
+	/// Converts this value into the given enum
+	///
+	/// # Errors
+	/// Returns the error of the [`TryFrom`] impl block of the enum,
+	/// e.g. a [`TryFromError`] for enums deriving `TryFrom` if this value is not a valid discriminant.
+	#[inline]
+	fn to_enum<E>(self) -> Result<E, E::Error>
+	where
+		E: TryFrom<Self>,
+	{
+		E::try_from(self)
+	}
+}
+/// Generates an impl [`TryFromReprExt`] block for each given type
+macro_rules! impl_try_from_repr_ext {
+	($( $ty:ty ),* $(,)?) => {
+		$(
+			impl private::Sealed for $ty {}
+			impl TryFromReprExt for $ty {}
+		)*
+	};
+}
+impl_try_from_repr_ext![u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char];
+
+/// Prevents [`TryFromReprExt`] from being implemented outside of this crate
+mod private {
+	/// Types implementing [`TryFromReprExt`](super::TryFromReprExt)
+	pub trait Sealed {}
+}
@@ -0,0 +1,45 @@
+//! Provides the code generation for the [`enum_discrim`](crate::enum_discrim) attribute macro
+
+use crate::EnumModel;
+use darling::FromMeta;
+use proc_macro::TokenStream;
+
+/// Parsing struct for the arguments of the [`enum_discrim`](crate::enum_discrim) attribute macro
+#[derive(Debug, Default, FromMeta)]
+struct AttributeOptions {
+	/// Whether the discriminant of each variant is appended to its documentation
+	#[darling(default)]
+	doc_values: bool,
+}
+
+/// Formats the given base 10 discriminant as a hexadecimal number
+pub(crate) fn hex(number: &str) -> String {
+	match number.strip_prefix('-') {
+		Some(abs) => format!("-{}", hex(abs)),
+		None => number
+			.parse::<u128>()
+			.map_or_else(|_| number.to_owned(), |value| format!("{value:#X}")),
+	}
+}
+
+/// Rewrites the enum according to the given arguments
+pub(crate) fn expand(args: TokenStream, item: TokenStream) -> darling::Result<TokenStream> {
+	use syn::{parse::Parser, punctuated::Punctuated, ItemEnum, NestedMeta, Token};
+
+	let args = Punctuated::<NestedMeta, Token![,]>::parse_terminated.parse(args)?;
+	let options = AttributeOptions::from_list(&args.into_iter().collect::<Vec<_>>())?;
+	let mut output: ItemEnum = syn::parse(item.clone())?;
+
+	if options.doc_values {
+		let model = EnumModel::parse(item)?;
+		for (variant, model) in output.variants.iter_mut().zip(&model.variants) {
+			let doc = format!("Discriminant: `{}`", hex(&model.number));
+			if variant.attrs.iter().any(|attr| attr.path.is_ident("doc")) {
+				variant.attrs.push(syn::parse_quote!(#[doc = ""]));
+			}
+			variant.attrs.push(syn::parse_quote!(#[doc = #doc]));
+		}
+	}
+
+	Ok(quote::quote!(#output).into())
+}
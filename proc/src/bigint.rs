@@ -0,0 +1,56 @@
+//! Provides the code generation for the [`TryFromBigInt`](crate::derive_try_from_big_int) derive macro
+
+use crate::EnumModel;
+use proc_macro::TokenStream;
+
+/// Derives impl `TryFrom<BigInt>` and `TryFrom<&BigInt>` blocks,
+/// checking the value against the range of the primitive representation before matching the discriminants
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let repr = model.repr_ty();
+	let skipped = model.skipped_arm();
+	let reserved = model.reserved_arm();
+	let EnumModel {
+		ident, variants, ..
+	} = model;
+	let arms = variants
+		.iter()
+		.filter(|variant| variant.is_decoded())
+		.map(|variant| {
+			let span = variant.span;
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote_spanned!(span=> #value => ::core::result::Result::Ok(Self::#name),)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl ::core::convert::TryFrom<&::enum_discrim::__private::num_bigint::BigInt> for #ident {
+			type Error = ::enum_discrim::TryFromError;
+
+			#[inline]
+			fn try_from(value: &::enum_discrim::__private::num_bigint::BigInt) -> ::core::result::Result<Self, Self::Error> {
+				let value = <#repr as ::core::convert::TryFrom<&::enum_discrim::__private::num_bigint::BigInt>>::try_from(value)
+					.map_err(|_| Self::Error::new_out_of_range(::core::stringify!(#ident)))?;
+				match value {
+					#(#arms)*
+					#skipped
+					#reserved
+					_ => ::core::result::Result::Err(Self::Error::new(::core::stringify!(#ident))),
+				}
+			}
+		}
+
+		#[automatically_derived]
+		impl ::core::convert::TryFrom<::enum_discrim::__private::num_bigint::BigInt> for #ident {
+			type Error = ::enum_discrim::TryFromError;
+
+			#[inline]
+			fn try_from(value: ::enum_discrim::__private::num_bigint::BigInt) -> ::core::result::Result<Self, Self::Error> {
+				<Self as ::core::convert::TryFrom<&::enum_discrim::__private::num_bigint::BigInt>>::try_from(&value)
+			}
+		}
+	}
+	.into())
+}
@@ -0,0 +1,216 @@
+//! Provides the code generation for the [`Bits`](crate::derive_bits), [`Specifier`](crate::derive_specifier)
+//! and [`ArbitraryInt`](crate::derive_arbitrary_int) derive macros
+
+use crate::{EnumModel, VariantModel};
+use proc_macro::TokenStream;
+use syn::Ident;
+
+/// Resolves the width given with `#[discrim(bits = N)]`, checking that all discriminants fit in it
+///
+/// Returns the width, the smallest unsigned integer type holding it, and the discriminants as unsuffixed literals of that type.
+fn resolve(
+	model: &EnumModel,
+) -> darling::Result<(u32, proc_macro2::TokenStream, Vec<proc_macro2::Literal>)> {
+	let Some(bits) = model.options.bits else {
+		return Err(
+			darling::Error::custom("missing `#[discrim(bits = N)]` option").with_span(&model.ident),
+		);
+	};
+	let ty = match bits {
+		1..=8 => quote::quote!(u8),
+		9..=16 => quote::quote!(u16),
+		17..=32 => quote::quote!(u32),
+		33..=64 => quote::quote!(u64),
+		65..=128 => quote::quote!(u128),
+		_ => {
+			return Err(
+				darling::Error::custom("`#[discrim(bits = N)]` must be between 1 and 128")
+					.with_span(&model.ident),
+			);
+		}
+	};
+
+	let mut errors = darling::Error::accumulator();
+	let values = model
+		.variants
+		.iter()
+		.filter_map(|variant| match variant.number.parse::<u128>() {
+			Ok(value) if value.checked_shr(bits).unwrap_or_default() == 0 => {
+				Some(proc_macro2::Literal::u128_unsuffixed(value))
+			}
+			_ => {
+				errors.push(
+					darling::Error::custom(format!("discriminant does not fit in {bits} bits"))
+						.with_span(&variant.ident),
+				);
+				None
+			}
+		})
+		.collect();
+	errors.finish_with((bits, ty, values))
+}
+
+/// Returns the identifiers and discriminants of the variants converted from their discriminant, skipping aliases
+fn canonical<'m>(
+	variants: &'m [VariantModel],
+	values: &'m [proc_macro2::Literal],
+) -> (Vec<&'m Ident>, Vec<&'m proc_macro2::Literal>) {
+	variants
+		.iter()
+		.zip(values)
+		.filter(|(variant, _)| variant.is_decoded())
+		.map(|(variant, value)| (&variant.ident, value))
+		.unzip()
+}
+
+/// Derives an impl block containing the `BITS` const and the `from_bits` and `into_bits` const functions expected by bitfield crates
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let (bits, ty, values) = resolve(&model)?;
+	let EnumModel {
+		ident,
+		vis,
+		variants,
+		..
+	} = model;
+
+	let names = variants
+		.iter()
+		.map(|variant| &variant.ident)
+		.collect::<Vec<_>>();
+	let (decoded_names, decoded_values) = canonical(&variants, &values);
+
+	Ok(quote::quote! {
+		impl #ident {
+			/// Number of bits holding the discriminant
+			#vis const BITS: u32 = #bits;
+
+			/// Converts the given bits into a variant
+			///
+			/// # Panics
+			/// Panics if the bits are not the discriminant of any variant.
+			#[inline]
+			#vis const fn from_bits(bits: #ty) -> Self {
+				match bits {
+					#(#decoded_values => Self::#decoded_names,)*
+					_ => ::core::panic!(::core::concat!(
+						"Tried to convert invalid bits into a ",
+						::core::stringify!(#ident),
+					)),
+				}
+			}
+
+			/// Converts the given variant into bits
+			#[inline]
+			#vis const fn into_bits(self) -> #ty {
+				match self {
+					#(Self::#names => #values,)*
+				}
+			}
+		}
+	}
+	.into())
+}
+
+/// Derives an impl `Specifier` block for `modular-bitfield`, holding the discriminant in the declared number of bits
+pub(crate) fn specifier(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let (bits, ty, values) = resolve(&model)?;
+	let EnumModel {
+		ident, variants, ..
+	} = model;
+
+	let names = variants
+		.iter()
+		.map(|variant| &variant.ident)
+		.collect::<Vec<_>>();
+	let (decoded_names, decoded_values) = canonical(&variants, &values);
+	let bits = bits as usize;
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl ::enum_discrim::__private::modular_bitfield::Specifier for #ident {
+			const BITS: usize = #bits;
+			type Bytes = #ty;
+			type InOut = Self;
+
+			#[inline]
+			fn into_bytes(
+				input: Self::InOut,
+			) -> ::core::result::Result<Self::Bytes, ::enum_discrim::__private::modular_bitfield::error::OutOfBounds> {
+				::core::result::Result::Ok(match input {
+					#(Self::#names => #values,)*
+				})
+			}
+
+			#[inline]
+			fn from_bytes(
+				bytes: Self::Bytes,
+			) -> ::core::result::Result<
+				Self::InOut,
+				::enum_discrim::__private::modular_bitfield::error::InvalidBitPattern<Self::Bytes>,
+			> {
+				match bytes {
+					#(#decoded_values => ::core::result::Result::Ok(Self::#decoded_names),)*
+					_ => ::core::result::Result::Err(
+						::enum_discrim::__private::modular_bitfield::error::InvalidBitPattern::new(bytes),
+					),
+				}
+			}
+		}
+	}
+	.into())
+}
+
+/// Derives impl `From<enum> for uN` and `TryFrom<uN> for enum` blocks for the `arbitrary-int` type of the declared number of bits
+pub(crate) fn arbitrary_int(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let (bits, _, values) = resolve(&model)?;
+	if bits.is_power_of_two() && bits >= 8 {
+		return Err(darling::Error::custom(format!(
+			"`arbitrary-int` has no type of {bits} bits, consider the `Into` and `TryFrom` derive macros"
+		))
+		.with_span(&model.ident));
+	}
+	let reserved = model.reserved_arm();
+	let EnumModel {
+		ident, variants, ..
+	} = model;
+
+	let names = variants
+		.iter()
+		.map(|variant| &variant.ident)
+		.collect::<Vec<_>>();
+	let (decoded_names, decoded_values) = canonical(&variants, &values);
+	let target = quote::format_ident!("u{}", bits);
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl ::core::convert::From<#ident> for ::enum_discrim::__private::arbitrary_int::#target {
+			#[inline]
+			fn from(value: #ident) -> Self {
+				// All discriminants were checked to fit at expansion time
+				Self::new(match value {
+					#(#ident::#names => #values,)*
+				})
+			}
+		}
+
+		#[automatically_derived]
+		impl ::core::convert::TryFrom<::enum_discrim::__private::arbitrary_int::#target> for #ident {
+			type Error = ::enum_discrim::TryFromError;
+
+			#[inline]
+			fn try_from(
+				value: ::enum_discrim::__private::arbitrary_int::#target,
+			) -> ::core::result::Result<Self, Self::Error> {
+				match value.value() {
+					#(#decoded_values => ::core::result::Result::Ok(Self::#decoded_names),)*
+					#reserved
+					_ => ::core::result::Result::Err(Self::Error::new(::core::stringify!(#ident))),
+				}
+			}
+		}
+	}
+	.into())
+}
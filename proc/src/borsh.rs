@@ -0,0 +1,89 @@
+//! Provides the code generation for the [`BorshSerialize`](crate::derive_borsh_serialize) and [`BorshDeserialize`](crate::derive_borsh_deserialize) derive macros
+
+use crate::EnumModel;
+use proc_macro::TokenStream;
+
+/// Derives an impl `BorshSerialize` block, writing the discriminant with the width of the primitive representation
+pub(crate) fn serialize(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let portability = model.check_portability()?;
+	let EnumModel {
+		ident,
+		repr,
+		variants,
+		..
+	} = model;
+	let arms = variants
+		.iter()
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(Self::#name => #value,)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#portability
+
+		#[automatically_derived]
+		impl ::enum_discrim::__private::borsh::BorshSerialize for #ident {
+			#[inline]
+			fn serialize<W>(&self, writer: &mut W) -> ::enum_discrim::__private::borsh::io::Result<()>
+			where
+				W: ::enum_discrim::__private::borsh::io::Write,
+			{
+				let value: #repr = match self {
+					#(#arms)*
+				};
+				::enum_discrim::__private::borsh::BorshSerialize::serialize(&value, writer)
+			}
+		}
+	}
+	.into())
+}
+
+/// Derives an impl `BorshDeserialize` block, reading the discriminant with the width of the primitive representation
+pub(crate) fn deserialize(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let portability = model.check_portability()?;
+	let EnumModel {
+		ident,
+		repr,
+		variants,
+		..
+	} = model;
+	let arms = variants
+		.iter()
+		.filter(|variant| variant.is_decoded())
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(#value => ::core::result::Result::Ok(Self::#name),)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#portability
+
+		#[automatically_derived]
+		impl ::enum_discrim::__private::borsh::BorshDeserialize for #ident {
+			#[inline]
+			fn deserialize_reader<R>(reader: &mut R) -> ::enum_discrim::__private::borsh::io::Result<Self>
+			where
+				R: ::enum_discrim::__private::borsh::io::Read,
+			{
+				use ::enum_discrim::__private::borsh::io::{Error, ErrorKind};
+
+				let value: #repr = ::enum_discrim::__private::borsh::BorshDeserialize::deserialize_reader(reader)?;
+				match value {
+					#(#arms)*
+					_ => ::core::result::Result::Err(Error::new(
+						ErrorKind::InvalidData,
+						::core::concat!("Tried to convert an invalid value into a ", ::core::stringify!(#ident)),
+					)),
+				}
+			}
+		}
+	}
+	.into())
+}
@@ -0,0 +1,91 @@
+//! Provides the code generation for the [`Bson`](crate::derive_bson) derive macro
+
+use crate::{EnumModel, PrimitiveRepresentation};
+use proc_macro::TokenStream;
+
+/// Derives impl `From<enum> for Bson` and `TryFrom<Bson> for enum` blocks
+///
+/// The discriminant is stored as an `Int32` if the primitive representation fits in 32 bits,
+/// and as an `Int64` otherwise.
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	use proc_macro2::Literal;
+
+	let EnumModel {
+		ident,
+		repr,
+		repr_c,
+		variants,
+		..
+	} = EnumModel::parse_unit(item)?;
+	let int32 = repr_c
+		|| matches!(
+			repr,
+			PrimitiveRepresentation::u8
+				| PrimitiveRepresentation::u16
+				| PrimitiveRepresentation::i8
+				| PrimitiveRepresentation::i16
+				| PrimitiveRepresentation::i32
+		);
+
+	let mut errors = darling::Error::accumulator();
+	let (from_arms, try_from_arms): (Vec<_>, Vec<_>) = variants
+		.iter()
+		.filter_map(|variant| {
+			let name = &variant.ident;
+			let value = errors.handle(variant.number.parse::<i64>().map_err(|_| {
+				darling::Error::custom("BSON only supports discriminants fitting in an `i64`")
+					.with_span(name)
+			}))?;
+			let bson = if int32 {
+				#[allow(clippy::cast_possible_truncation)]
+				let value = Literal::i32_suffixed(value as i32);
+				quote::quote!(Int32(#value))
+			} else {
+				let value = Literal::i64_suffixed(value);
+				quote::quote!(Int64(#value))
+			};
+			let value = Literal::i64_suffixed(value);
+
+			Some((
+				quote::quote!(#ident::#name => ::enum_discrim::__private::bson::Bson::#bson,),
+				variant
+					.is_decoded()
+					.then(|| quote::quote!(#value => ::core::result::Result::Ok(Self::#name),)),
+			))
+		})
+		.unzip();
+	errors.finish()?;
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl ::core::convert::From<#ident> for ::enum_discrim::__private::bson::Bson {
+			#[inline]
+			fn from(value: #ident) -> Self {
+				match value {
+					#(#from_arms)*
+				}
+			}
+		}
+
+		#[automatically_derived]
+		impl ::core::convert::TryFrom<::enum_discrim::__private::bson::Bson> for #ident {
+			type Error = ::enum_discrim::TryFromError;
+
+			#[inline]
+			fn try_from(
+				value: ::enum_discrim::__private::bson::Bson,
+			) -> ::core::result::Result<Self, Self::Error> {
+				let value = match value {
+					::enum_discrim::__private::bson::Bson::Int32(value) => i64::from(value),
+					::enum_discrim::__private::bson::Bson::Int64(value) => value,
+					_ => return ::core::result::Result::Err(Self::Error::new(::core::stringify!(#ident))),
+				};
+				match value {
+					#(#try_from_arms)*
+					_ => ::core::result::Result::Err(Self::Error::new(::core::stringify!(#ident))),
+				}
+			}
+		}
+	}
+	.into())
+}
@@ -0,0 +1,97 @@
+//! Provides the code generation for the [`BytesCodec`](crate::derive_bytes_codec) derive macro
+
+use crate::{options::Endianness, EnumModel, PrimitiveRepresentation};
+use proc_macro::TokenStream;
+
+/// Derives an impl block containing functions to read and write the discriminant with [`bytes`](https://docs.rs/bytes)'s `Buf` and `BufMut` traits
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	if matches!(
+		model.repr,
+		PrimitiveRepresentation::usize | PrimitiveRepresentation::isize
+	) {
+		return Err(darling::Error::custom(format!(
+			"`{}` discriminants have a platform-dependent width, consider a fixed-width primitive representation",
+			model.repr
+		))
+		.with_span(&model.ident));
+	}
+	let skipped = model.skipped_arm();
+	let reserved = model.reserved_arm();
+	let EnumModel {
+		ident,
+		vis,
+		options,
+		repr,
+		variants,
+		..
+	} = model;
+
+	let suffix = match options.endianness {
+		Some(Endianness::Little)
+			if !matches!(
+				repr,
+				PrimitiveRepresentation::u8 | PrimitiveRepresentation::i8
+			) =>
+		{
+			"_le"
+		}
+		_ => "",
+	};
+	let get = quote::format_ident!("get_{}{}", repr, suffix);
+	let put = quote::format_ident!("put_{}{}", repr, suffix);
+	let get_arms = variants
+		.iter()
+		.filter(|variant| variant.is_decoded())
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(#value => ::core::result::Result::Ok(Self::#name),)
+		});
+	let put_arms = variants.iter().map(|variant| {
+		let name = &variant.ident;
+		let value = &variant.value;
+		quote::quote!(Self::#name => #value,)
+	});
+
+	Ok(quote::quote! {
+		impl #ident {
+			/// Reads a discriminant from the given buffer, advancing it by the width of the primitive representation
+			///
+			/// # Errors
+			/// Returns an error if the value is not a valid discriminant.
+			///
+			/// # Panics
+			/// Panics if the buffer has fewer remaining bytes than the width of the primitive representation.
+			#[inline]
+			#vis fn get_from<B>(buf: &mut B) -> ::core::result::Result<Self, ::enum_discrim::TryFromError>
+			where
+				B: ?Sized + ::enum_discrim::__private::bytes::Buf,
+			{
+				let value: #repr = buf.#get();
+				match value {
+					#(#get_arms)*
+					#skipped
+					#reserved
+					_ => ::core::result::Result::Err(::enum_discrim::TryFromError::new(::core::stringify!(#ident))),
+				}
+			}
+
+			/// Writes the discriminant of the given variant to the given buffer, with the width of the primitive representation
+			///
+			/// # Panics
+			/// Panics if the buffer has less remaining capacity than the width of the primitive representation.
+			#[inline]
+			#vis fn put_to<B>(&self, buf: &mut B)
+			where
+				B: ?Sized + ::enum_discrim::__private::bytes::BufMut,
+			{
+				let value: #repr = match self {
+					#(#put_arms)*
+				};
+				buf.#put(value);
+			}
+		}
+	}
+	.into())
+}
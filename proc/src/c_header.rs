@@ -0,0 +1,53 @@
+//! Provides the code generation for the [`CHeader`](crate::derive_c_header) derive macro
+
+use crate::{EnumModel, PrimitiveRepresentation};
+use proc_macro::TokenStream;
+
+/// Derives an impl block for the `CEnum` trait, describing the enum as C declarations
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let EnumModel {
+		ident,
+		repr,
+		variants,
+		..
+	} = EnumModel::parse_unit(item)?;
+
+	let (c_type, literal) = match repr {
+		PrimitiveRepresentation::u8 => ("uint8_t", "UINT8_C"),
+		PrimitiveRepresentation::u16 => ("uint16_t", "UINT16_C"),
+		PrimitiveRepresentation::u32 => ("uint32_t", "UINT32_C"),
+		PrimitiveRepresentation::u64 => ("uint64_t", "UINT64_C"),
+		PrimitiveRepresentation::usize => ("size_t", ""),
+		PrimitiveRepresentation::i8 => ("int8_t", "INT8_C"),
+		PrimitiveRepresentation::i16 => ("int16_t", "INT16_C"),
+		PrimitiveRepresentation::i32 => ("int32_t", "INT32_C"),
+		PrimitiveRepresentation::i64 => ("int64_t", "INT64_C"),
+		PrimitiveRepresentation::isize => ("ptrdiff_t", ""),
+		PrimitiveRepresentation::u128 | PrimitiveRepresentation::i128 => {
+			return Err(
+				darling::Error::custom(format!("`{repr}` has no equivalent C type"))
+					.with_span(&ident),
+			);
+		}
+	};
+	let variants = variants.iter().map(|variant| {
+		let name = &variant.name;
+		let value = if literal.is_empty() {
+			format!("(({c_type}){})", variant.number)
+		} else {
+			format!("{literal}({})", variant.number)
+		};
+
+		quote::quote!((#name, #value))
+	});
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl ::enum_discrim::c_header::CEnum for #ident {
+			const NAME: &'static str = ::core::stringify!(#ident);
+			const C_TYPE: &'static str = #c_type;
+			const VARIANTS: &'static [(&'static str, &'static str)] = &[#(#variants),*];
+		}
+	}
+	.into())
+}
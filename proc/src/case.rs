@@ -0,0 +1,83 @@
+//! Provides conversions of identifiers between casing conventions
+
+use darling::FromMeta;
+
+/// Converts an `UpperCamelCase` identifier to `snake_case`
+pub(crate) fn snake_case(ident: &str) -> String {
+	let mut snake = String::with_capacity(ident.len() + ident.len() / 2);
+	let mut chars = ident.chars().peekable();
+	let mut previous_lower = false;
+	while let Some(c) = chars.next() {
+		if c.is_uppercase() {
+			let next_lower = chars.peek().is_some_and(|next| next.is_lowercase());
+			if !snake.is_empty() && !snake.ends_with('_') && (previous_lower || next_lower) {
+				snake.push('_');
+			}
+			snake.extend(c.to_lowercase());
+			previous_lower = false;
+		} else {
+			snake.push(c);
+			previous_lower = c.is_lowercase() || c.is_ascii_digit();
+		}
+	}
+	snake
+}
+
+/// Casing convention applied to the names of the variants by `#[discrim(rename_all = "...")]`
+///
+/// The accepted values are the same as `serde`'s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RenameRule {
+	/// `lowercase`
+	Lower,
+	/// `UPPERCASE`
+	Upper,
+	/// `PascalCase`
+	Pascal,
+	/// `camelCase`
+	Camel,
+	/// `snake_case`
+	Snake,
+	/// `SCREAMING_SNAKE_CASE`
+	ScreamingSnake,
+	/// `kebab-case`
+	Kebab,
+	/// `SCREAMING-KEBAB-CASE`
+	ScreamingKebab,
+}
+impl RenameRule {
+	/// Applies the casing convention to an `UpperCamelCase` variant identifier
+	pub(crate) fn apply(self, variant: &str) -> String {
+		match self {
+			Self::Lower => variant.to_lowercase(),
+			Self::Upper => variant.to_uppercase(),
+			Self::Pascal => variant.to_owned(),
+			Self::Camel => {
+				let mut chars = variant.chars();
+				chars.next().map_or_else(String::new, |first| {
+					first.to_lowercase().chain(chars).collect()
+				})
+			}
+			Self::Snake => snake_case(variant),
+			Self::ScreamingSnake => snake_case(variant).to_uppercase(),
+			Self::Kebab => snake_case(variant).replace('_', "-"),
+			Self::ScreamingKebab => snake_case(variant).to_uppercase().replace('_', "-"),
+		}
+	}
+}
+impl FromMeta for RenameRule {
+	#[inline]
+	fn from_string(value: &str) -> darling::Result<Self> {
+		match value {
+			"lowercase" => Ok(Self::Lower),
+			"UPPERCASE" => Ok(Self::Upper),
+			"PascalCase" => Ok(Self::Pascal),
+			"camelCase" => Ok(Self::Camel),
+			"snake_case" => Ok(Self::Snake),
+			"SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnake),
+			"kebab-case" => Ok(Self::Kebab),
+			"SCREAMING-KEBAB-CASE" => Ok(Self::ScreamingKebab),
+			_ => Err(darling::Error::unknown_value(value)),
+		}
+	}
+}
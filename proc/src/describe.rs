@@ -0,0 +1,63 @@
+//! Provides the code generation for the [`Describe`](crate::derive_describe) derive macro
+
+use crate::EnumModel;
+use proc_macro::TokenStream;
+
+/// Generates a const expression of type `EnumInfo`, describing the given enum
+pub(crate) fn enum_info(model: &EnumModel) -> darling::Result<proc_macro2::TokenStream> {
+	use proc_macro2::Literal;
+
+	let ident = &model.ident;
+	let repr = if model.repr_c {
+		"c_int".to_owned()
+	} else {
+		model.repr.to_string()
+	};
+
+	let mut errors = darling::Error::accumulator();
+	let variants = model
+		.variants
+		.iter()
+		.filter_map(|variant| {
+			let name = &variant.name;
+			let value =
+				errors.handle(variant.number.parse().map(Literal::i128_suffixed).map_err(
+					|_| {
+						darling::Error::custom(
+							"enum metadata only supports discriminants fitting in an `i128`",
+						)
+						.with_span(&variant.ident)
+					},
+				))?;
+
+			Some(quote::quote!(::enum_discrim::VariantInfo::new(#name, #value)))
+		})
+		.collect::<Vec<_>>();
+	errors.finish()?;
+
+	Ok(quote::quote! {
+		::enum_discrim::EnumInfo::new(::core::stringify!(#ident), #repr, &[#(#variants),*])
+	})
+}
+
+/// Derives an impl block for the `Describe` trait, returning the static metadata of the enum
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse(item)?;
+	let info = enum_info(&model)?;
+	let EnumModel {
+		ident, generics, ..
+	} = &model;
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl #impl_generics ::enum_discrim::Describe for #ident #ty_generics #where_clause {
+			#[inline]
+			fn enum_info() -> &'static ::enum_discrim::EnumInfo {
+				static INFO: ::enum_discrim::EnumInfo = #info;
+				&INFO
+			}
+		}
+	}
+	.into())
+}
@@ -1,79 +1,619 @@
-//! Provides [the parsing structs](DiscriminantsInput) for the [`Discriminants`](crate::derive_discriminants) derive macro
+//! Provides the code generation for the [`Discriminants`](crate::derive_discriminants) derive macro
 
-use darling::{ast::Data, util::SpannedValue, FromAttributes, FromDeriveInput};
+use crate::{EnumModel, VariantModel};
 use proc_macro::TokenStream;
-use syn::{Attribute, Generics, Ident, Variant, Visibility};
-
-/// Parsing struct for the [`Discriminants`](crate::derive_discriminants) derive macro
-#[derive(Debug, FromDeriveInput)]
-#[darling(supports(enum_any), forward_attrs(repr))]
-struct DiscriminantsInput {
-	/// Enum identifier
-	ident: Ident,
-	/// Enum visibility
-	vis: Visibility,
-	/// Enum generics
-	generics: Generics,
-	/// Variants contained in the enum
-	data: Data<SpannedValue<Variant>, ()>,
-	/// Forwarded attributes
-	attrs: Vec<Attribute>,
+use syn::{ext::IdentExt, Fields, Path, Visibility};
+
+/// Computes the type and the match arms of the `category` function, if any variant has a `#[discrim(category = "...")]` option
+///
+/// The type is the given path without its last segment.
+fn category(
+	variants: &[VariantModel],
+) -> darling::Result<Option<(Path, Vec<proc_macro2::TokenStream>)>> {
+	let Some(first) = variants
+		.iter()
+		.find_map(|variant| variant.options.category.as_ref())
+	else {
+		return Ok(None);
+	};
+	if first.segments.len() < 2 {
+		return Err(darling::Error::custom("expected a path to an enum variant").with_span(first));
+	}
+	let ty = Path {
+		leading_colon: first.leading_colon,
+		segments: first
+			.segments
+			.iter()
+			.take(first.segments.len() - 1)
+			.cloned()
+			.collect(),
+	};
+
+	let mut errors = darling::Error::accumulator();
+	let arms = variants
+		.iter()
+		.filter_map(|variant| {
+			let span = variant.span;
+			let name = &variant.ident;
+			match &variant.options.category {
+				Some(category) => {
+					Some(quote::quote_spanned!(span=> Self::#name { .. } => #category,))
+				}
+				None => {
+					errors.push(
+						darling::Error::custom(
+							"missing `#[discrim(category = \"...\")]`, as other variants have one",
+						)
+						.with_span(name),
+					);
+					None
+				}
+			}
+		})
+		.collect();
+	errors.finish_with(Some((ty, arms)))
 }
 
-/// Derives an impl block containing the discriminants of all enum variants as consts
-pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
-	use crate::PrimitiveRepresentation;
-	use syn::DeriveInput;
+/// Generates the `NEXT_FREE_DISCRIMINANT` const, if the `#[discrim(next_free)]` option is given
+///
+/// The free value is neither a discriminant nor within a reserved range.
+fn next_free(model: &EnumModel) -> darling::Result<Option<proc_macro2::TokenStream>> {
+	use crate::options::NextFree;
 
-	let item: DeriveInput = syn::parse(item)?;
-	let DiscriminantsInput {
+	let EnumModel {
 		ident,
 		vis,
+		options,
+		repr,
+		variants,
+		..
+	} = model;
+	let Some(next_free) = &options.next_free else {
+		return Ok(None);
+	};
+	model.require_numbers("`#[discrim(next_free)]`")?;
+
+	let mut errors = darling::Error::accumulator();
+	let mut taken = variants
+		.iter()
+		.filter_map(|variant| match variant.number.parse::<i128>() {
+			Ok(number) => Some(number),
+			Err(_) => {
+				errors.push(
+					darling::Error::custom(
+						"discriminant is too large to compute the next free one",
+					)
+					.with_span(&variant.ident),
+				);
+				None
+			}
+		})
+		.collect::<Vec<_>>();
+	errors.finish()?;
+	taken.extend(
+		variants
+			.iter()
+			.flat_map(|variant| &variant.options.aliases)
+			.map(|alias| alias.0),
+	);
+	taken.sort_unstable();
+
+	let start = match (
+		next_free.clone().unwrap_or_default(),
+		taken.first(),
+		taken.last(),
+	) {
+		(_, None, _) | (_, _, None) => Some(0),
+		(NextFree::Max, _, Some(max)) => max.checked_add(1),
+		(NextFree::Gap, Some(min), _) => min.checked_add(1),
+	};
+	let free = start.and_then(|start| {
+		(start..=i128::MAX).find(|candidate| {
+			taken.binary_search(candidate).is_err()
+				&& !options
+					.reserved
+					.iter()
+					.any(|range| range.contains(*candidate))
+		})
+	});
+	let Some(free) = free.filter(|free| repr.fits(&free.to_string())) else {
+		return Err(
+			darling::Error::custom(format!("no free discriminant is left in `{repr}`"))
+				.with_span(ident),
+		);
+	};
+
+	let repr_ty = model.repr_ty();
+	let value = crate::options::int_literal(free);
+	Ok(Some(quote::quote! {
+		/// Smallest value that is free to be assigned to a new variant
+		#vis const NEXT_FREE_DISCRIMINANT: #repr_ty = #value;
+	}))
+}
+
+/// Generates the `as_*` and `into_*` accessors of the given variant, if it has fields
+///
+/// The accessors return the only field of the variant, or a tuple of all its fields.
+fn accessors(vis: &Visibility, variant: &VariantModel) -> Option<proc_macro2::TokenStream> {
+	if variant.unit {
+		return None;
+	}
+
+	let span = variant.span;
+	let name = &variant.ident;
+	let snake = crate::case::snake_case(&name.unraw().to_string());
+	let as_fn = quote::format_ident!("as_{}", snake, span = name.span());
+	let into_fn = quote::format_ident!("into_{}", snake, span = name.span());
+	let (bindings, types): (Vec<_>, Vec<_>) = variant
+		.fields
+		.iter()
+		.enumerate()
+		.map(|(index, field)| {
+			let binding = field
+				.ident
+				.clone()
+				.unwrap_or_else(|| quote::format_ident!("_{}", index));
+			(binding, &field.ty)
+		})
+		.unzip();
+	let pattern = match &variant.fields {
+		Fields::Named(_) => quote::quote!(Self::#name { #(#bindings),* }),
+		_ => quote::quote!(Self::#name(#(#bindings),*)),
+	};
+	let (ref_ty, ty, value) = match (bindings.as_slice(), types.as_slice()) {
+		([binding], [ty]) => (
+			quote::quote!(&#ty),
+			quote::quote!(#ty),
+			quote::quote!(#binding),
+		),
+		_ => (
+			quote::quote!((#(&#types,)*)),
+			quote::quote!((#(#types,)*)),
+			quote::quote!((#(#bindings,)*)),
+		),
+	};
+	let as_doc = format!(
+		"Returns references to the fields of the given variant, if it is [{name}](Self::{name})"
+	);
+	let into_doc =
+		format!("Returns the fields of the given variant, if it is [{name}](Self::{name})");
+
+	Some(quote::quote_spanned! {span=>
+		#[doc = #as_doc]
+		#[inline]
+		#vis fn #as_fn(&self) -> ::core::option::Option<#ref_ty> {
+			match self {
+				#pattern => ::core::option::Option::Some(#value),
+				#[allow(unreachable_patterns)]
+				_ => ::core::option::Option::None,
+			}
+		}
+
+		#[doc = #into_doc]
+		#[inline]
+		#vis fn #into_fn(self) -> ::core::option::Option<#ty> {
+			match self {
+				#pattern => ::core::option::Option::Some(#value),
+				#[allow(unreachable_patterns)]
+				_ => ::core::option::Option::None,
+			}
+		}
+	})
+}
+
+/// Generates a [`Debug`] impl block rendering each variant as `Name(=0x2A)`, followed by its fields
+///
+/// Each type parameter of the enum is bound by [`Debug`].
+fn debug(model: &EnumModel) -> proc_macro2::TokenStream {
+	let EnumModel {
+		ident,
 		generics,
-		data,
-		attrs,
-	} = DiscriminantsInput::from_derive_input(&item)?;
-	let Data::Enum(data) = data else {
-		unreachable!()
+		variants,
+		..
+	} = model;
+
+	let mut generics = generics.clone();
+	let bounded = generics
+		.type_params()
+		.map(|param| param.ident.clone())
+		.collect::<Vec<_>>();
+	let where_clause = generics.make_where_clause();
+	for param in bounded {
+		where_clause
+			.predicates
+			.push(syn::parse_quote!(#param: ::core::fmt::Debug));
+	}
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+	let arms = variants.iter().map(|variant| {
+		let span = variant.span;
+		let name = &variant.ident;
+		let label = format!(
+			"{}(={})",
+			name.unraw(),
+			crate::attribute::hex(&variant.number)
+		);
+		let bindings = variant
+			.fields
+			.iter()
+			.enumerate()
+			.map(|(index, field)| {
+				field
+					.ident
+					.clone()
+					.unwrap_or_else(|| quote::format_ident!("_{}", index))
+			})
+			.collect::<Vec<_>>();
+
+		match &variant.fields {
+			Fields::Named(_) => {
+				let keys = bindings.iter().map(|binding| binding.unraw().to_string());
+				quote::quote_spanned! {span=>
+					Self::#name { #(#bindings),* } => f
+						.debug_struct(#label)
+						#(.field(#keys, #bindings))*
+						.finish(),
+				}
+			}
+			Fields::Unnamed(_) => quote::quote_spanned! {span=>
+				Self::#name(#(#bindings),*) => f
+					.debug_tuple(#label)
+					#(.field(#bindings))*
+					.finish(),
+			},
+			Fields::Unit => quote::quote_spanned!(span=> Self::#name => f.write_str(#label),),
+		}
+	});
+	// Zero-variant enums cannot be matched through a reference
+	let scrutinee = if variants.is_empty() {
+		quote::quote!(*self)
+	} else {
+		quote::quote!(self)
 	};
 
-	let where_clause = &generics.where_clause;
-	let repr = PrimitiveRepresentation::from_attributes(&attrs)?;
-	/// Generates a match arm for each given type
-	macro_rules! discriminants_with_ty {
-		($( $ty:ident ),* $(,)?) => {
-			match repr {$(
-				PrimitiveRepresentation::$ty => crate::scan_variants::<$ty>(&data)?
-					.into_iter()
-					.map(|(variant, value)| {
-						let span = variant.span();
-						let name = quote::format_ident!("{}_D", variant.ident);
-						let doc = format!("Discriminant of the [{0}](Self::{0}) variant", variant.ident);
-
-						quote::quote_spanned! {span=>
-							#[doc = #doc]
-							#vis const #name: #repr = #value;
-						}
-					})
-					.collect::<Vec<_>>(),
-			)*}
-		};
+	quote::quote! {
+		#[automatically_derived]
+		impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #where_clause {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+				match #scrutinee {
+					#(#arms)*
+				}
+			}
+		}
 	}
-	let discriminants =
-		discriminants_with_ty![u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize];
+}
+
+/// Derives an impl block containing the discriminants of all enum variants as consts
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_opaque(item)?;
+	crate::manifest::write(&model)?;
+	let EnumModel {
+		ident,
+		vis,
+		generics,
+		options,
+		variants,
+		..
+	} = &model;
+
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	let repr_ty = model.repr_ty();
+	let discriminants = variants
+		.iter()
+		.filter(|variant| !variant.options.skip)
+		.map(|variant| {
+			let span = variant.span;
+			let value = &variant.value;
+			let name = variant
+				.options
+				.const_name
+				.clone()
+				.unwrap_or_else(|| quote::format_ident!("{}_D", variant.ident));
+			let doc = format!(
+				"Discriminant of the [{0}](Self::{0}) variant",
+				variant.ident
+			);
+			let docs = variant.forwarded_docs();
+
+			quote::quote_spanned! {span=>
+				#[doc = #doc]
+				#docs
+				#vis const #name: #repr_ty = #value;
+			}
+		})
+		.collect::<Vec<_>>();
+	let name_arms = variants
+		.iter()
+		.filter(|variant| variant.is_decoded())
+		.map(|variant| {
+			let span = variant.span;
+			let value = &variant.value;
+			let variant_name = &variant.name;
+
+			quote::quote_spanned!(span=> #value => ::core::option::Option::Some(#variant_name),)
+		})
+		.collect::<Vec<_>>();
+	// Zero-variant enums cannot declare `#[repr(inttype)]`, and must never be read
+	let discriminant = if options.logical_repr.is_some() || variants.is_empty() {
+		let arms = variants.iter().map(|variant| {
+			let span = variant.span;
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote_spanned!(span=> Self::#name { .. } => #value,)
+		});
+
+		quote::quote! {
+			match *self {
+				#(#arms)*
+			}
+		}
+	} else {
+		quote::quote! {
+			// SAFETY: Our macro resolves to `compile_error!` if `#[repr(inttype)]` or `#[repr(C)]` is missing
+			unsafe { ::core::ptr::from_ref(self).cast::<#repr_ty>().read() }
+		}
+	};
+	let category = category(variants)?.map(|(ty, arms)| {
+		quote::quote! {
+			/// Returns the category of the given variant
+			#vis fn category(&self) -> #ty {
+				match self {
+					#(#arms)*
+				}
+			}
+		}
+	});
+	let predicates = options
+		.predicates
+		.then(|| {
+			variants.iter().map(|variant| {
+				let span = variant.span;
+				let name = &variant.ident;
+				let predicate = quote::format_ident!(
+					"is_{}",
+					crate::case::snake_case(&name.unraw().to_string()),
+					span = name.span()
+				);
+				let doc = format!("Returns whether the given variant is [{name}](Self::{name})");
+
+				quote::quote_spanned! {span=>
+					#[doc = #doc]
+					#[inline]
+					#vis const fn #predicate(&self) -> bool {
+						::core::matches!(self, Self::#name { .. })
+					}
+				}
+			})
+		})
+		.into_iter()
+		.flatten()
+		.collect::<Vec<_>>();
+	let accessors = options
+		.accessors
+		.then(|| {
+			variants
+				.iter()
+				.filter_map(|variant| accessors(vis, variant))
+		})
+		.into_iter()
+		.flatten()
+		.collect::<Vec<_>>();
+	let debug = options.debug.then(|| debug(&model));
+	let next_free = next_free(&model)?;
+	let newtype = options.newtype_ident(ident).map(|newtype| {
+		let doc = format!("Discriminant of [`{ident}`]");
+
+		quote::quote! {
+			#[doc = #doc]
+			#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+			#[repr(transparent)]
+			#vis struct #newtype(
+				/// Raw discriminant
+				pub #repr_ty,
+			);
+
+			#[automatically_derived]
+			impl ::core::fmt::Display for #newtype {
+				#[inline]
+				fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+					::core::fmt::Display::fmt(&self.0, f)
+				}
+			}
+
+			#[automatically_derived]
+			impl ::core::convert::From<#newtype> for #repr_ty {
+				#[inline]
+				fn from(value: #newtype) -> Self {
+					value.0
+				}
+			}
+
+			#[automatically_derived]
+			impl #impl_generics ::core::convert::From<&#ident #ty_generics> for #newtype #where_clause {
+				#[inline]
+				fn from(value: &#ident #ty_generics) -> Self {
+					Self(value.discriminant())
+				}
+			}
+
+			#[automatically_derived]
+			impl #impl_generics ::core::convert::From<#ident #ty_generics> for #newtype #where_clause {
+				#[inline]
+				fn from(value: #ident #ty_generics) -> Self {
+					Self(value.discriminant())
+				}
+			}
+		}
+	});
+
+	let kind = options.kind.as_ref().map(|kind| {
+		let kind = kind
+			.clone()
+			.unwrap_or_else(|| quote::format_ident!("{}Kind", ident));
+		let doc = format!("Kind of [`{ident}`], without the fields of its variants");
+		// Logical discriminants may be shared by several variants, so they are only returned by `discriminant`
+		let explicit = options.logical_repr.is_none() && !variants.is_empty();
+		// The C types are not valid representations, so `#[repr(C)]` is mirrored as is
+		let repr = explicit.then(|| {
+			if model.repr_c {
+				quote::quote!(#[repr(C)])
+			} else {
+				let repr = &model.repr;
+				quote::quote!(#[repr(#repr)])
+			}
+		});
+		let kind_variants = variants.iter().map(|variant| {
+			let span = variant.span;
+			let name = &variant.ident;
+			let docs = &variant.docs;
+			let value = explicit.then(|| {
+				let value = &variant.value;
+				quote::quote!(= #value)
+			});
+
+			quote::quote_spanned! {span=>
+				#(#docs)*
+				#name #value,
+			}
+		});
+		let discriminant_arms = variants.iter().map(|variant| {
+			let span = variant.span;
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote_spanned!(span=> Self::#name => #value,)
+		});
+		let from_arms = variants.iter().map(|variant| {
+			let span = variant.span;
+			let name = &variant.ident;
+			quote::quote_spanned!(span=> #ident::#name { .. } => Self::#name,)
+		});
+
+		quote::quote! {
+			#[doc = #doc]
+			#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+			#repr
+			#vis enum #kind {
+				#(#kind_variants)*
+			}
+
+			impl #kind {
+				/// Returns the discriminant of the given kind, which is the one of the matching variant
+				#vis const fn discriminant(self) -> #repr_ty {
+					match self {
+						#(#discriminant_arms)*
+					}
+				}
+			}
+
+			#[automatically_derived]
+			impl #impl_generics ::core::convert::From<&#ident #ty_generics> for #kind #where_clause {
+				#[inline]
+				fn from(value: &#ident #ty_generics) -> Self {
+					match *value {
+						#(#from_arms)*
+					}
+				}
+			}
+
+			#[automatically_derived]
+			impl #impl_generics ::core::convert::From<#ident #ty_generics> for #kind #where_clause {
+				#[inline]
+				fn from(value: #ident #ty_generics) -> Self {
+					Self::from(&value)
+				}
+			}
+		}
+	});
+
+	let markers = if options.markers {
+		if !generics.params.is_empty() {
+			return Err(darling::Error::custom(
+				"`#[discrim(markers)]` is not supported on generic enums",
+			)
+			.with_span(&generics.params));
+		}
+
+		let markers = variants
+			.iter()
+			.filter(|variant| !variant.options.skip)
+			.map(|variant| {
+				let span = variant.span;
+				let value = &variant.value;
+				// Prefixed with the enum, so that the markers of different enums do not collide
+				let marker = quote::format_ident!("{}{}Variant", ident, variant.ident);
+				let doc = format!("Marker of the [{0}]({ident}::{0}) variant", variant.ident);
+				let docs = variant.forwarded_docs();
+
+				quote::quote_spanned! {span=>
+					#[doc = #doc]
+					#docs
+					#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+					#vis struct #marker;
+
+					impl #marker {
+						/// Discriminant of the variant
+						#vis const DISCRIMINANT: #repr_ty = #value;
+					}
+
+					#[automatically_derived]
+					impl ::enum_discrim::VariantMarker for #marker {
+						type Enum = #ident;
+						type Repr = #repr_ty;
+
+						const DISCRIMINANT: #repr_ty = #value;
+					}
+				}
+			});
+		Some(quote::quote!(#(#markers)*))
+	} else {
+		None
+	};
 
 	Ok(quote::quote! {
 		#[automatically_derived]
 		#[allow(non_upper_case_globals)]
-		impl #generics #ident #generics #where_clause {
+		impl #impl_generics #ident #ty_generics #where_clause {
 			/// Returns the discriminant of the given variant
-			#vis fn discriminant(&self) -> #repr {
-				// SAFETY: Our macro resolves to `compile_error!` if `#[repr]` is missing
-				unsafe { <*const Self>::from(self).cast::<#repr>().read() }
+			#vis fn discriminant(&self) -> #repr_ty {
+				#discriminant
+			}
+
+			#category
+
+			#(#predicates)*
+
+			#(#accessors)*
+
+			/// Returns the name of the variant with the given discriminant, if any
+			#vis const fn name_of(value: #repr_ty) -> ::core::option::Option<&'static str> {
+				match value {
+					#(#name_arms)*
+					_ => ::core::option::Option::None,
+				}
 			}
 
 			#(#discriminants)*
+
+			#next_free
 		}
+
+		#[automatically_derived]
+		impl #impl_generics ::enum_discrim::Discriminant for #ident #ty_generics #where_clause {
+			type Repr = #repr_ty;
+
+			#[inline]
+			fn discriminant(&self) -> Self::Repr {
+				Self::discriminant(self)
+			}
+		}
+
+		#debug
+
+		#newtype
+
+		#kind
+
+		#markers
 	}
 	.into())
 }
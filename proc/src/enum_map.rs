@@ -0,0 +1,72 @@
+//! Provides the code generation for the [`EnumMap`](crate::derive_enum_map) derive macro
+
+use darling::{ast::Data, util::SpannedValue, FromDeriveInput};
+use proc_macro::TokenStream;
+use syn::{Ident, Variant};
+
+/// Parsing struct for the [`EnumMap`](crate::derive_enum_map) derive macro
+#[derive(Debug, FromDeriveInput)]
+#[darling(supports(enum_unit))]
+struct EnumMapInput {
+	/// Enum identifier
+	ident: Ident,
+	/// Variants contained in the enum
+	data: Data<SpannedValue<Variant>, ()>,
+}
+
+/// Derives impl `Enum` and `EnumArray` blocks for `enum-map`, indexing the variants by ordinal
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	use syn::DeriveInput;
+
+	let item: DeriveInput = syn::parse(item)?;
+	let EnumMapInput { ident, data } = EnumMapInput::from_derive_input(&item)?;
+	let Data::Enum(data) = data else {
+		unreachable!()
+	};
+
+	let length = data.len();
+	let (into_arms, from_arms): (Vec<_>, Vec<_>) = data
+		.iter()
+		.enumerate()
+		.map(|(ordinal, variant)| {
+			let span = variant.span();
+			let name = &variant.ident;
+			(
+				quote::quote_spanned!(span=> Self::#name => #ordinal,),
+				quote::quote_spanned!(span=> #ordinal => Self::#name,),
+			)
+		})
+		.unzip();
+
+	Ok(quote::quote! {
+		const _: () = {
+			use ::enum_discrim::__private::enum_map::{Enum, EnumArray};
+
+			#[automatically_derived]
+			impl Enum for #ident {
+				const LENGTH: usize = #length;
+
+				#[inline]
+				fn from_usize(value: usize) -> Self {
+					match value {
+						#(#from_arms)*
+						_ => ::core::panic!("index out of range for `{}`", ::core::stringify!(#ident)),
+					}
+				}
+
+				#[inline]
+				fn into_usize(self) -> usize {
+					match self {
+						#(#into_arms)*
+					}
+				}
+			}
+
+			#[automatically_derived]
+			impl<V> EnumArray<V> for #ident {
+				type Array = [V; #length];
+			}
+		};
+	}
+	.into())
+}
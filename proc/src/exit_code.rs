@@ -0,0 +1,45 @@
+//! Provides the code generation for the [`ExitCode`](crate::derive_exit_code) derive macro
+
+use crate::{EnumModel, PrimitiveRepresentation};
+use proc_macro::TokenStream;
+
+/// Derives an impl `From<enum> for ExitCode` block, exiting with the discriminant as status code
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let EnumModel {
+		ident,
+		repr,
+		repr_c,
+		variants,
+		..
+	} = model;
+	if repr_c || repr != PrimitiveRepresentation::u8 {
+		return Err(darling::Error::custom(
+			"exit codes require a `#[repr(u8)]` enum, as only the lowest 8 bits of a status are portable",
+		)
+		.with_span(&ident));
+	}
+
+	let arms = variants
+		.iter()
+		.map(|variant| {
+			let span = variant.span;
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote_spanned!(span=> #ident::#name => #value,)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl ::core::convert::From<#ident> for ::enum_discrim::__private::std::process::ExitCode {
+			#[inline]
+			fn from(value: #ident) -> Self {
+				Self::from(match value {
+					#(#arms)*
+				})
+			}
+		}
+	}
+	.into())
+}
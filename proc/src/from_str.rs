@@ -0,0 +1,54 @@
+//! Provides the code generation for the [`FromStr`](crate::derive_from_str) derive macro
+
+use crate::NamedEnum;
+use proc_macro::TokenStream;
+
+/// Derives an impl [`FromStr`](std::str::FromStr) block, parsing the names of the variants
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let NamedEnum {
+		ident,
+		options,
+		variants,
+	} = NamedEnum::parse(item)?;
+
+	let body = if options.ascii_case_insensitive {
+		let checks = variants.iter().map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.name;
+			quote::quote! {
+				if s.eq_ignore_ascii_case(#value) {
+					return ::core::result::Result::Ok(Self::#name);
+				}
+			}
+		});
+		quote::quote! {
+			#(#checks)*
+			::core::result::Result::Err(Self::Err::new(::core::stringify!(#ident)))
+		}
+	} else {
+		let arms = variants.iter().map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.name;
+			quote::quote!(#value => ::core::result::Result::Ok(Self::#name),)
+		});
+		quote::quote! {
+			match s {
+				#(#arms)*
+				_ => ::core::result::Result::Err(Self::Err::new(::core::stringify!(#ident))),
+			}
+		}
+	};
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl ::core::str::FromStr for #ident {
+			type Err = ::enum_discrim::FromStrError;
+
+			#[inline]
+			fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+				#body
+			}
+		}
+	}
+	.into())
+}
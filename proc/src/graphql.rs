@@ -0,0 +1,134 @@
+//! Provides the code generation for the [`GraphQLEnum`](crate::derive_graphql) derive macro
+
+use crate::NamedEnum;
+use proc_macro::TokenStream;
+
+/// Derives impl blocks for the `InputType` and `OutputType` traits of `async-graphql`
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let NamedEnum {
+		ident, variants, ..
+	} = NamedEnum::parse(item)?;
+	let names = variants
+		.iter()
+		.map(|variant| &variant.name)
+		.collect::<Vec<_>>();
+	let variants = variants
+		.iter()
+		.map(|variant| &variant.ident)
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		const _: () = {
+			use ::enum_discrim::__private::alloc::{borrow::Cow, string::{String, ToString}, vec::Vec};
+			use ::enum_discrim::__private::async_graphql::{
+				indexmap::IndexMap,
+				registry::{Deprecation, MetaEnumValue, MetaType, MetaTypeId, Registry},
+				ContextSelectionSet, InputType, InputValueError, InputValueResult, Name, OutputType,
+				Positioned, ServerResult, Value,
+				parser::types::Field,
+			};
+
+			impl #ident {
+				/// Registers the GraphQL enum type
+				fn __graphql_create_type_info(registry: &mut Registry) -> String {
+					registry.create_input_type::<Self, _>(MetaTypeId::Enum, |_registry| MetaType::Enum {
+						name: ::core::stringify!(#ident).to_string(),
+						description: ::core::option::Option::None,
+						enum_values: {
+							let mut enum_values = IndexMap::new();
+							#(
+								enum_values.insert(#names.to_string(), MetaEnumValue {
+									name: #names.to_string(),
+									description: ::core::option::Option::None,
+									deprecation: Deprecation::NoDeprecated,
+									visible: ::core::option::Option::None,
+									inaccessible: false,
+									tags: Vec::new(),
+									directive_invocations: Vec::new(),
+								});
+							)*
+							enum_values
+						},
+						visible: ::core::option::Option::None,
+						inaccessible: false,
+						tags: Vec::new(),
+						rust_typename: ::core::option::Option::Some(::core::any::type_name::<Self>()),
+						directive_invocations: Vec::new(),
+						requires_scopes: Vec::new(),
+					})
+				}
+
+				/// Returns the GraphQL value of the variant
+				fn __graphql_value(&self) -> Value {
+					Value::Enum(Name::new(match self {
+						#(Self::#variants => #names,)*
+					}))
+				}
+			}
+
+			#[automatically_derived]
+			impl InputType for #ident {
+				type RawValueType = Self;
+
+				#[inline]
+				fn type_name() -> Cow<'static, str> {
+					Cow::Borrowed(::core::stringify!(#ident))
+				}
+
+				#[inline]
+				fn create_type_info(registry: &mut Registry) -> String {
+					Self::__graphql_create_type_info(registry)
+				}
+
+				fn parse(value: ::core::option::Option<Value>) -> InputValueResult<Self> {
+					let value = value.unwrap_or_default();
+					let name = match &value {
+						Value::Enum(name) => name.as_str(),
+						Value::String(name) => name.as_str(),
+						_ => return ::core::result::Result::Err(InputValueError::expected_type(value)),
+					};
+					match name {
+						#(#names => ::core::result::Result::Ok(Self::#variants),)*
+						name => ::core::result::Result::Err(InputValueError::custom(::enum_discrim::__private::alloc::format!(
+							"Enumeration type does not contain value \"{}\"",
+							name,
+						))),
+					}
+				}
+
+				#[inline]
+				fn to_value(&self) -> Value {
+					self.__graphql_value()
+				}
+
+				#[inline]
+				fn as_raw_value(&self) -> ::core::option::Option<&Self::RawValueType> {
+					::core::option::Option::Some(self)
+				}
+			}
+
+			#[automatically_derived]
+			impl OutputType for #ident {
+				#[inline]
+				fn type_name() -> Cow<'static, str> {
+					Cow::Borrowed(::core::stringify!(#ident))
+				}
+
+				#[inline]
+				fn create_type_info(registry: &mut Registry) -> String {
+					Self::__graphql_create_type_info(registry)
+				}
+
+				#[inline]
+				async fn resolve(
+					&self,
+					_ctx: &ContextSelectionSet<'_>,
+					_field: &Positioned<Field>,
+				) -> ServerResult<Value> {
+					::core::result::Result::Ok(self.__graphql_value())
+				}
+			}
+		};
+	}
+	.into())
+}
@@ -1,37 +1,204 @@
-//! Provides [the parsing struct](IntoInput) for the [`TryFrom`](crate::derive_try_from) derive macro
+//! Provides the code generation for the [`Into`](crate::derive_into) derive macro
 
-use darling::{FromAttributes, FromDeriveInput};
+use crate::{EnumModel, PrimitiveRepresentation};
 use proc_macro::TokenStream;
-use syn::{Attribute, Ident};
-
-/// Parsing struct for the [`Into`](crate::derive_into) derive macro
-#[derive(Debug, FromDeriveInput)]
-#[darling(supports(enum_unit), forward_attrs(repr))]
-struct IntoInput {
-	/// Enum identifier
-	ident: Ident,
-	/// Forwarded attributes
-	attrs: Vec<Attribute>,
-}
 
 /// Derives a [`Into<repr>`] impl block
 pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
-	use crate::PrimitiveRepresentation;
-	use syn::DeriveInput;
+	let model = EnumModel::parse_unit_opaque(item)?;
+	let EnumModel {
+		ident,
+		options,
+		variants,
+		..
+	} = &model;
+
+	let repr_ty = model.repr_ty();
+	let body = if options.logical_repr.is_some() {
+		let arms = variants.iter().map(|variant| {
+			let span = variant.span;
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote_spanned!(span=> #ident::#name => #value,)
+		});
 
-	let item: DeriveInput = syn::parse(item)?;
-	let IntoInput { ident, attrs } = IntoInput::from_derive_input(&item)?;
+		quote::quote! {
+			match value {
+				#(#arms)*
+			}
+		}
+	} else {
+		quote::quote!(value as #repr_ty)
+	};
 
-	let repr = PrimitiveRepresentation::from_attributes(&attrs)?;
+	if !options.narrow.is_empty() {
+		model.require_numbers("`#[discrim(narrow = \"...\")]`")?;
+	}
+	let mut errors = darling::Error::accumulator();
+	let narrow = options
+		.narrow
+		.iter()
+		.filter_map(|&target| {
+			if matches!(
+				target,
+				PrimitiveRepresentation::usize | PrimitiveRepresentation::isize
+			) {
+				errors.push(
+					darling::Error::custom(format!(
+						"cannot narrow into `{target}`, as its width is platform-dependent"
+					))
+					.with_span(ident),
+				);
+				return None;
+			}
+			if target == model.repr {
+				errors.push(
+					darling::Error::custom(format!(
+						"`{target}` is already the primitive representation"
+					))
+					.with_span(ident),
+				);
+				return None;
+			}
+			for variant in variants
+				.iter()
+				.filter(|variant| !target.fits(&variant.number))
+			{
+				errors.push(
+					darling::Error::custom(format!("discriminant does not fit in `{target}`"))
+						.with_span(&variant.ident),
+				);
+			}
+
+			Some(quote::quote! {
+				#[automatically_derived]
+				impl ::core::convert::From<#ident> for #target {
+					#[inline]
+					#[allow(
+						clippy::cast_possible_truncation,
+						clippy::cast_possible_wrap,
+						clippy::cast_sign_loss
+					)]
+					fn from(value: #ident) -> Self {
+						// All discriminants were checked to fit at expansion time
+						<#repr_ty as ::core::convert::From<#ident>>::from(value) as #target
+					}
+				}
+			})
+		})
+		.collect::<Vec<_>>();
+	errors.finish()?;
+
+	let into_char = if options.char {
+		if model.repr_c
+			|| !matches!(
+				model.repr,
+				PrimitiveRepresentation::u8 | PrimitiveRepresentation::u32
+			) {
+			return Err(darling::Error::custom(
+				"`#[discrim(char)]` requires a `#[repr(u8)]` or `#[repr(u32)]` enum",
+			)
+			.with_span(ident));
+		}
+		model.require_numbers("`#[discrim(char)]`")?;
+
+		let mut errors = darling::Error::accumulator();
+		let arms = variants
+			.iter()
+			.filter_map(|variant| {
+				let span = variant.span;
+				let name = &variant.ident;
+				let value = variant
+					.number
+					.parse()
+					.ok()
+					.and_then(char::from_u32)
+					.map(proc_macro2::Literal::character);
+				let Some(value) = value else {
+					errors.push(
+						darling::Error::custom("discriminant is not a valid Unicode scalar value")
+							.with_span(name),
+					);
+					return None;
+				};
+				Some(quote::quote_spanned!(span=> #ident::#name => #value,))
+			})
+			.collect::<Vec<_>>();
+		errors.finish()?;
+
+		Some(quote::quote! {
+			#[automatically_derived]
+			impl ::core::convert::From<#ident> for char {
+				#[inline]
+				fn from(value: #ident) -> Self {
+					match value {
+						#(#arms)*
+					}
+				}
+			}
+		})
+	} else {
+		None
+	};
+
+	let c_types = model.c_types()?;
+	if !c_types.is_empty() {
+		model.require_numbers("`#[discrim(c_int)]` and `#[discrim(c_uint)]`")?;
+	}
+	let mut errors = darling::Error::accumulator();
+	let into_c = c_types
+		.into_iter()
+		.map(|(c_ty, alias)| {
+			let arms = variants
+				.iter()
+				.map(|variant| {
+					if !alias.fits(&variant.number) {
+						errors.push(
+							darling::Error::custom(format!(
+								"discriminant does not fit in `{alias}`"
+							))
+							.with_span(&variant.ident),
+						);
+					}
+					let span = variant.span;
+					let name = &variant.ident;
+					let value = variant
+						.number
+						.parse()
+						.map_or_else(|_| variant.value.clone(), crate::options::int_literal);
+					quote::quote_spanned!(span=> #ident::#name => #value,)
+				})
+				.collect::<Vec<_>>();
+
+			quote::quote! {
+				#[automatically_derived]
+				impl ::core::convert::From<#ident> for #c_ty {
+					#[inline]
+					fn from(value: #ident) -> Self {
+						match value {
+							#(#arms)*
+						}
+					}
+				}
+			}
+		})
+		.collect::<Vec<_>>();
+	errors.finish()?;
 
 	Ok(quote::quote! {
 		#[automatically_derived]
-		impl From<#ident> for #repr {
+		impl ::core::convert::From<#ident> for #repr_ty {
 			#[inline]
 			fn from(value: #ident) -> Self {
-				value as #repr
+				#body
 			}
 		}
+
+		#(#narrow)*
+
+		#into_char
+
+		#(#into_c)*
 	}
 	.into())
 }
@@ -0,0 +1,41 @@
+//! Provides the code generation for the [`IntoStaticStr`](crate::derive_into_static_str) derive macro
+
+use crate::NamedEnum;
+use proc_macro::TokenStream;
+
+/// Derives impl `From<enum> for &'static str` and `From<&enum> for &'static str` blocks, returning the names of the variants
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let NamedEnum {
+		ident, variants, ..
+	} = NamedEnum::parse(item)?;
+
+	let arms = variants
+		.iter()
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.name;
+			quote::quote!(#ident::#name => #value,)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl ::core::convert::From<&#ident> for &'static str {
+			#[inline]
+			fn from(value: &#ident) -> Self {
+				match value {
+					#(#arms)*
+				}
+			}
+		}
+
+		#[automatically_derived]
+		impl ::core::convert::From<#ident> for &'static str {
+			#[inline]
+			fn from(value: #ident) -> Self {
+				<Self as ::core::convert::From<&#ident>>::from(&value)
+			}
+		}
+	}
+	.into())
+}
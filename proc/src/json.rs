@@ -0,0 +1,107 @@
+//! Provides the code generation for the [`JsonValue`](crate::derive_json_value) derive macro
+
+use crate::{options::SerdeMode, EnumModel, PrimitiveRepresentation};
+use proc_macro::TokenStream;
+
+/// Derives impl `From<enum> for Value` and `TryFrom<&Value> for enum` blocks
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	use proc_macro2::Literal;
+
+	let EnumModel {
+		ident,
+		options,
+		repr,
+		variants,
+		..
+	} = EnumModel::parse_unit(item)?;
+	if options.serde != SerdeMode::Name
+		&& matches!(
+			repr,
+			PrimitiveRepresentation::u128 | PrimitiveRepresentation::i128
+		) {
+		return Err(darling::Error::custom(format!(
+			"`{repr}` discriminants cannot be stored in a JSON value, consider `#[discrim(serde = \"name\")]`"
+		))
+		.with_span(&ident));
+	}
+
+	let mut errors = darling::Error::accumulator();
+	let from_arms = variants
+		.iter()
+		.map(|variant| {
+			let name = &variant.ident;
+			let json = match options.serde {
+				SerdeMode::Value | SerdeMode::Tagged | SerdeMode::Key => variant.value.clone(),
+				SerdeMode::Name | SerdeMode::HumanReadable => {
+					let variant_name = &variant.name;
+					quote::quote!(#variant_name)
+				}
+			};
+			quote::quote!(#ident::#name => ::enum_discrim::__private::serde_json::Value::from(#json),)
+		})
+		.collect::<Vec<_>>();
+	let number_arms = variants
+		.iter()
+		.filter(|variant| variant.is_decoded())
+		.filter_map(|variant| {
+			let name = &variant.ident;
+			let value = errors.handle(
+				variant
+					.number
+					.parse()
+					.map(Literal::i128_suffixed)
+					.map_err(|err| darling::Error::custom(err).with_span(name)),
+			)?;
+			Some(quote::quote!(#value => ::core::result::Result::Ok(Self::#name),))
+		})
+		.collect::<Vec<_>>();
+	let name_arms = variants
+		.iter()
+		.map(|variant| {
+			let name = &variant.ident;
+			let variant_name = &variant.name;
+			quote::quote!(#variant_name => ::core::result::Result::Ok(Self::#name),)
+		})
+		.collect::<Vec<_>>();
+	errors.finish()?;
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl ::core::convert::From<#ident> for ::enum_discrim::__private::serde_json::Value {
+			#[inline]
+			fn from(value: #ident) -> Self {
+				match value {
+					#(#from_arms)*
+				}
+			}
+		}
+
+		#[automatically_derived]
+		impl ::core::convert::TryFrom<&::enum_discrim::__private::serde_json::Value> for #ident {
+			type Error = ::enum_discrim::TryFromError;
+
+			#[inline]
+			fn try_from(
+				value: &::enum_discrim::__private::serde_json::Value,
+			) -> ::core::result::Result<Self, Self::Error> {
+				if let ::core::option::Option::Some(name) = value.as_str() {
+					return match name {
+						#(#name_arms)*
+						_ => ::core::result::Result::Err(Self::Error::new(::core::stringify!(#ident))),
+					};
+				}
+
+				let number = match (value.as_u64(), value.as_i64()) {
+					(::core::option::Option::Some(number), _) => i128::from(number),
+					(_, ::core::option::Option::Some(number)) => i128::from(number),
+					_ => return ::core::result::Result::Err(Self::Error::new(::core::stringify!(#ident))),
+				};
+				match number {
+					#(#number_arms)*
+					_ => ::core::result::Result::Err(Self::Error::new(::core::stringify!(#ident))),
+				}
+			}
+		}
+	}
+	.into())
+}
@@ -48,50 +48,430 @@
 )]
 #![forbid(unsafe_code, clippy::missing_panics_doc, clippy::missing_errors_doc)]
 
-use darling::{util::SpannedValue, FromAttributes, FromMeta};
+use crate::options::{EnumOptions, LogicalValue, VariantOptions};
+use darling::{ast::Data, util::SpannedValue, FromAttributes, FromDeriveInput, FromMeta};
 use proc_macro::TokenStream;
+use proc_macro2::Span;
 use quote::{IdentFragment, ToTokens};
 use std::{
 	fmt::{self, Debug, Display, Formatter},
 	ops::Add,
 	str::FromStr,
 };
-use syn::{Attribute, NestedMeta, Variant};
+use syn::{Attribute, Fields, Generics, Ident, NestedMeta, Variant, Visibility};
 
+mod attribute;
+mod bigint;
+mod bits;
+mod borsh;
+mod bson;
+mod bytes;
+mod c_header;
+mod case;
+mod describe;
 mod discriminants;
+mod enum_map;
+mod exit_code;
+mod from_str;
+mod graphql;
 mod into;
+mod into_static_str;
+mod json;
+mod manifest;
+mod minicbor;
+mod newtype;
+mod options;
+mod ordinal;
+mod postgres;
+mod redis;
+mod reflect;
+mod register;
+mod repr_cmp;
+mod scale;
+mod sea_orm;
+mod serde_repr;
+mod speedy;
+mod step;
+mod to_schema;
 mod try_from;
+mod ts;
+mod ufmt;
+mod valuable;
+mod varint;
+mod visitor;
 
 #[allow(missing_docs, clippy::missing_docs_in_private_items)]
-#[proc_macro_derive(Discriminants)]
+#[proc_macro_derive(Discriminants, attributes(discrim))]
 #[inline]
 pub fn derive_discriminants(item: TokenStream) -> TokenStream {
-	match discriminants::derive(item) {
-		Ok(tokens) => tokens,
-		Err(err) => err.write_errors().into(),
-	}
+	expand(item, discriminants::derive)
 }
 
 #[allow(missing_docs, clippy::missing_docs_in_private_items)]
-#[proc_macro_derive(Into)]
+#[proc_macro_derive(Into, attributes(discrim))]
 #[inline]
 pub fn derive_into(item: TokenStream) -> TokenStream {
-	match into::derive(item) {
+	expand(item, into::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(TryFrom, attributes(discrim))]
+#[inline]
+pub fn derive_try_from(item: TokenStream) -> TokenStream {
+	expand(item, try_from::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(Ordinal, attributes(discrim))]
+#[inline]
+pub fn derive_ordinal(item: TokenStream) -> TokenStream {
+	expand(item, ordinal::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(Describe, attributes(discrim))]
+#[inline]
+pub fn derive_describe(item: TokenStream) -> TokenStream {
+	expand(item, describe::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(Step, attributes(discrim))]
+#[inline]
+pub fn derive_step(item: TokenStream) -> TokenStream {
+	expand(item, step::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(Visitor, attributes(discrim))]
+#[inline]
+pub fn derive_visitor(item: TokenStream) -> TokenStream {
+	expand(item, visitor::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(ReprCmp, attributes(discrim))]
+#[inline]
+pub fn derive_repr_cmp(item: TokenStream) -> TokenStream {
+	expand(item, repr_cmp::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(Reflect, attributes(discrim))]
+#[inline]
+pub fn derive_reflect(item: TokenStream) -> TokenStream {
+	expand(item, reflect::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(GraphQLEnum, attributes(discrim))]
+#[inline]
+pub fn derive_graphql(item: TokenStream) -> TokenStream {
+	expand(item, graphql::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(TS, attributes(discrim))]
+#[inline]
+pub fn derive_ts(item: TokenStream) -> TokenStream {
+	expand(item, ts::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(ToSchema, attributes(discrim))]
+#[inline]
+pub fn derive_to_schema(item: TokenStream) -> TokenStream {
+	expand(item, to_schema::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(BorshSerialize, attributes(discrim))]
+#[inline]
+pub fn derive_borsh_serialize(item: TokenStream) -> TokenStream {
+	expand(item, borsh::serialize)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(BorshDeserialize, attributes(discrim))]
+#[inline]
+pub fn derive_borsh_deserialize(item: TokenStream) -> TokenStream {
+	expand(item, borsh::deserialize)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(Writable, attributes(discrim))]
+#[inline]
+pub fn derive_speedy_writable(item: TokenStream) -> TokenStream {
+	expand(item, speedy::writable)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(Readable, attributes(discrim))]
+#[inline]
+pub fn derive_speedy_readable(item: TokenStream) -> TokenStream {
+	expand(item, speedy::readable)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(BytesCodec, attributes(discrim))]
+#[inline]
+pub fn derive_bytes_codec(item: TokenStream) -> TokenStream {
+	expand(item, bytes::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(ToSql, attributes(discrim))]
+#[inline]
+pub fn derive_to_sql(item: TokenStream) -> TokenStream {
+	expand(item, postgres::to_sql)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(FromSql, attributes(discrim))]
+#[inline]
+pub fn derive_from_sql(item: TokenStream) -> TokenStream {
+	expand(item, postgres::from_sql)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(ExitCode, attributes(discrim))]
+#[inline]
+pub fn derive_exit_code(item: TokenStream) -> TokenStream {
+	expand(item, exit_code::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(TryFromBigInt, attributes(discrim))]
+#[inline]
+pub fn derive_try_from_big_int(item: TokenStream) -> TokenStream {
+	expand(item, bigint::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(ActiveEnum, attributes(discrim))]
+#[inline]
+pub fn derive_active_enum(item: TokenStream) -> TokenStream {
+	expand(item, sea_orm::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(UFmt, attributes(discrim))]
+#[inline]
+pub fn derive_ufmt(item: TokenStream) -> TokenStream {
+	expand(item, ufmt::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(ToRedisArgs, attributes(discrim))]
+#[inline]
+pub fn derive_to_redis_args(item: TokenStream) -> TokenStream {
+	expand(item, redis::to_redis_args)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(FromRedisValue, attributes(discrim))]
+#[inline]
+pub fn derive_from_redis_value(item: TokenStream) -> TokenStream {
+	expand(item, redis::from_redis_value)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(Bson, attributes(discrim))]
+#[inline]
+pub fn derive_bson(item: TokenStream) -> TokenStream {
+	expand(item, bson::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(Register, attributes(discrim))]
+#[inline]
+pub fn derive_register(item: TokenStream) -> TokenStream {
+	expand(item, register::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(JsonValue, attributes(discrim))]
+#[inline]
+pub fn derive_json_value(item: TokenStream) -> TokenStream {
+	expand(item, json::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(ScaleEncode, attributes(discrim))]
+#[inline]
+pub fn derive_scale_encode(item: TokenStream) -> TokenStream {
+	expand(item, scale::encode)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(ScaleDecode, attributes(discrim))]
+#[inline]
+pub fn derive_scale_decode(item: TokenStream) -> TokenStream {
+	expand(item, scale::decode)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(CborEncode, attributes(discrim))]
+#[inline]
+pub fn derive_cbor_encode(item: TokenStream) -> TokenStream {
+	expand(item, minicbor::encode)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(CborDecode, attributes(discrim))]
+#[inline]
+pub fn derive_cbor_decode(item: TokenStream) -> TokenStream {
+	expand(item, minicbor::decode)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(Valuable, attributes(discrim))]
+#[inline]
+pub fn derive_valuable(item: TokenStream) -> TokenStream {
+	expand(item, valuable::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(EnumMap, attributes(discrim))]
+#[inline]
+pub fn derive_enum_map(item: TokenStream) -> TokenStream {
+	expand(item, enum_map::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(SerdeRepr, attributes(discrim))]
+#[inline]
+pub fn derive_serde_repr(item: TokenStream) -> TokenStream {
+	expand(item, serde_repr::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(FromStr, attributes(discrim))]
+#[inline]
+pub fn derive_from_str(item: TokenStream) -> TokenStream {
+	expand(item, from_str::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(IntoStaticStr, attributes(discrim))]
+#[inline]
+pub fn derive_into_static_str(item: TokenStream) -> TokenStream {
+	expand(item, into_static_str::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(CHeader, attributes(discrim))]
+#[inline]
+pub fn derive_c_header(item: TokenStream) -> TokenStream {
+	expand(item, c_header::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(Varint, attributes(discrim))]
+#[inline]
+pub fn derive_varint(item: TokenStream) -> TokenStream {
+	expand(item, varint::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(Newtype, attributes(discrim))]
+#[inline]
+pub fn derive_newtype(item: TokenStream) -> TokenStream {
+	expand(item, newtype::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(Bits, attributes(discrim))]
+#[inline]
+pub fn derive_bits(item: TokenStream) -> TokenStream {
+	expand(item, bits::derive)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(Specifier, attributes(discrim))]
+#[inline]
+pub fn derive_specifier(item: TokenStream) -> TokenStream {
+	expand(item, bits::specifier)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_derive(ArbitraryInt, attributes(discrim))]
+#[inline]
+pub fn derive_arbitrary_int(item: TokenStream) -> TokenStream {
+	expand(item, bits::arbitrary_int)
+}
+
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[proc_macro_attribute]
+#[inline]
+pub fn enum_discrim(args: TokenStream, item: TokenStream) -> TokenStream {
+	match attribute::expand(args, item) {
 		Ok(tokens) => tokens,
 		Err(err) => err.write_errors().into(),
 	}
 }
 
-#[allow(missing_docs, clippy::missing_docs_in_private_items)]
-#[proc_macro_derive(TryFrom)]
+/// Oldest compiler version supported by `enum_discrim`, and thus by the generated code
+///
+/// This *must* match the `rust-version` of the workspace manifest.
+const MSRV: options::Msrv = options::Msrv(81);
+
+/// Expands a derive macro, applying the options common to all derive macros
 #[inline]
-pub fn derive_try_from(item: TokenStream) -> TokenStream {
-	match try_from::derive(item) {
+fn expand(
+	item: TokenStream,
+	derive: fn(TokenStream) -> darling::Result<TokenStream>,
+) -> TokenStream {
+	match derive(item.clone()).and_then(|tokens| gate(item, tokens)) {
 		Ok(tokens) => tokens,
 		Err(err) => err.write_errors().into(),
 	}
 }
 
+/// Wraps the impl blocks and functions generated by a derive macro in an anonymous `const _` block,
+/// and each generated item in the `#[discrim(cfg(...))]` option, if any
+///
+/// This also checks the `#[discrim(msrv = "...")]` option, if any, against [`MSRV`].
+///
+/// The anonymous block keeps the impl blocks and functions (e.g. proof harnesses) from colliding with, or being referred to by, items of the user.
+/// Other items (modules, types, consts…) are part of the generated API, so they are left in the scope of the enum.
+fn gate(item: TokenStream, tokens: TokenStream) -> darling::Result<TokenStream> {
+	use syn::{DeriveInput, File, Item};
+
+	let item: DeriveInput = syn::parse(item)?;
+	let options = EnumOptions::from_attributes(&item.attrs)?;
+	if let Some(msrv) = options.msrv.filter(|msrv| *msrv < MSRV) {
+		return Err(darling::Error::custom(format!(
+			"`#[discrim(msrv = \"1.{}\")]` is older than 1.{}, the oldest compiler version supported by `enum_discrim`",
+			msrv.0, MSRV.0
+		))
+		.with_span(&item.ident));
+	}
+	let cfg = options.cfg.map(|cfg| quote::quote!(#[cfg(#cfg)]));
+	let (impls, items): (Vec<_>, Vec<_>) = syn::parse::<File>(tokens)?
+		.items
+		.into_iter()
+		.partition(|item| matches!(item, Item::Impl(_) | Item::Fn(_)));
+	let impls = (!impls.is_empty()).then(|| {
+		quote::quote! {
+			#cfg
+			const _: () = {
+				#(#impls)*
+			};
+		}
+	});
+
+	Ok(quote::quote! {
+		#(
+			#cfg
+			#items
+		)*
+
+		#impls
+	}
+	.into())
+}
+
 #[allow(non_camel_case_types, clippy::missing_docs_in_private_items)]
 /// Enumeration of possible [primitive representations](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) of an enum
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -129,6 +509,11 @@ impl FromStr for PrimitiveRepresentation {
 	}
 }
 impl FromMeta for PrimitiveRepresentation {
+	#[inline]
+	fn from_string(value: &str) -> darling::Result<Self> {
+		value.parse().map_err(darling::Error::custom)
+	}
+
 	#[inline]
 	fn from_nested_meta(item: &NestedMeta) -> darling::Result<Self> {
 		use syn::Meta;
@@ -149,6 +534,39 @@ impl FromMeta for PrimitiveRepresentation {
 			.ok_or_else(|| darling::Error::custom("#[repr(inttype)] must be specified"))
 	}
 }
+impl PrimitiveRepresentation {
+	/// Returns whether this type is a signed integer type
+	#[inline]
+	fn is_signed(self) -> bool {
+		matches!(
+			self,
+			Self::i8 | Self::i16 | Self::i32 | Self::i64 | Self::i128 | Self::isize
+		)
+	}
+
+	/// Returns whether the given base 10 number is a value of this type
+	fn fits(self, number: &str) -> bool {
+		/// Generates a match arm for each given type
+		macro_rules! impl_match {
+			($( $ty:ident ),* $(,)?) => {
+				match self {$(
+					Self::$ty => number.parse::<$ty>().is_ok(),
+				)*}
+			};
+		}
+		impl_match![u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize]
+	}
+
+	/// Resolves the primitive representation of an enum,
+	/// from the `#[discrim(logical_repr = "...")]` option if given, or from the `#[repr(inttype)]` attribute otherwise
+	#[inline]
+	fn resolve(attrs: &[Attribute], options: &EnumOptions) -> darling::Result<Self> {
+		match options.logical_repr {
+			Some(repr) => Ok(repr),
+			None => Self::from_attributes(attrs),
+		}
+	}
+}
 impl FromAttributes for PrimitiveRepresentation {
 	#[inline]
 	fn from_attributes(attrs: &[Attribute]) -> darling::Result<Self> {
@@ -183,11 +601,650 @@ impl ToTokens for PrimitiveRepresentation {
 	}
 }
 
+/// Returns whether the given attributes contain `#[repr(C)]`
+fn is_repr_c(attrs: &[Attribute]) -> bool {
+	use syn::Meta;
+
+	attrs
+		.iter()
+		.filter(|attr| attr.path.is_ident("repr"))
+		.filter_map(|attr| attr.parse_meta().ok())
+		.any(|meta| match meta {
+			Meta::List(list) => list.nested.iter().any(
+				|nested| matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("C")),
+			),
+			_ => false,
+		})
+}
+
+/// Parsing struct for the derive macros based on [`EnumModel`]
+#[derive(Debug, FromDeriveInput)]
+#[darling(supports(enum_any), forward_attrs(repr, discrim))]
+struct EnumModelInput {
+	/// Enum identifier
+	ident: Ident,
+	/// Enum visibility
+	vis: Visibility,
+	/// Enum generics
+	generics: Generics,
+	/// Variants contained in the enum
+	data: Data<SpannedValue<Variant>, ()>,
+	/// Forwarded attributes
+	attrs: Vec<Attribute>,
+}
+
+/// Enum with a [`PrimitiveRepresentation`], with the discriminants and options of its variants resolved
+///
+/// All the discriminant-based derive macros consume this model,
+/// so that they validate and interpret enums identically.
+#[derive(Debug)]
+struct EnumModel {
+	/// Enum identifier
+	ident: Ident,
+	/// Enum visibility
+	vis: Visibility,
+	/// Enum generics
+	generics: Generics,
+	/// Options given with `#[discrim(...)]`
+	options: EnumOptions,
+	/// Primitive representation of the enum
+	repr: PrimitiveRepresentation,
+	/// Whether the enum only declares `#[repr(C)]`, in which case the discriminants are [`c_int`](core::ffi::c_int)
+	repr_c: bool,
+	/// Variants contained in the enum, in declaration order
+	variants: Vec<VariantModel>,
+}
+/// Variant of an [`EnumModel`]
+#[derive(Debug)]
+struct VariantModel {
+	/// Variant identifier
+	ident: Ident,
+	/// Span of the whole variant
+	span: Span,
+	/// Whether the variant has no fields
+	unit: bool,
+	/// Fields of the variant
+	fields: Fields,
+	/// Doc comments of the variant
+	docs: Vec<Attribute>,
+	/// Options given with `#[discrim(...)]`
+	options: VariantOptions,
+	/// Discriminant, as a literal of the primitive representation
+	value: proc_macro2::TokenStream,
+	/// Discriminant, as a base 10 number
+	///
+	/// For [opaque](Self::opaque) discriminants, this is the path to the const instead.
+	number: String,
+	/// Whether the discriminant is a path to a const, passed through verbatim
+	///
+	/// Only the [`Discriminants`](crate::derive_discriminants), [`Into`](crate::derive_into)
+	/// and [`TryFrom`](crate::derive_try_from) derive macros support these discriminants.
+	opaque: bool,
+	/// Name used by the name-based derive macros
+	name: String,
+	/// Whether another variant with the same discriminant is converted from it instead, with `#[discrim(allow_aliases)]`
+	///
+	/// The derive macros converting from a discriminant *must* skip these variants.
+	alias: bool,
+}
+impl EnumModel {
+	/// Parses an enum with any kind of variants and resolves the discriminant of each variant
+	///
+	/// Paths to consts are rejected as discriminants.
+	fn parse(item: TokenStream) -> darling::Result<Self> {
+		let model = Self::parse_opaque(item)?;
+
+		let mut errors = darling::Error::accumulator();
+		for variant in model.variants.iter().filter(|variant| variant.opaque) {
+			errors.push(
+				darling::Error::custom(
+					"path discriminants are only supported by the `Discriminants`, `Into` and `TryFrom` derive macros",
+				)
+				.with_span(&variant.ident),
+			);
+		}
+		errors.finish_with(model)
+	}
+
+	/// Parses an enum with any kind of variants and resolves the discriminant of each variant,
+	/// passing paths to consts through verbatim
+	fn parse_opaque(item: TokenStream) -> darling::Result<Self> {
+		use proc_macro2::Literal;
+		use syn::{DeriveInput, Fields};
+
+		let item: DeriveInput = syn::parse(item)?;
+		let EnumModelInput {
+			ident,
+			vis,
+			generics,
+			data,
+			attrs,
+		} = EnumModelInput::from_derive_input(&item)?;
+		let Data::Enum(data) = data else {
+			unreachable!()
+		};
+
+		let options = EnumOptions::from_attributes(&attrs)?;
+		let (repr, repr_c) = match PrimitiveRepresentation::resolve(&attrs, &options) {
+			Ok(repr) => (repr, false),
+			Err(_) if is_repr_c(&attrs) => (PrimitiveRepresentation::i32, true),
+			Err(err) => return Err(err),
+		};
+		if options.hash_values.is_some() && options.logical_repr.is_none() {
+			return Err(darling::Error::custom(
+				"`#[discrim(hash_values = \"...\")]` requires `#[discrim(logical_repr = \"...\")]`, as derive macros cannot change the actual discriminants",
+			)
+			.with_span(&ident));
+		}
+		let variant_options = VariantOptions::from_variants(&data)?;
+		/// Generates a match arm for each given type
+		macro_rules! values_with_ty {
+			($( $ty:ident ),* $(,)?) => {
+				match repr {$(
+					PrimitiveRepresentation::$ty => scan_variants::<$ty>(&data)?
+						.into_iter()
+						.map(|(variant, value)| match value {
+							Scanned::Value(value) => (variant, quote::quote!(#value), value.to_string(), false),
+							Scanned::Literal(value, lit) => (variant, suffixed(lit, ::core::stringify!($ty)), value.to_string(), false),
+							Scanned::Path(path) => (variant, path.to_token_stream(), path.to_token_stream().to_string(), true),
+						})
+						.collect::<Vec<_>>(),
+				)*}
+			};
+		}
+		let values = if repr_c {
+			scan_variants::<i32>(&data)?
+				.into_iter()
+				.map(|(variant, value)| match value {
+					Scanned::Value(value) => (
+						variant,
+						Literal::i32_unsuffixed(value).into_token_stream(),
+						value.to_string(),
+						false,
+					),
+					Scanned::Literal(value, lit) => {
+						(variant, lit.to_token_stream(), value.to_string(), false)
+					}
+					Scanned::Path(path) => (
+						variant,
+						path.to_token_stream(),
+						path.to_token_stream().to_string(),
+						true,
+					),
+				})
+				.collect::<Vec<_>>()
+		} else {
+			values_with_ty![u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize]
+		};
+		let mut variants: Vec<VariantModel> = values
+			.into_iter()
+			.zip(variant_options)
+			.map(|((variant, value, number, opaque), variant_options)| {
+				let name = options.variant_name(&variant.ident, &variant_options);
+				let (value, number, opaque) = match (variant_options.value, options.hash_values) {
+					(Some(LogicalValue(logical)), _) => {
+						let magnitude = syn::LitInt::new(
+							&format!("{}{repr}", logical.unsigned_abs()),
+							variant.ident.span(),
+						);
+						(
+							if logical < 0 {
+								quote::quote!(-#magnitude)
+							} else {
+								magnitude.into_token_stream()
+							},
+							logical.to_string(),
+							false,
+						)
+					}
+					(None, Some(hash_values)) if variant.discriminant.is_none() => {
+						let hash = hash_values.hash(&name);
+						(
+							syn::LitInt::new(&format!("{hash}{repr}"), variant.ident.span())
+								.into_token_stream(),
+							hash.to_string(),
+							false,
+						)
+					}
+					_ => (value, number, opaque),
+				};
+
+				VariantModel {
+					ident: variant.ident.clone(),
+					span: variant.span(),
+					unit: matches!(variant.fields, Fields::Unit),
+					fields: variant.fields.clone(),
+					docs: variant
+						.attrs
+						.iter()
+						.filter(|attr| attr.path.is_ident("doc"))
+						.cloned()
+						.collect(),
+					name,
+					options: variant_options,
+					value,
+					number,
+					opaque,
+					alias: false,
+				}
+			})
+			.collect();
+
+		let mut errors = darling::Error::accumulator();
+		for variant in variants
+			.iter()
+			.filter(|variant| variant.options.value.is_some())
+		{
+			if options.logical_repr.is_none() {
+				errors.push(
+					darling::Error::custom(
+						"`#[discrim(value = ...)]` requires `#[discrim(logical_repr = \"...\")]`, as derive macros cannot change the actual discriminants",
+					)
+					.with_span(&variant.ident),
+				);
+			} else if !repr.fits(&variant.number) {
+				errors.push(
+					darling::Error::custom(format!("discriminant does not fit in `{repr}`"))
+						.with_span(&variant.ident),
+				);
+			}
+		}
+		resolve_aliases(&ident, &options, &mut variants, &mut errors);
+		if options.hash_values.is_some() {
+			for variant in variants
+				.iter()
+				.filter(|variant| !variant.opaque && !repr.fits(&variant.number))
+			{
+				errors.push(
+					darling::Error::custom(format!("hashed discriminant does not fit in `{repr}`"))
+						.with_span(&variant.ident),
+				);
+			}
+		}
+		for variant in &variants {
+			let Ok(value) = variant.number.parse::<i128>() else {
+				continue;
+			};
+			if options.reserved.iter().any(|range| range.contains(value)) {
+				errors.push(
+					darling::Error::custom("discriminant is within a reserved range")
+						.with_span(&variant.ident),
+				);
+			}
+		}
+		check_aliases(&options, repr, &variants, &mut errors);
+		errors.finish()?;
+
+		Ok(Self {
+			ident,
+			vis,
+			generics,
+			options,
+			repr,
+			repr_c,
+			variants,
+		})
+	}
+
+	/// Parses an enum with *only* unit variants and resolves the discriminant of each variant
+	///
+	/// Paths to consts are rejected as discriminants.
+	#[inline]
+	fn parse_unit(item: TokenStream) -> darling::Result<Self> {
+		Self::parse(item)?.require_unit()
+	}
+
+	/// Parses an enum with *only* unit variants and resolves the discriminant of each variant,
+	/// passing paths to consts through verbatim
+	#[inline]
+	fn parse_unit_opaque(item: TokenStream) -> darling::Result<Self> {
+		Self::parse_opaque(item)?.require_unit()
+	}
+
+	/// Rejects the enum if it has variants with fields
+	fn require_unit(self) -> darling::Result<Self> {
+		let mut errors = darling::Error::accumulator();
+		for variant in self.variants.iter().filter(|variant| !variant.unit) {
+			errors.push(
+				darling::Error::custom("Unsupported shape: expected only unit variants")
+					.with_span(&variant.ident),
+			);
+		}
+		errors.finish_with(self)
+	}
+
+	/// Returns the C integer types given with `#[discrim(c_int)]` and `#[discrim(c_uint)]`,
+	/// along with the primitive type they are an alias of on mainstream targets
+	///
+	/// The types which are already the primitive representation are rejected, as their impl blocks would overlap.
+	fn c_types(&self) -> darling::Result<Vec<(proc_macro2::TokenStream, PrimitiveRepresentation)>> {
+		let mut errors = darling::Error::accumulator();
+		let types = [
+			(self.options.c_int, "c_int", PrimitiveRepresentation::i32),
+			(self.options.c_uint, "c_uint", PrimitiveRepresentation::u32),
+		]
+		.into_iter()
+		.filter(|(enabled, ..)| *enabled)
+		.filter_map(|(_, name, alias)| {
+			if self.repr == alias {
+				errors.push(
+					darling::Error::custom(format!(
+						"`{name}` is already the primitive representation"
+					))
+					.with_span(&self.ident),
+				);
+				return None;
+			}
+			let name = quote::format_ident!("{}", name);
+			Some((quote::quote!(::core::ffi::#name), alias))
+		})
+		.collect();
+		errors.finish_with(types)
+	}
+
+	/// Rejects the [opaque](VariantModel::opaque) discriminants, for the code generation requiring their values
+	fn require_numbers(&self, feature: &str) -> darling::Result<()> {
+		let mut errors = darling::Error::accumulator();
+		for variant in self.variants.iter().filter(|variant| variant.opaque) {
+			errors.push(
+				darling::Error::custom(format!(
+					"{feature} requires integer discriminants, not paths"
+				))
+				.with_span(&variant.ident),
+			);
+		}
+		errors.finish()
+	}
+
+	/// Checks that the discriminants have the same width on all targets, for the derive macros writing them to a wire format
+	///
+	/// If the primitive representation is `usize` or `isize`, this returns code triggering a deprecation warning,
+	/// or an error with `#[discrim(deny_platform_dependent)]`.
+	fn check_portability(&self) -> darling::Result<Option<proc_macro2::TokenStream>> {
+		if !matches!(
+			self.repr,
+			PrimitiveRepresentation::usize | PrimitiveRepresentation::isize
+		) {
+			return Ok(None);
+		}
+
+		let message = format!(
+			"`{}` discriminants have a platform-dependent width, consider a fixed-width primitive representation",
+			self.repr
+		);
+		if self.options.deny_platform_dependent {
+			return Err(darling::Error::custom(message).with_span(&self.ident));
+		}
+		let span = self.ident.span();
+		Ok(Some(quote::quote_spanned! {span=>
+			const _: () = {
+				#[deprecated(note = #message)]
+				const PLATFORM_DEPENDENT: () = ();
+				PLATFORM_DEPENDENT
+			};
+		}))
+	}
+
+	/// Returns the match arm rejecting the reserved discriminants in the generated `TryFrom` impl blocks, if any range is reserved
+	fn reserved_arm(&self) -> Option<proc_macro2::TokenStream> {
+		if self.options.reserved.is_empty() {
+			return None;
+		}
+
+		let ident = &self.ident;
+		let ranges = &self.options.reserved;
+		Some(quote::quote! {
+			#(#ranges)|* => ::core::result::Result::Err(
+				::enum_discrim::TryFromError::new_reserved(::core::stringify!(#ident)),
+			),
+		})
+	}
+
+	/// Returns the discriminants of the variants excluded with `#[discrim(skip)]`,
+	/// except those shared with a variant which is converted into
+	fn skipped_values(&self) -> Vec<&proc_macro2::TokenStream> {
+		self.variants
+			.iter()
+			.filter(|variant| {
+				variant.options.skip
+					&& !self
+						.variants
+						.iter()
+						.any(|other| other.is_decoded() && other.number == variant.number)
+			})
+			.map(|variant| &variant.value)
+			.collect()
+	}
+
+	/// Returns the match arm rejecting the discriminants of the variants excluded with `#[discrim(skip)]`
+	/// in the generated `TryFrom` impl blocks, if any variant is skipped
+	fn skipped_arm(&self) -> Option<proc_macro2::TokenStream> {
+		let values = self.skipped_values();
+		if values.is_empty() {
+			return None;
+		}
+
+		let ident = &self.ident;
+		Some(quote::quote! {
+			#(#values)|* => ::core::result::Result::Err(
+				::enum_discrim::TryFromError::new_skipped(::core::stringify!(#ident)),
+			),
+		})
+	}
+
+	/// Returns the type of the discriminants
+	#[inline]
+	fn repr_ty(&self) -> proc_macro2::TokenStream {
+		if self.repr_c {
+			quote::quote!(::core::ffi::c_int)
+		} else {
+			self.repr.to_token_stream()
+		}
+	}
+}
+
+impl VariantModel {
+	/// Returns whether the derive macros converting from a discriminant convert into this variant
+	///
+	/// Aliases and variants excluded with `#[discrim(skip)]` are never converted into.
+	#[inline]
+	const fn is_decoded(&self) -> bool {
+		!self.alias && !self.options.skip
+	}
+
+	/// Returns the doc comments of the variant, to be appended to the docs of the items generated for it
+	///
+	/// This way, IDEs show the docs of the variant when hovering these items.
+	fn forwarded_docs(&self) -> Option<proc_macro2::TokenStream> {
+		if self.docs.is_empty() {
+			return None;
+		}
+
+		let docs = &self.docs;
+		Some(quote::quote! {
+			#[doc = ""]
+			#(#docs)*
+		})
+	}
+}
+
+/// Parsing struct for the name-based derive macros, supporting enums with *only* unit variants
+#[derive(Debug, FromDeriveInput)]
+#[darling(supports(enum_unit), forward_attrs(discrim))]
+struct NamedEnumInput {
+	/// Enum identifier
+	ident: Ident,
+	/// Variants contained in the enum
+	data: Data<SpannedValue<Variant>, ()>,
+	/// Forwarded attributes
+	attrs: Vec<Attribute>,
+}
+
+/// Enum with *only* unit variants, with the names of its variants computed
+#[derive(Debug)]
+struct NamedEnum {
+	/// Enum identifier
+	ident: Ident,
+	/// Options given with `#[discrim(...)]`
+	options: EnumOptions,
+	/// Variants contained in the enum, in declaration order
+	variants: Vec<NamedVariant>,
+}
+/// Unit variant of a [`NamedEnum`]
+#[derive(Debug)]
+struct NamedVariant {
+	/// Variant identifier
+	ident: Ident,
+	/// Name used by the name-based derive macros
+	name: String,
+}
+impl NamedEnum {
+	/// Parses an enum with *only* unit variants and computes the name of each variant
+	fn parse(item: TokenStream) -> darling::Result<Self> {
+		use syn::DeriveInput;
+
+		let item: DeriveInput = syn::parse(item)?;
+		let NamedEnumInput { ident, data, attrs } = NamedEnumInput::from_derive_input(&item)?;
+		let Data::Enum(data) = data else {
+			unreachable!()
+		};
+
+		let options = EnumOptions::from_attributes(&attrs)?;
+		let variants = data
+			.iter()
+			.zip(VariantOptions::from_variants(&data)?)
+			.map(|(variant, variant_options)| NamedVariant {
+				ident: variant.ident.clone(),
+				name: options.variant_name(&variant.ident, &variant_options),
+			})
+			.collect();
+
+		Ok(Self {
+			ident,
+			options,
+			variants,
+		})
+	}
+}
+
+/// Marks the variants sharing their discriminant with a canonical variant as aliases
+///
+/// The canonical variant is the one with `#[discrim(canonical)]`, or the first one declared.
+/// Variants may only share their discriminant with `#[discrim(allow_aliases)]`.
+fn resolve_aliases(
+	ident: &Ident,
+	options: &EnumOptions,
+	variants: &mut [VariantModel],
+	errors: &mut darling::error::Accumulator,
+) {
+	use std::collections::BTreeMap;
+
+	if options.allow_aliases && options.logical_repr.is_none() {
+		errors.push(
+			darling::Error::custom(
+				"`#[discrim(allow_aliases)]` requires `#[discrim(logical_repr = \"...\")]`, as the compiler rejects duplicate discriminants",
+			)
+			.with_span(ident),
+		);
+		return;
+	}
+
+	let mut groups = BTreeMap::<&str, Vec<usize>>::new();
+	for (index, variant) in variants.iter().enumerate() {
+		groups.entry(&variant.number).or_default().push(index);
+	}
+	let mut aliases = Vec::new();
+	for indices in groups.into_values().filter(|indices| indices.len() > 1) {
+		let first = &variants[indices[0]];
+		if !options.allow_aliases {
+			for &index in &indices[1..] {
+				errors.push(
+					darling::Error::custom(format!(
+						"discriminant {} is also the one of `{}`, consider `#[discrim(allow_aliases)]`",
+						first.number, first.ident
+					))
+					.with_span(&variants[index].ident),
+				);
+			}
+			continue;
+		}
+
+		let canonical = indices
+			.iter()
+			.copied()
+			.filter(|&index| variants[index].options.canonical)
+			.collect::<Vec<_>>();
+		for &index in canonical.iter().skip(1) {
+			errors.push(
+				darling::Error::custom(
+					"another variant with this discriminant is already canonical",
+				)
+				.with_span(&variants[index].ident),
+			);
+		}
+		// Skipped variants are never converted into, so another one is preferred
+		let canonical = canonical.first().copied().unwrap_or_else(|| {
+			indices
+				.iter()
+				.copied()
+				.find(|&index| !variants[index].options.skip)
+				.unwrap_or(indices[0])
+		});
+		aliases.extend(indices.into_iter().filter(|&index| index != canonical));
+	}
+	for index in aliases {
+		variants[index].alias = true;
+	}
+}
+
+/// Checks the values given with `#[discrim(alias = ...)]`
+///
+/// Each alias must fit in the primitive representation, and must neither be reserved
+/// nor be the discriminant or another alias of any variant.
+fn check_aliases(
+	options: &EnumOptions,
+	repr: PrimitiveRepresentation,
+	variants: &[VariantModel],
+	errors: &mut darling::error::Accumulator,
+) {
+	use std::collections::BTreeSet;
+
+	let mut taken = variants
+		.iter()
+		.map(|variant| variant.number.clone())
+		.collect::<BTreeSet<_>>();
+	for variant in variants {
+		for &LogicalValue(alias) in &variant.options.aliases {
+			let number = alias.to_string();
+			let message = if !repr.fits(&number) {
+				format!("alias {alias} does not fit in `{repr}`")
+			} else if options.reserved.iter().any(|range| range.contains(alias)) {
+				format!("alias {alias} is within a reserved range")
+			} else if !taken.insert(number) {
+				format!("alias {alias} is already the discriminant or an alias of a variant")
+			} else {
+				continue;
+			};
+			errors.push(darling::Error::custom(message).with_span(&variant.ident));
+		}
+	}
+}
+
+/// Discriminant of a variant, computed by [`scan_variants`]
+enum Scanned<'v, D> {
+	/// Value computed by the macro
+	Value(D),
+	/// Value of an integer literal, which is kept to preserve its radix in the generated code
+	Literal(D, &'v syn::LitInt),
+	/// Path to a const, passed through verbatim as its value is only known to the compiler
+	Path(&'v syn::ExprPath),
+}
+
 /// Wraps an iterator of [`Variant`] to compute the discriminants and return them
 #[inline]
 fn scan_variants<'v, D>(
 	iter: impl IntoIterator<Item = &'v SpannedValue<Variant>>,
-) -> darling::Result<Vec<(&'v SpannedValue<Variant>, D)>>
+) -> darling::Result<Vec<(&'v SpannedValue<Variant>, Scanned<'v, D>)>>
 where
 	D: Default + FromStr + Increment + Copy,
 	D::Err: Display,
@@ -198,31 +1255,41 @@ where
 	let vec = iter
 		.into_iter()
 		.scan(
-			D::default(),
-			|d: &mut D, variant: &'v SpannedValue<Variant>| {
-				let value = match variant
-					.discriminant
-					.as_ref()
-					.map_or(Ok(*d), |(_eq, value)| match value {
-						Expr::Lit(ExprLit { lit, .. }) => match lit {
-							Lit::Int(value) => {
-								let value: D = value.base10_parse()?;
-								// .map_err(|err| darling::Error::custom(err).with_span(value))?;
-								*d = value.increment();
-								Ok(value)
-							}
-							lit => Err(darling::Error::unexpected_lit_type(lit)),
-						},
-						_ => Err(darling::Error::custom(
-							"Discriminant must be an integer literal",
-						)),
-					}) {
-					Ok(value) => value,
-					Err(err) => {
-						return Some(Err(err));
-					}
+			// The discriminant following a path is unknown
+			Some(D::default()),
+			|d: &mut Option<D>, variant: &'v SpannedValue<Variant>| {
+				let value = match variant.discriminant.as_ref() {
+					None => d.map(Scanned::Value).ok_or_else(|| {
+						darling::Error::custom(
+							"variants following a path discriminant need an explicit discriminant",
+						)
+						.with_span(&variant.ident)
+					}),
+					Some((_eq, Expr::Path(path))) => Ok(Scanned::Path(path)),
+					Some((_eq, Expr::Lit(ExprLit { lit, .. }))) => match lit {
+						Lit::Int(lit) => lit
+							.base10_parse()
+							.map(|value| Scanned::Literal(value, lit))
+							.map_err(darling::Error::from),
+						lit => Err(darling::Error::unexpected_lit_type(lit)),
+					},
+					Some((_eq, expr)) => eval_discriminant(expr).and_then(|value| {
+						value.to_string().parse().map(Scanned::Value).map_err(|_| {
+							darling::Error::custom(format!(
+								"discriminant {value} does not fit in the primitive representation"
+							))
+							.with_span(expr)
+						})
+					}),
 				};
-				Some(Ok((variant, value)))
+				match &value {
+					Ok(Scanned::Value(value) | Scanned::Literal(value, _)) => {
+						*d = Some(value.increment());
+					}
+					Ok(Scanned::Path(_)) => *d = None,
+					Err(_) => {}
+				}
+				Some(value.map(|value| (variant, value)))
 			},
 		)
 		.filter_map(|res| match res {
@@ -236,6 +1303,59 @@ where
 	accumulator.finish().map(|()| vec)
 }
 
+/// Returns the given integer literal with the given type suffix, unless it already has one
+///
+/// The literal is otherwise kept as written, so that the generated consts show discriminants in their original radix.
+fn suffixed(lit: &syn::LitInt, suffix: &str) -> proc_macro2::TokenStream {
+	if lit.suffix().is_empty() {
+		syn::LitInt::new(&format!("{}_{suffix}", lit.token()), lit.span()).into_token_stream()
+	} else {
+		lit.to_token_stream()
+	}
+}
+
+/// Evaluates a discriminant written as a const expression
+///
+/// Only integer literals combined with `-` (negation or subtraction), `+`, `<<`, `>>` and `|` are supported.
+fn eval_discriminant(expr: &syn::Expr) -> darling::Result<i128> {
+	use syn::{BinOp, Expr, ExprLit, Lit, UnOp};
+
+	let overflow =
+		|| darling::Error::custom("overflow while evaluating the discriminant").with_span(expr);
+	match expr {
+		Expr::Lit(ExprLit {
+			lit: Lit::Int(value),
+			..
+		}) => value.base10_parse().map_err(darling::Error::from),
+		Expr::Paren(expr) => eval_discriminant(&expr.expr),
+		Expr::Group(expr) => eval_discriminant(&expr.expr),
+		Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => eval_discriminant(&unary.expr)?
+			.checked_neg()
+			.ok_or_else(overflow),
+		Expr::Binary(binary) => {
+			let left = eval_discriminant(&binary.left)?;
+			let right = eval_discriminant(&binary.right)?;
+			let shift = || u32::try_from(right).ok();
+			match binary.op {
+				BinOp::Add(_) => left.checked_add(right),
+				BinOp::Sub(_) => left.checked_sub(right),
+				BinOp::Shl(_) => shift().and_then(|right| left.checked_shl(right)),
+				BinOp::Shr(_) => shift().and_then(|right| left.checked_shr(right)),
+				BinOp::BitOr(_) => Some(left | right),
+				_ => return Err(darling::Error::custom(
+					"unsupported operator in discriminant, expected `+`, `-`, `<<`, `>>` or `|`",
+				)
+				.with_span(&binary.op)),
+			}
+			.ok_or_else(overflow)
+		}
+		_ => Err(darling::Error::custom(
+			"Discriminant must be an integer literal or a const expression of integer literals",
+		)
+		.with_span(expr)),
+	}
+}
+
 /// Utility trait for the [`scan_variants`] function
 trait Increment: Add<Output = Self> + Sized {
 	/// Returns `self + 1`
@@ -0,0 +1,116 @@
+//! Provides the writing of the manifest requested with `#[discrim(manifest)]`
+
+use crate::EnumModel;
+#[cfg(feature = "manifest")]
+use std::{fmt::Write as _, path::PathBuf};
+
+/// Escapes the given string as a JSON string literal
+#[cfg(feature = "manifest")]
+fn json_string(value: &str) -> String {
+	let mut escaped = String::with_capacity(value.len() + 2);
+	escaped.push('"');
+	for c in value.chars() {
+		match c {
+			'"' => escaped.push_str("\\\""),
+			'\\' => escaped.push_str("\\\\"),
+			c if c.is_control() => {
+				let _ = write!(escaped, "\\u{:04x}", u32::from(c));
+			}
+			c => escaped.push(c),
+		}
+	}
+	escaped.push('"');
+	escaped
+}
+
+/// Renders the JSON description of the given enum
+#[cfg(feature = "manifest")]
+fn render(model: &EnumModel) -> String {
+	let variants = model
+		.variants
+		.iter()
+		.map(|variant| {
+			format!(
+				"\t\t{{ \"ident\": {}, \"name\": {}, \"value\": {} }}",
+				json_string(&variant.ident.to_string()),
+				json_string(&variant.name),
+				variant.number,
+			)
+		})
+		.collect::<Vec<_>>();
+	let repr = if model.repr_c {
+		"c_int".to_owned()
+	} else {
+		model.repr.to_string()
+	};
+
+	format!(
+		"{{\n\t\"name\": {},\n\t\"repr\": {},\n\t\"variants\": [\n{}\n\t]\n}}\n",
+		json_string(&model.ident.to_string()),
+		json_string(&repr),
+		variants.join(",\n"),
+	)
+}
+
+/// Resolves the path of the manifest
+///
+/// A given path is relative to the directory of the manifest of the crate being compiled.
+/// Otherwise, the manifest is written in `OUT_DIR`, which is only set for crates with a build script.
+#[cfg(feature = "manifest")]
+fn path(model: &EnumModel, path: Option<&str>) -> darling::Result<PathBuf> {
+	use std::env;
+
+	match path {
+		Some(path) => {
+			let root = env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+			Ok(PathBuf::from(root).join(path))
+		}
+		None => {
+			let out_dir = env::var_os("OUT_DIR").ok_or_else(|| {
+				darling::Error::custom(
+					"`OUT_DIR` is not set, consider adding a build script or giving a path with `#[discrim(manifest = \"...\")]`",
+				)
+				.with_span(&model.ident)
+			})?;
+			Ok(PathBuf::from(out_dir).join(format!("{}.discrim.json", model.ident)))
+		}
+	}
+}
+
+/// Writes the manifest of the given enum, if requested with `#[discrim(manifest)]`
+#[cfg(feature = "manifest")]
+pub(crate) fn write(model: &EnumModel) -> darling::Result<()> {
+	let Some(manifest) = &model.options.manifest else {
+		return Ok(());
+	};
+	model.require_numbers("`#[discrim(manifest)]`")?;
+	let path = path(model, manifest.as_ref().explicit().map(String::as_str))?;
+
+	let contents = render(model);
+	// Skip writing an identical manifest, so that build tools watching it are not triggered on every expansion
+	if std::fs::read_to_string(&path).is_ok_and(|existing| existing == contents) {
+		return Ok(());
+	}
+	if let Some(parent) = path.parent() {
+		std::fs::create_dir_all(parent).map_err(|err| {
+			darling::Error::custom(format!("cannot create `{}`: {err}", parent.display()))
+				.with_span(&model.ident)
+		})?;
+	}
+	std::fs::write(&path, contents).map_err(|err| {
+		darling::Error::custom(format!("cannot write `{}`: {err}", path.display()))
+			.with_span(&model.ident)
+	})
+}
+
+/// Rejects `#[discrim(manifest)]`, as the `manifest` feature is disabled
+#[cfg(not(feature = "manifest"))]
+pub(crate) fn write(model: &EnumModel) -> darling::Result<()> {
+	match &model.options.manifest {
+		Some(_) => Err(darling::Error::custom(
+			"`#[discrim(manifest)]` requires the `manifest` feature of `enum_discrim`",
+		)
+		.with_span(&model.ident)),
+		None => Ok(()),
+	}
+}
@@ -0,0 +1,90 @@
+//! Provides the code generation for the [`CborEncode`](crate::derive_cbor_encode) and [`CborDecode`](crate::derive_cbor_decode) derive macros
+
+use crate::EnumModel;
+use proc_macro::TokenStream;
+
+/// Derives an impl `Encode` block for `minicbor`, encoding the enum as its discriminant
+pub(crate) fn encode(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let portability = model.check_portability()?;
+	let EnumModel {
+		ident,
+		repr,
+		variants,
+		..
+	} = model;
+	let arms = variants
+		.iter()
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(Self::#name => #value,)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#portability
+
+		#[automatically_derived]
+		impl<C> ::enum_discrim::__private::minicbor::Encode<C> for #ident {
+			#[inline]
+			fn encode<W>(
+				&self,
+				e: &mut ::enum_discrim::__private::minicbor::Encoder<W>,
+				ctx: &mut C,
+			) -> ::core::result::Result<(), ::enum_discrim::__private::minicbor::encode::Error<W::Error>>
+			where
+				W: ::enum_discrim::__private::minicbor::encode::Write,
+			{
+				let value: #repr = match self {
+					#(#arms)*
+				};
+				::enum_discrim::__private::minicbor::Encode::encode(&value, e, ctx)
+			}
+		}
+	}
+	.into())
+}
+
+/// Derives an impl `Decode` block for `minicbor`, decoding the enum from its discriminant
+pub(crate) fn decode(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let portability = model.check_portability()?;
+	let EnumModel {
+		ident,
+		repr,
+		variants,
+		..
+	} = model;
+	let arms = variants
+		.iter()
+		.filter(|variant| variant.is_decoded())
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(#value => ::core::result::Result::Ok(Self::#name),)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#portability
+
+		#[automatically_derived]
+		impl<'b, C> ::enum_discrim::__private::minicbor::Decode<'b, C> for #ident {
+			#[inline]
+			fn decode(
+				d: &mut ::enum_discrim::__private::minicbor::Decoder<'b>,
+				ctx: &mut C,
+			) -> ::core::result::Result<Self, ::enum_discrim::__private::minicbor::decode::Error> {
+				let value: #repr = ::enum_discrim::__private::minicbor::Decode::decode(d, ctx)?;
+				match value {
+					#(#arms)*
+					_ => ::core::result::Result::Err(::enum_discrim::__private::minicbor::decode::Error::message(
+						::core::concat!("Tried to convert an invalid value into a ", ::core::stringify!(#ident)),
+					)),
+				}
+			}
+		}
+	}
+	.into())
+}
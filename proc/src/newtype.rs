@@ -0,0 +1,117 @@
+//! Provides [the parsing struct](NewtypeInput) for the [`Newtype`](crate::derive_newtype) derive macro
+
+use crate::{options::EnumOptions, PrimitiveRepresentation};
+use darling::{ast::Data, FromAttributes, FromDeriveInput};
+use proc_macro::TokenStream;
+use syn::{Attribute, Field, Ident, Visibility};
+
+/// Parsing struct for the [`Newtype`](crate::derive_newtype) derive macro
+#[derive(Debug, FromDeriveInput)]
+#[darling(supports(struct_newtype), forward_attrs(discrim))]
+struct NewtypeInput {
+	/// Struct identifier
+	ident: Ident,
+	/// Struct visibility
+	vis: Visibility,
+	/// Field wrapped by the struct
+	data: Data<(), Field>,
+	/// Forwarded attributes
+	attrs: Vec<Attribute>,
+}
+
+/// Derives `TryFrom<repr>` and `From<Self> for repr` impl blocks for a newtype accepting the values of the `valid` ranges,
+/// and an impl block containing the `consts` values
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	use syn::{DeriveInput, Type, TypePath};
+
+	let item: DeriveInput = syn::parse(item)?;
+	let NewtypeInput {
+		ident,
+		vis,
+		data,
+		attrs,
+	} = NewtypeInput::from_derive_input(&item)?;
+	let Data::Struct(fields) = data else {
+		unreachable!()
+	};
+	let field = &fields.fields[0];
+	let repr: PrimitiveRepresentation = match &field.ty {
+		Type::Path(TypePath { qself: None, path }) => {
+			darling::util::path_to_string(path)
+				.parse()
+				.map_err(|err| darling::Error::custom(err).with_span(&field.ty))?
+		}
+		ty => {
+			return Err(darling::Error::custom("expected a primitive integer type").with_span(ty));
+		}
+	};
+	let options = EnumOptions::from_attributes(&attrs)?;
+	if options.valid.is_empty() {
+		return Err(darling::Error::custom(
+			"missing `#[discrim(valid = \"...\")]` option, listing the accepted values",
+		)
+		.with_span(&ident));
+	}
+
+	let mut errors = darling::Error::accumulator();
+	let consts = options
+		.consts
+		.0
+		.iter()
+		.filter_map(|(name, value)| {
+			if !repr.fits(&value.to_string())
+				|| !options.valid.iter().any(|range| range.contains(*value))
+			{
+				errors.push(
+					darling::Error::custom("value is not within the `valid` ranges")
+						.with_span(name),
+				);
+				return None;
+			}
+			let literal = crate::options::int_literal(*value);
+			let doc = format!("`{value}`");
+
+			Some(quote::quote! {
+				#[doc = #doc]
+				#vis const #name: Self = Self(#literal);
+			})
+		})
+		.collect::<Vec<_>>();
+	errors.finish()?;
+	let ranges = &options.valid;
+
+	Ok(quote::quote! {
+		impl #ident {
+			#(#consts)*
+
+			/// Returns the wrapped value
+			#[inline]
+			#vis const fn get(self) -> #repr {
+				self.0
+			}
+		}
+
+		#[automatically_derived]
+		impl ::core::convert::TryFrom<#repr> for #ident {
+			type Error = ::enum_discrim::TryFromError;
+
+			#[inline]
+			fn try_from(value: #repr) -> ::core::result::Result<Self, Self::Error> {
+				#[allow(unreachable_patterns)]
+				match value {
+					#(#ranges)|* => ::core::result::Result::Ok(Self(value)),
+					_ => ::core::result::Result::Err(Self::Error::new(::core::stringify!(#ident))),
+				}
+			}
+		}
+
+		#[automatically_derived]
+		impl ::core::convert::From<#ident> for #repr {
+			#[inline]
+			fn from(value: #ident) -> Self {
+				value.0
+			}
+		}
+	}
+	.into())
+}
@@ -0,0 +1,510 @@
+//! Provides [the parsing structs](EnumOptions) for the `#[discrim(...)]` attributes
+//!
+//! All derive macros parse the same attributes, so that an option meant for one derive macro
+//! is never rejected by another one.
+
+use crate::{case::RenameRule, PrimitiveRepresentation};
+use darling::{
+	util::{Override, SpannedValue},
+	FromAttributes, FromMeta,
+};
+use quote::ToTokens;
+use syn::{ext::IdentExt, Ident, Lit, Meta, NestedMeta, Path, Variant};
+
+/// Parsing struct for the `#[discrim(...)]` attributes of an enum
+#[derive(Debug, Default, FromAttributes)]
+#[darling(attributes(discrim))]
+pub(crate) struct EnumOptions {
+	/// Casing convention applied to the names of the variants
+	#[darling(default)]
+	pub(crate) rename_all: Option<RenameRule>,
+	/// Whether the name parser ignores ASCII case
+	#[darling(default)]
+	pub(crate) ascii_case_insensitive: bool,
+	/// Name of the newtype wrapping the discriminant, generated by the [`Discriminants`](crate::derive_discriminants) derive macro
+	/// and converted from by the [`TryFrom`](crate::derive_try_from) derive macro
+	///
+	/// If no name is given, the newtype is named after the enum with a `Discriminant` suffix.
+	#[darling(default)]
+	pub(crate) newtype: Option<Override<Ident>>,
+	/// Name of the fieldless enum mirroring the variants, generated by the [`Discriminants`](crate::derive_discriminants) derive macro
+	///
+	/// If no name is given, the enum is named after the enum with a `Kind` suffix.
+	#[darling(default)]
+	pub(crate) kind: Option<Override<Ident>>,
+	/// Whether the [`Discriminants`](crate::derive_discriminants) derive macro also generates a marker type for each variant
+	#[darling(default)]
+	pub(crate) markers: bool,
+	/// Strategy used by the [`Discriminants`](crate::derive_discriminants) derive macro to compute the `NEXT_FREE_DISCRIMINANT` const
+	///
+	/// If no strategy is given, the value following the highest discriminant is used.
+	#[darling(default)]
+	pub(crate) next_free: Option<Override<NextFree>>,
+	/// Path of the JSON manifest written by the [`Discriminants`](crate::derive_discriminants) derive macro
+	///
+	/// If no path is given, the manifest is written in `OUT_DIR`.
+	#[darling(default)]
+	pub(crate) manifest: Option<Override<String>>,
+	/// Whether the [`Discriminants`](crate::derive_discriminants) derive macro also generates an `is_*` predicate for each variant
+	#[darling(default)]
+	pub(crate) predicates: bool,
+	/// Whether the [`Discriminants`](crate::derive_discriminants) derive macro also generates `as_*` and `into_*` accessors for each variant with fields
+	#[darling(default)]
+	pub(crate) accessors: bool,
+	/// Whether the [`Discriminants`](crate::derive_discriminants) derive macro also generates a [`Debug`] impl block showing the discriminant of each variant
+	#[darling(default)]
+	pub(crate) debug: bool,
+	/// Function called by the [`TryFrom`](crate::derive_try_from) derive macro with the invalid value before returning an error
+	#[darling(default)]
+	pub(crate) on_unknown: Option<Path>,
+	/// Error type of the impl blocks generated by the [`TryFrom`](crate::derive_try_from) derive macro
+	#[darling(default)]
+	pub(crate) error: Option<Path>,
+	/// Function converting a `TryFromError` into the error type given with `#[discrim(error = "...")]`
+	///
+	/// If no function is given, the error type must implement `From<TryFromError>`.
+	#[darling(default)]
+	pub(crate) map_err: Option<Path>,
+	/// Whether the [`TryFrom`](crate::derive_try_from) derive macro returns a `TryFromError<Self>` rather than a `TryFromError`
+	#[darling(default)]
+	pub(crate) typed_error: bool,
+	/// Integer types narrower than the primitive representation, which the [`Into`](crate::derive_into) derive macro also converts into
+	///
+	/// All discriminants must fit in these types.
+	#[darling(multiple)]
+	pub(crate) narrow: Vec<PrimitiveRepresentation>,
+	/// Strategy used by the [`TryFrom`](crate::derive_try_from) derive macro to find the variant of a value
+	#[darling(default)]
+	pub(crate) lookup: Lookup,
+	/// Number of bits holding the discriminant, used by the [`Bits`](crate::derive_bits) and [`Specifier`](crate::derive_specifier) derive macros
+	#[darling(default)]
+	pub(crate) bits: Option<u32>,
+	/// Whether the [`TryFrom`](crate::derive_try_from) derive macro compares the value with every discriminant without branching,
+	/// so that the conversion time of valid values does not depend on the value
+	///
+	/// Only the lookup is constant-time, the conversion still branches on its outcome.
+	#[darling(default)]
+	pub(crate) const_time: bool,
+	/// Ranges of discriminants rejected by the [`TryFrom`](crate::derive_try_from) derive macro with a distinct error
+	///
+	/// No variant may have a discriminant within these ranges.
+	#[darling(multiple)]
+	pub(crate) reserved: Vec<ValueRange>,
+	/// Ranges of values accepted by the [`Newtype`](crate::derive_newtype) derive macro
+	#[darling(multiple)]
+	pub(crate) valid: Vec<ValueRange>,
+	/// Named values of the [`Newtype`](crate::derive_newtype) derive macro, generated as consts
+	#[darling(default)]
+	pub(crate) consts: Constants,
+	/// Whether the [`TryFrom`](crate::derive_try_from) derive macro also generates a conversion from `f64`
+	#[darling(default)]
+	pub(crate) try_from_f64: bool,
+	/// Whether the [`TryFrom`](crate::derive_try_from) and [`Into`](crate::derive_into) derive macros also convert from and into `char`
+	#[darling(default)]
+	pub(crate) char: bool,
+	/// Whether the [`TryFrom`](crate::derive_try_from) and [`Into`](crate::derive_into) derive macros also convert from and into `c_int`
+	#[darling(default)]
+	pub(crate) c_int: bool,
+	/// Whether the [`TryFrom`](crate::derive_try_from) and [`Into`](crate::derive_into) derive macros also convert from and into `c_uint`
+	#[darling(default)]
+	pub(crate) c_uint: bool,
+	/// Whether the [`TryFrom`](crate::derive_try_from) derive macro also generates [Kani](https://model-checking.github.io/kani/) proof harnesses
+	#[darling(default)]
+	pub(crate) kani_proofs: bool,
+	/// Representation used by the [`SerdeRepr`](crate::derive_serde_repr) and [`JsonValue`](crate::derive_json_value) derive macros
+	#[darling(default)]
+	pub(crate) serde: SerdeMode,
+	/// Byte order of the discriminant written by the [`Writable`](crate::derive_speedy_writable), [`Readable`](crate::derive_speedy_readable)
+	/// and [`BytesCodec`](crate::derive_bytes_codec) derive macros
+	///
+	/// If no byte order is given, the one of the `speedy` context is used, and `bytes` uses big-endian.
+	#[darling(default)]
+	pub(crate) endianness: Option<Endianness>,
+	/// Type of the discriminants, for enums without a `#[repr(inttype)]` attribute
+	///
+	/// With this option, the generated code never relies on the layout of the enum.
+	#[darling(default)]
+	pub(crate) logical_repr: Option<PrimitiveRepresentation>,
+	/// Hash function assigning the discriminants of the variants without an explicit one, from their names
+	///
+	/// This option requires `#[discrim(logical_repr = "...")]`, as derive macros cannot change the actual discriminants.
+	#[darling(default)]
+	pub(crate) hash_values: Option<HashValues>,
+	/// Whether the derive macros writing the discriminant to a wire format reject `usize` and `isize` representations,
+	/// instead of only warning
+	#[darling(default)]
+	pub(crate) deny_platform_dependent: bool,
+	/// Whether several variants may share a discriminant, the conversions from a discriminant returning the canonical variant
+	///
+	/// This option requires `#[discrim(logical_repr = "...")]`, as the compiler rejects duplicate actual discriminants.
+	#[darling(default)]
+	pub(crate) allow_aliases: bool,
+	/// Configuration predicate gating all the generated items
+	#[darling(default)]
+	pub(crate) cfg: Option<CfgPredicate>,
+	/// Oldest compiler version the generated code must support
+	#[darling(default)]
+	pub(crate) msrv: Option<Msrv>,
+}
+impl EnumOptions {
+	/// Returns the name of the newtype wrapping the discriminant of the given enum, if any
+	pub(crate) fn newtype_ident(&self, ident: &Ident) -> Option<Ident> {
+		self.newtype.as_ref().map(|newtype| {
+			newtype
+				.clone()
+				.unwrap_or_else(|| quote::format_ident!("{}Discriminant", ident))
+		})
+	}
+
+	/// Returns the name of the given variant, as used by the name-based derive macros
+	///
+	/// The `rename` option of the variant takes precedence over the `rename_all` option of the enum.
+	pub(crate) fn variant_name(&self, variant: &Ident, options: &VariantOptions) -> String {
+		if let Some(rename) = &options.rename {
+			return rename.clone();
+		}
+
+		let name = variant.unraw().to_string();
+		match self.rename_all {
+			Some(rule) => rule.apply(&name),
+			None => name,
+		}
+	}
+}
+
+/// Parsing struct for the `#[discrim(...)]` attributes of a variant
+#[derive(Debug, Default, FromAttributes)]
+#[darling(attributes(discrim))]
+pub(crate) struct VariantOptions {
+	/// Name of the variant used by the name-based derive macros
+	#[darling(default)]
+	pub(crate) rename: Option<String>,
+	/// Name of the discriminant const generated by the [`Discriminants`](crate::derive_discriminants) derive macro
+	#[darling(default)]
+	pub(crate) const_name: Option<Ident>,
+	/// Path to the category of the variant, returned by the `category` function generated by the [`Discriminants`](crate::derive_discriminants) derive macro
+	#[darling(default)]
+	pub(crate) category: Option<Path>,
+	/// Whether the variant is the one converted from its discriminant, when other variants share it
+	#[darling(default)]
+	pub(crate) canonical: bool,
+	/// Logical discriminant of the variant, overriding its actual one
+	#[darling(default)]
+	pub(crate) value: Option<LogicalValue>,
+	/// Whether the variant is excluded from the discriminant consts and from the conversions from a discriminant
+	#[darling(default)]
+	pub(crate) skip: bool,
+	/// Whether the variant is converted from all the values which are not the discriminant of another variant
+	#[darling(default)]
+	pub(crate) default: bool,
+	/// Other values converted into the variant by the [`TryFrom`](crate::derive_try_from) derive macro
+	#[darling(multiple, rename = "alias")]
+	pub(crate) aliases: Vec<LogicalValue>,
+}
+impl VariantOptions {
+	/// Parses the `#[discrim(...)]` attributes of each given variant, accumulating all errors
+	pub(crate) fn from_variants<'v, I>(variants: I) -> darling::Result<Vec<Self>>
+	where
+		I: IntoIterator<Item = &'v SpannedValue<Variant>>,
+	{
+		let mut errors = darling::Error::accumulator();
+		let options = variants
+			.into_iter()
+			.filter_map(|variant| errors.handle(Self::from_attributes(&variant.attrs)))
+			.collect();
+		errors.finish_with(options)
+	}
+}
+
+/// Representation of an enum in the code generated by the [`SerdeRepr`](crate::derive_serde_repr) derive macro
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SerdeMode {
+	/// The enum is represented as its discriminant
+	#[default]
+	Value,
+	/// The enum is represented as the name of its variant
+	Name,
+	/// The enum is represented as a struct of its discriminant and the fields of its variant
+	Tagged,
+	/// The enum is represented as the name of its variant in human-readable formats, and as its discriminant otherwise
+	HumanReadable,
+	/// The enum implements `Serialize` and `Deserialize` as its discriminant formatted as a string, for use as a map key
+	Key,
+}
+impl FromMeta for SerdeMode {
+	#[inline]
+	fn from_string(value: &str) -> darling::Result<Self> {
+		match value {
+			"value" => Ok(Self::Value),
+			"name" => Ok(Self::Name),
+			"tagged" => Ok(Self::Tagged),
+			"human_readable" => Ok(Self::HumanReadable),
+			"key" => Ok(Self::Key),
+			_ => Err(darling::Error::unknown_value(value)),
+		}
+	}
+}
+
+/// Compiler version given with `#[discrim(msrv = "...")]`
+///
+/// Only the minor version is kept, as all supported versions are `1.x`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Msrv(pub(crate) u32);
+impl FromMeta for Msrv {
+	#[inline]
+	fn from_string(value: &str) -> darling::Result<Self> {
+		let mut parts = value.split('.');
+		match (
+			parts.next(),
+			parts.next().map(str::parse::<u32>),
+			parts.next().map(str::parse::<u32>),
+			parts.next(),
+		) {
+			(Some("1"), Some(Ok(minor)), None | Some(Ok(_)), None) => Ok(Self(minor)),
+			_ => Err(darling::Error::custom(format!(
+				"invalid compiler version `{value}`, expected `1.x` or `1.x.y`"
+			))),
+		}
+	}
+}
+
+/// Strategy given with `#[discrim(next_free = "...")]`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NextFree {
+	/// The smallest free value greater than the highest discriminant
+	#[default]
+	Max,
+	/// The smallest free value greater than the lowest discriminant
+	Gap,
+}
+impl FromMeta for NextFree {
+	#[inline]
+	fn from_string(value: &str) -> darling::Result<Self> {
+		match value {
+			"max" => Ok(Self::Max),
+			"gap" => Ok(Self::Gap),
+			_ => Err(darling::Error::unknown_value(value)),
+		}
+	}
+}
+
+/// Strategy given with `#[discrim(lookup = "...")]`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Lookup {
+	/// The value is matched against each discriminant
+	#[default]
+	Match,
+	/// The value is checked against the bounds of the contiguous discriminants
+	Range,
+	/// The value indexes a table of booleans spanning from the lowest to the highest discriminant
+	Table,
+	/// The value is searched in a sorted array of the discriminants
+	BinarySearch,
+}
+impl FromMeta for Lookup {
+	#[inline]
+	fn from_string(value: &str) -> darling::Result<Self> {
+		match value {
+			"match" => Ok(Self::Match),
+			"range" => Ok(Self::Range),
+			"table" => Ok(Self::Table),
+			"binary_search" => Ok(Self::BinarySearch),
+			_ => Err(darling::Error::unknown_value(value)),
+		}
+	}
+}
+
+/// Hash function given with `#[discrim(hash_values = "...")]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HashValues {
+	/// 32-bit [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/index.html)
+	Fnv1a32,
+}
+impl HashValues {
+	/// Hashes the given variant name
+	pub(crate) fn hash(self, name: &str) -> u128 {
+		match self {
+			Self::Fnv1a32 => {
+				let hash = name.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+					(hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+				});
+				u128::from(hash)
+			}
+		}
+	}
+}
+impl FromMeta for HashValues {
+	#[inline]
+	fn from_string(value: &str) -> darling::Result<Self> {
+		match value {
+			"fnv1a32" => Ok(Self::Fnv1a32),
+			_ => Err(darling::Error::unknown_value(value)),
+		}
+	}
+}
+
+/// Byte order given with `#[discrim(endianness = "...")]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Endianness {
+	/// Least significant byte first
+	Little,
+	/// Most significant byte first
+	Big,
+}
+impl FromMeta for Endianness {
+	#[inline]
+	fn from_string(value: &str) -> darling::Result<Self> {
+		match value {
+			"little" => Ok(Self::Little),
+			"big" => Ok(Self::Big),
+			_ => Err(darling::Error::unknown_value(value)),
+		}
+	}
+}
+
+/// Converts the given integer into an unsuffixed literal, negated if needed
+pub(crate) fn int_literal(value: i128) -> proc_macro2::TokenStream {
+	let magnitude = proc_macro2::Literal::u128_unsuffixed(value.unsigned_abs());
+	if value < 0 {
+		quote::quote!(-#magnitude)
+	} else {
+		magnitude.into_token_stream()
+	}
+}
+
+/// Range of values given with `#[discrim(reserved = "...")]` or `#[discrim(valid = "...")]`, with inclusive bounds
+///
+/// The range is written as a single integer, `start..=end` or `start..end`,
+/// with integers in any of the bases accepted by Rust literals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ValueRange {
+	/// Lowest value of the range
+	start: i128,
+	/// Highest value of the range
+	end: i128,
+}
+impl ValueRange {
+	/// Returns whether the given value is within the range
+	#[inline]
+	pub(crate) const fn contains(&self, value: i128) -> bool {
+		self.start <= value && value <= self.end
+	}
+
+	/// Parses an integer with an optional sign and base prefix
+	fn parse_int(value: &str) -> darling::Result<i128> {
+		let value = value.trim();
+		let (negative, digits) = match value.strip_prefix('-') {
+			Some(digits) => (true, digits.trim_start()),
+			None => (false, value),
+		};
+		let digits = digits.replace('_', "");
+		let (radix, digits) = if let Some(digits) = digits.strip_prefix("0x") {
+			(16, digits)
+		} else if let Some(digits) = digits.strip_prefix("0o") {
+			(8, digits)
+		} else if let Some(digits) = digits.strip_prefix("0b") {
+			(2, digits)
+		} else {
+			(10, digits.as_str())
+		};
+		let magnitude = i128::from_str_radix(digits, radix)
+			.map_err(|_| darling::Error::unknown_value(value))?;
+		Ok(if negative { -magnitude } else { magnitude })
+	}
+}
+impl FromMeta for ValueRange {
+	fn from_string(value: &str) -> darling::Result<Self> {
+		let (start, end) = if let Some((start, end)) = value.split_once("..=") {
+			(Self::parse_int(start)?, Self::parse_int(end)?)
+		} else if let Some((start, end)) = value.split_once("..") {
+			let end = Self::parse_int(end)?
+				.checked_sub(1)
+				.ok_or_else(|| darling::Error::unknown_value(value))?;
+			(Self::parse_int(start)?, end)
+		} else {
+			let value = Self::parse_int(value)?;
+			(value, value)
+		};
+
+		if start > end {
+			return Err(darling::Error::custom("range is empty"));
+		}
+		Ok(Self { start, end })
+	}
+}
+impl ToTokens for ValueRange {
+	fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+		let start = int_literal(self.start);
+		let end = int_literal(self.end);
+		tokens.extend(quote::quote!(#start..=#end));
+	}
+}
+
+/// Named values given with `#[discrim(consts(NAME = value, ...))]`, in declaration order
+///
+/// Negative values are written as strings, e.g. `NONE = "-1"`.
+#[derive(Debug, Default)]
+pub(crate) struct Constants(pub(crate) Vec<(Ident, i128)>);
+impl FromMeta for Constants {
+	fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+		let mut errors = darling::Error::accumulator();
+		let constants = items
+			.iter()
+			.filter_map(|item| {
+				let NestedMeta::Meta(Meta::NameValue(meta)) = item else {
+					errors.push(darling::Error::custom("expected `NAME = value`").with_span(item));
+					return None;
+				};
+				let Some(ident) = meta.path.get_ident() else {
+					errors.push(
+						darling::Error::custom("expected an identifier").with_span(&meta.path),
+					);
+					return None;
+				};
+				let value = match &meta.lit {
+					Lit::Int(value) => value.base10_parse().map_err(darling::Error::from),
+					Lit::Str(value) => ValueRange::parse_int(&value.value()),
+					lit => Err(darling::Error::unexpected_lit_type(lit)),
+				};
+				errors
+					.handle(value.map_err(|err| err.with_span(&meta.lit)))
+					.map(|value| (ident.clone(), value))
+			})
+			.collect();
+		errors.finish_with(Self(constants))
+	}
+}
+
+/// Logical discriminant given with `#[discrim(value = ...)]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LogicalValue(pub(crate) i128);
+impl FromMeta for LogicalValue {
+	fn from_value(value: &Lit) -> darling::Result<Self> {
+		match value {
+			Lit::Int(value) => value.base10_parse().map(Self).map_err(darling::Error::from),
+			Lit::Str(value) => ValueRange::parse_int(&value.value()).map(Self),
+			lit => Err(darling::Error::unexpected_lit_type(lit)),
+		}
+		.map_err(|err| err.with_span(value))
+	}
+}
+
+/// Configuration predicate given with `#[discrim(cfg(...))]`
+#[derive(Debug)]
+pub(crate) struct CfgPredicate(NestedMeta);
+impl FromMeta for CfgPredicate {
+	#[inline]
+	fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+		match items {
+			[predicate] => Ok(Self(predicate.clone())),
+			[] => Err(darling::Error::too_few_items(1)),
+			_ => Err(darling::Error::too_many_items(1)),
+		}
+	}
+}
+impl ToTokens for CfgPredicate {
+	#[inline]
+	fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+		self.0.to_tokens(tokens);
+	}
+}
@@ -0,0 +1,72 @@
+//! Provides [the parsing struct](OrdinalInput) for the [`Ordinal`](crate::derive_ordinal) derive macro
+
+use darling::{ast::Data, util::SpannedValue, FromDeriveInput};
+use proc_macro::TokenStream;
+use syn::{Ident, Variant};
+
+/// Parsing struct for the [`Ordinal`](crate::derive_ordinal) derive macro
+#[derive(Debug, FromDeriveInput)]
+#[darling(supports(enum_unit))]
+struct OrdinalInput {
+	/// Enum identifier
+	ident: Ident,
+	/// Variants contained in the enum
+	data: Data<SpannedValue<Variant>, ()>,
+}
+
+/// Derives an impl [`Ordinal`] block
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	use syn::DeriveInput;
+
+	let item: DeriveInput = syn::parse(item)?;
+	let OrdinalInput { ident, data } = OrdinalInput::from_derive_input(&item)?;
+	let Data::Enum(data) = data else {
+		unreachable!()
+	};
+
+	let count = data.len();
+	let (ordinal_arms, from_ordinal_arms): (Vec<_>, Vec<_>) = data
+		.iter()
+		.enumerate()
+		.map(|(ordinal, variant)| {
+			let span = variant.span();
+			let name = &variant.ident;
+			(
+				quote::quote_spanned!(span=> Self::#name => #ordinal,),
+				quote::quote_spanned!(span=> #ordinal => ::core::option::Option::Some(Self::#name),),
+			)
+		})
+		.unzip();
+
+	Ok(quote::quote! {
+		#[doc(hidden)]
+		impl #ident {
+			/// Returns the position of the given variant in the enum declaration, in const contexts
+			#[inline]
+			pub const fn __ordinal(&self) -> usize {
+				match *self {
+					#(#ordinal_arms)*
+				}
+			}
+		}
+
+		#[automatically_derived]
+		impl ::enum_discrim::Ordinal for #ident {
+			const COUNT: usize = #count;
+
+			#[inline]
+			fn ordinal(&self) -> usize {
+				self.__ordinal()
+			}
+
+			#[inline]
+			fn from_ordinal(ordinal: usize) -> ::core::option::Option<Self> {
+				match ordinal {
+					#(#from_ordinal_arms)*
+					_ => ::core::option::Option::None,
+				}
+			}
+		}
+	}
+	.into())
+}
@@ -0,0 +1,127 @@
+//! Provides the code generation for the [`ToSql`](crate::derive_to_sql) and [`FromSql`](crate::derive_from_sql) derive macros
+
+use crate::{EnumModel, PrimitiveRepresentation};
+use proc_macro::TokenStream;
+
+/// Returns the narrowest `PostgreSQL` integer type holding every value of the primitive representation
+///
+/// `SMALLINT`, `INTEGER` and `BIGINT` are respectively mapped to `i16`, `i32` and `i64`.
+fn sql_ty(model: &EnumModel) -> darling::Result<proc_macro2::TokenStream> {
+	use PrimitiveRepresentation as Repr;
+
+	match model.repr {
+		Repr::u8 | Repr::i8 | Repr::i16 => Ok(quote::quote!(i16)),
+		Repr::u16 | Repr::i32 => Ok(quote::quote!(i32)),
+		Repr::u32 | Repr::i64 => Ok(quote::quote!(i64)),
+		repr => Err(darling::Error::custom(format!(
+			"`{repr}` discriminants have no corresponding PostgreSQL integer type"
+		))
+		.with_span(&model.ident)),
+	}
+}
+
+/// Derives an impl `ToSql` block, writing the discriminant as a `SMALLINT`, an `INTEGER` or a `BIGINT`
+pub(crate) fn to_sql(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let sql_ty = sql_ty(&model)?;
+	let repr = model.repr_ty();
+	let EnumModel {
+		ident, variants, ..
+	} = model;
+	let arms = variants
+		.iter()
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(Self::#name => #value,)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl ::enum_discrim::__private::postgres_types::ToSql for #ident {
+			#[inline]
+			fn to_sql(
+				&self,
+				ty: &::enum_discrim::__private::postgres_types::Type,
+				out: &mut ::enum_discrim::__private::postgres_types::private::BytesMut,
+			) -> ::core::result::Result<
+				::enum_discrim::__private::postgres_types::IsNull,
+				::enum_discrim::__private::alloc::boxed::Box<
+					dyn ::core::error::Error + ::core::marker::Sync + ::core::marker::Send,
+				>,
+			> {
+				let value: #repr = match self {
+					#(#arms)*
+				};
+				::enum_discrim::__private::postgres_types::ToSql::to_sql(
+					&<#sql_ty as ::core::convert::From<#repr>>::from(value),
+					ty,
+					out,
+				)
+			}
+
+			#[inline]
+			fn accepts(ty: &::enum_discrim::__private::postgres_types::Type) -> bool {
+				<#sql_ty as ::enum_discrim::__private::postgres_types::ToSql>::accepts(ty)
+			}
+
+			::enum_discrim::__private::postgres_types::to_sql_checked!();
+		}
+	}
+	.into())
+}
+
+/// Derives an impl `FromSql` block, reading the discriminant from a `SMALLINT`, an `INTEGER` or a `BIGINT`
+pub(crate) fn from_sql(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let sql_ty = sql_ty(&model)?;
+	let repr = model.repr_ty();
+	let skipped = model.skipped_arm();
+	let reserved = model.reserved_arm();
+	let EnumModel {
+		ident, variants, ..
+	} = model;
+	let arms = variants
+		.iter()
+		.filter(|variant| variant.is_decoded())
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(#value => ::core::result::Result::Ok(Self::#name),)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl<'a> ::enum_discrim::__private::postgres_types::FromSql<'a> for #ident {
+			#[inline]
+			fn from_sql(
+				ty: &::enum_discrim::__private::postgres_types::Type,
+				raw: &'a [u8],
+			) -> ::core::result::Result<
+				Self,
+				::enum_discrim::__private::alloc::boxed::Box<
+					dyn ::core::error::Error + ::core::marker::Sync + ::core::marker::Send,
+				>,
+			> {
+				let value = <#sql_ty as ::enum_discrim::__private::postgres_types::FromSql<'a>>::from_sql(ty, raw)?;
+				let value = <#repr as ::core::convert::TryFrom<#sql_ty>>::try_from(value)
+					.map_err(|_| ::enum_discrim::TryFromError::new_out_of_range(::core::stringify!(#ident)))?;
+				let value: ::core::result::Result<Self, ::enum_discrim::TryFromError> = match value {
+					#(#arms)*
+					#skipped
+					#reserved
+					_ => ::core::result::Result::Err(::enum_discrim::TryFromError::new(::core::stringify!(#ident))),
+				};
+				::core::result::Result::Ok(value?)
+			}
+
+			#[inline]
+			fn accepts(ty: &::enum_discrim::__private::postgres_types::Type) -> bool {
+				<#sql_ty as ::enum_discrim::__private::postgres_types::FromSql<'a>>::accepts(ty)
+			}
+		}
+	}
+	.into())
+}
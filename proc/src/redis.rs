@@ -0,0 +1,86 @@
+//! Provides the code generation for the [`ToRedisArgs`](crate::derive_to_redis_args) and [`FromRedisValue`](crate::derive_from_redis_value) derive macros
+
+use crate::EnumModel;
+use proc_macro::TokenStream;
+
+/// Derives an impl `ToRedisArgs` block, writing the discriminant as an integer
+pub(crate) fn to_redis_args(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let portability = model.check_portability()?;
+	let EnumModel {
+		ident,
+		repr,
+		variants,
+		..
+	} = model;
+	let arms = variants
+		.iter()
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(Self::#name => #value,)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#portability
+
+		#[automatically_derived]
+		impl ::enum_discrim::__private::redis::ToRedisArgs for #ident {
+			#[inline]
+			fn write_redis_args<W>(&self, out: &mut W)
+			where
+				W: ?Sized + ::enum_discrim::__private::redis::RedisWrite,
+			{
+				let value: #repr = match self {
+					#(#arms)*
+				};
+				::enum_discrim::__private::redis::ToRedisArgs::write_redis_args(&value, out);
+			}
+		}
+	}
+	.into())
+}
+
+/// Derives an impl `FromRedisValue` block, reading the discriminant as an integer
+pub(crate) fn from_redis_value(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let portability = model.check_portability()?;
+	let EnumModel {
+		ident,
+		repr,
+		variants,
+		..
+	} = model;
+	let arms = variants
+		.iter()
+		.filter(|variant| variant.is_decoded())
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(#value => ::core::result::Result::Ok(Self::#name),)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#portability
+
+		#[automatically_derived]
+		impl ::enum_discrim::__private::redis::FromRedisValue for #ident {
+			#[inline]
+			fn from_redis_value(
+				value: &::enum_discrim::__private::redis::Value,
+			) -> ::enum_discrim::__private::redis::RedisResult<Self> {
+				let value: #repr = ::enum_discrim::__private::redis::FromRedisValue::from_redis_value(value)?;
+				match value {
+					#(#arms)*
+					_ => ::core::result::Result::Err(::core::convert::From::from((
+						::enum_discrim::__private::redis::ErrorKind::TypeError,
+						::core::concat!("Tried to convert an invalid value into a ", ::core::stringify!(#ident)),
+					))),
+				}
+			}
+		}
+	}
+	.into())
+}
@@ -0,0 +1,253 @@
+//! Provides [the parsing struct](ReflectInput) for the [`Reflect`](crate::derive_reflect) derive macro
+
+use darling::{ast::Data, util::SpannedValue, FromDeriveInput};
+use proc_macro::TokenStream;
+use syn::{Ident, Variant};
+
+/// Parsing struct for the [`Reflect`](crate::derive_reflect) derive macro
+#[derive(Debug, FromDeriveInput)]
+#[darling(supports(enum_unit))]
+struct ReflectInput {
+	/// Enum identifier
+	ident: Ident,
+	/// Variants contained in the enum
+	data: Data<SpannedValue<Variant>, ()>,
+}
+
+/// Derives impl blocks for the enum reflection traits of `bevy_reflect`
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	use syn::DeriveInput;
+
+	let item: DeriveInput = syn::parse(item)?;
+	let ReflectInput { ident, data } = ReflectInput::from_derive_input(&item)?;
+	let Data::Enum(data) = data else {
+		unreachable!()
+	};
+
+	let names = data
+		.iter()
+		.map(|variant| variant.ident.to_string())
+		.collect::<Vec<_>>();
+	let variants = data
+		.iter()
+		.map(|variant| &variant.ident)
+		.collect::<Vec<_>>();
+	let ordinals = (0..data.len()).collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		const _: () = {
+			use ::core::any::Any;
+			use ::enum_discrim::__private::alloc::boxed::Box;
+			use ::enum_discrim::__private::bevy_reflect::{
+				utility::NonGenericTypeInfoCell, ApplyError, DynamicEnum, Enum, EnumInfo, FromReflect,
+				FromType, GetTypeRegistration, Reflect, ReflectFromPtr, ReflectFromReflect, ReflectKind,
+				ReflectMut, ReflectOwned, ReflectRef, TypeInfo, TypePath, TypeRegistration, Typed,
+				UnitVariantInfo, VariantFieldIter, VariantInfo, VariantType,
+			};
+
+			#[automatically_derived]
+			impl TypePath for #ident {
+				#[inline]
+				fn type_path() -> &'static str {
+					::core::concat!(::core::module_path!(), "::", ::core::stringify!(#ident))
+				}
+
+				#[inline]
+				fn short_type_path() -> &'static str {
+					::core::stringify!(#ident)
+				}
+			}
+
+			#[automatically_derived]
+			impl Typed for #ident {
+				fn type_info() -> &'static TypeInfo {
+					static CELL: NonGenericTypeInfoCell = NonGenericTypeInfoCell::new();
+					CELL.get_or_set(|| {
+						TypeInfo::Enum(EnumInfo::new::<Self>(&[
+							#(VariantInfo::Unit(UnitVariantInfo::new(#names)),)*
+						]))
+					})
+				}
+			}
+
+			#[automatically_derived]
+			impl GetTypeRegistration for #ident {
+				fn get_type_registration() -> TypeRegistration {
+					let mut registration = TypeRegistration::of::<Self>();
+					registration.insert::<ReflectFromPtr>(FromType::<Self>::from_type());
+					registration.insert::<ReflectFromReflect>(FromType::<Self>::from_type());
+					registration
+				}
+			}
+
+			#[automatically_derived]
+			impl Reflect for #ident {
+				#[inline]
+				fn get_represented_type_info(&self) -> ::core::option::Option<&'static TypeInfo> {
+					::core::option::Option::Some(<Self as Typed>::type_info())
+				}
+
+				#[inline]
+				fn into_any(self: Box<Self>) -> Box<dyn Any> {
+					self
+				}
+
+				#[inline]
+				fn as_any(&self) -> &dyn Any {
+					self
+				}
+
+				#[inline]
+				fn as_any_mut(&mut self) -> &mut dyn Any {
+					self
+				}
+
+				#[inline]
+				fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+					self
+				}
+
+				#[inline]
+				fn as_reflect(&self) -> &dyn Reflect {
+					self
+				}
+
+				#[inline]
+				fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+					self
+				}
+
+				fn try_apply(&mut self, value: &dyn Reflect) -> ::core::result::Result<(), ApplyError> {
+					let ReflectRef::Enum(value) = value.reflect_ref() else {
+						return ::core::result::Result::Err(ApplyError::MismatchedKinds {
+							from_kind: value.reflect_kind(),
+							to_kind: ReflectKind::Enum,
+						});
+					};
+					*self = match value.variant_name() {
+						#(#names => Self::#variants,)*
+						name => {
+							return ::core::result::Result::Err(ApplyError::UnknownVariant {
+								enum_name: <Self as TypePath>::type_path().into(),
+								variant_name: name.into(),
+							});
+						}
+					};
+					::core::result::Result::Ok(())
+				}
+
+				#[inline]
+				fn set(&mut self, value: Box<dyn Reflect>) -> ::core::result::Result<(), Box<dyn Reflect>> {
+					*self = value.take()?;
+					::core::result::Result::Ok(())
+				}
+
+				#[inline]
+				fn reflect_kind(&self) -> ReflectKind {
+					ReflectKind::Enum
+				}
+
+				#[inline]
+				fn reflect_ref(&self) -> ReflectRef {
+					ReflectRef::Enum(self)
+				}
+
+				#[inline]
+				fn reflect_mut(&mut self) -> ReflectMut {
+					ReflectMut::Enum(self)
+				}
+
+				#[inline]
+				fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+					ReflectOwned::Enum(self)
+				}
+
+				#[inline]
+				fn clone_value(&self) -> Box<dyn Reflect> {
+					Box::new(Enum::clone_dynamic(self))
+				}
+			}
+
+			#[automatically_derived]
+			impl Enum for #ident {
+				#[inline]
+				fn field(&self, _name: &str) -> ::core::option::Option<&dyn Reflect> {
+					::core::option::Option::None
+				}
+
+				#[inline]
+				fn field_at(&self, _index: usize) -> ::core::option::Option<&dyn Reflect> {
+					::core::option::Option::None
+				}
+
+				#[inline]
+				fn field_mut(&mut self, _name: &str) -> ::core::option::Option<&mut dyn Reflect> {
+					::core::option::Option::None
+				}
+
+				#[inline]
+				fn field_at_mut(&mut self, _index: usize) -> ::core::option::Option<&mut dyn Reflect> {
+					::core::option::Option::None
+				}
+
+				#[inline]
+				fn index_of(&self, _name: &str) -> ::core::option::Option<usize> {
+					::core::option::Option::None
+				}
+
+				#[inline]
+				fn name_at(&self, _index: usize) -> ::core::option::Option<&str> {
+					::core::option::Option::None
+				}
+
+				#[inline]
+				fn iter_fields(&self) -> VariantFieldIter<'_> {
+					VariantFieldIter::new(self)
+				}
+
+				#[inline]
+				fn field_len(&self) -> usize {
+					0
+				}
+
+				#[inline]
+				fn variant_name(&self) -> &str {
+					match self {
+						#(Self::#variants => #names,)*
+					}
+				}
+
+				#[inline]
+				fn variant_index(&self) -> usize {
+					match self {
+						#(Self::#variants => #ordinals,)*
+					}
+				}
+
+				#[inline]
+				fn variant_type(&self) -> VariantType {
+					VariantType::Unit
+				}
+
+				#[inline]
+				fn clone_dynamic(&self) -> DynamicEnum {
+					DynamicEnum::from_ref::<Self>(self)
+				}
+			}
+
+			#[automatically_derived]
+			impl FromReflect for #ident {
+				fn from_reflect(reflect: &dyn Reflect) -> ::core::option::Option<Self> {
+					let ReflectRef::Enum(value) = reflect.reflect_ref() else {
+						return ::core::option::Option::None;
+					};
+					match value.variant_name() {
+						#(#names => ::core::option::Option::Some(Self::#variants),)*
+						_ => ::core::option::Option::None,
+					}
+				}
+			}
+		};
+	}
+	.into())
+}
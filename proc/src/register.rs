@@ -0,0 +1,17 @@
+//! Provides the code generation for the [`Register`](crate::derive_register) derive macro
+
+use crate::EnumModel;
+use proc_macro::TokenStream;
+
+/// Derives an entry of the `enum_discrim::registry`, describing the enum and its discriminants
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse(item)?;
+	let info = crate::describe::enum_info(&model)?;
+
+	Ok(quote::quote! {
+		::enum_discrim::__private::inventory::submit! {
+			::enum_discrim::registry::RegisteredEnum::new(::core::module_path!(), &#info)
+		}
+	}
+	.into())
+}
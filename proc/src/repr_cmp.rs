@@ -0,0 +1,66 @@
+//! Provides the code generation for the [`ReprCmp`](crate::derive_repr_cmp) derive macro
+
+use crate::EnumModel;
+use proc_macro::TokenStream;
+
+/// Derives impl `PartialEq` and `PartialOrd` blocks between the enum and its primitive representation, in both directions
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let EnumModel {
+		ident,
+		repr,
+		variants,
+		..
+	} = EnumModel::parse_unit(item)?;
+	let arms = variants.iter().map(|variant| {
+		let name = &variant.ident;
+		let value = &variant.value;
+		quote::quote!(#ident::#name => #value,)
+	});
+
+	Ok(quote::quote! {
+		const _: () = {
+			use ::core::cmp::Ordering;
+
+			/// Returns the discriminant of the given variant
+			#[inline]
+			const fn discriminant(value: &#ident) -> #repr {
+				match *value {
+					#(#arms)*
+				}
+			}
+
+			#[automatically_derived]
+			impl ::core::cmp::PartialEq<#repr> for #ident {
+				#[inline]
+				fn eq(&self, other: &#repr) -> bool {
+					discriminant(self) == *other
+				}
+			}
+
+			#[automatically_derived]
+			impl ::core::cmp::PartialEq<#ident> for #repr {
+				#[inline]
+				fn eq(&self, other: &#ident) -> bool {
+					*self == discriminant(other)
+				}
+			}
+
+			#[automatically_derived]
+			impl ::core::cmp::PartialOrd<#repr> for #ident {
+				#[inline]
+				fn partial_cmp(&self, other: &#repr) -> ::core::option::Option<Ordering> {
+					discriminant(self).partial_cmp(other)
+				}
+			}
+
+			#[automatically_derived]
+			impl ::core::cmp::PartialOrd<#ident> for #repr {
+				#[inline]
+				fn partial_cmp(&self, other: &#ident) -> ::core::option::Option<Ordering> {
+					self.partial_cmp(&discriminant(other))
+				}
+			}
+		};
+	}
+	.into())
+}
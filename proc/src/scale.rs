@@ -0,0 +1,95 @@
+//! Provides the code generation for the [`ScaleEncode`](crate::derive_scale_encode) and [`ScaleDecode`](crate::derive_scale_decode) derive macros
+
+use crate::EnumModel;
+use proc_macro::TokenStream;
+
+/// Derives impl `Encode` and `EncodeLike` blocks, writing the discriminant with the width of the primitive representation
+pub(crate) fn encode(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let portability = model.check_portability()?;
+	let EnumModel {
+		ident,
+		repr,
+		variants,
+		..
+	} = model;
+	let arms = variants
+		.iter()
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(Self::#name => #value,)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#portability
+
+		#[automatically_derived]
+		impl ::enum_discrim::__private::parity_scale_codec::Encode for #ident {
+			#[inline]
+			fn size_hint(&self) -> usize {
+				::core::mem::size_of::<#repr>()
+			}
+
+			#[inline]
+			fn encode_to<T>(&self, dest: &mut T)
+			where
+				T: ::enum_discrim::__private::parity_scale_codec::Output + ?Sized,
+			{
+				let value: #repr = match self {
+					#(#arms)*
+				};
+				::enum_discrim::__private::parity_scale_codec::Encode::encode_to(&value, dest);
+			}
+		}
+
+		#[automatically_derived]
+		impl ::enum_discrim::__private::parity_scale_codec::EncodeLike for #ident {}
+	}
+	.into())
+}
+
+/// Derives an impl `Decode` block, reading the discriminant with the width of the primitive representation
+pub(crate) fn decode(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let portability = model.check_portability()?;
+	let EnumModel {
+		ident,
+		repr,
+		variants,
+		..
+	} = model;
+	let arms = variants
+		.iter()
+		.filter(|variant| variant.is_decoded())
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(#value => ::core::result::Result::Ok(Self::#name),)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#portability
+
+		#[automatically_derived]
+		impl ::enum_discrim::__private::parity_scale_codec::Decode for #ident {
+			#[inline]
+			fn decode<I>(input: &mut I) -> ::core::result::Result<Self, ::enum_discrim::__private::parity_scale_codec::Error>
+			where
+				I: ::enum_discrim::__private::parity_scale_codec::Input,
+			{
+				let value: #repr = ::enum_discrim::__private::parity_scale_codec::Decode::decode(input)?;
+				match value {
+					#(#arms)*
+					_ => ::core::result::Result::Err(::core::convert::From::from(::core::concat!(
+						"Tried to convert an invalid value into a ",
+						::core::stringify!(#ident),
+					))),
+				}
+			}
+		}
+	}
+	.into())
+}
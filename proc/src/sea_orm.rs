@@ -0,0 +1,116 @@
+//! Provides the code generation for the [`ActiveEnum`](crate::derive_active_enum) derive macro
+
+use crate::{EnumModel, PrimitiveRepresentation};
+use proc_macro::TokenStream;
+
+/// Derives impl `ActiveEnum` and `Iterable` blocks, storing the discriminant in an integer column
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	use PrimitiveRepresentation as Repr;
+
+	let model = EnumModel::parse_unit(item)?;
+	let column_ty = match model.repr {
+		Repr::u8 => quote::quote!(TinyUnsigned),
+		Repr::u16 => quote::quote!(SmallUnsigned),
+		Repr::u32 => quote::quote!(Unsigned),
+		Repr::u64 => quote::quote!(BigUnsigned),
+		Repr::i8 => quote::quote!(TinyInteger),
+		Repr::i16 => quote::quote!(SmallInteger),
+		Repr::i32 => quote::quote!(Integer),
+		Repr::i64 => quote::quote!(BigInteger),
+		repr => {
+			return Err(darling::Error::custom(format!(
+				"`{repr}` discriminants have no corresponding SeaORM column type"
+			))
+			.with_span(&model.ident));
+		}
+	};
+	let repr = model.repr_ty();
+	let reserved = (!model.options.reserved.is_empty()).then(|| {
+		let ranges = &model.options.reserved;
+		let ident = &model.ident;
+		quote::quote! {
+			#(#ranges)|* => ::core::result::Result::Err(::enum_discrim::__private::sea_orm::DbErr::Type(
+				::enum_discrim::__private::alloc::format!(
+					"reserved value for {} enum: {}",
+					::core::stringify!(#ident),
+					value,
+				),
+			)),
+		}
+	});
+	let EnumModel {
+		ident, variants, ..
+	} = model;
+	let names = variants
+		.iter()
+		.map(|variant| &variant.ident)
+		.collect::<Vec<_>>();
+	let to_arms = variants.iter().map(|variant| {
+		let name = &variant.ident;
+		let value = &variant.value;
+		quote::quote!(Self::#name => #value,)
+	});
+	let from_arms = variants
+		.iter()
+		.filter(|variant| variant.is_decoded())
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(#value => ::core::result::Result::Ok(Self::#name),)
+		});
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl ::enum_discrim::__private::sea_orm::Iterable for #ident {
+			type Iterator = ::enum_discrim::__private::alloc::vec::IntoIter<Self>;
+
+			#[inline]
+			fn iter() -> Self::Iterator {
+				::enum_discrim::__private::alloc::vec![#(Self::#names),*].into_iter()
+			}
+		}
+
+		#[automatically_derived]
+		impl ::enum_discrim::__private::sea_orm::ActiveEnum for #ident {
+			type Value = #repr;
+			type ValueVec = ::enum_discrim::__private::alloc::vec::Vec<#repr>;
+
+			#[inline]
+			fn name() -> ::enum_discrim::__private::sea_orm::sea_query::DynIden {
+				::enum_discrim::__private::sea_orm::sea_query::SeaRc::new(
+					::enum_discrim::__private::sea_orm::sea_query::Alias::new(::core::stringify!(#ident)),
+				)
+			}
+
+			#[inline]
+			fn to_value(&self) -> Self::Value {
+				match self {
+					#(#to_arms)*
+				}
+			}
+
+			#[inline]
+			fn try_from_value(value: &Self::Value) -> ::core::result::Result<Self, ::enum_discrim::__private::sea_orm::DbErr> {
+				match *value {
+					#(#from_arms)*
+					#reserved
+					_ => ::core::result::Result::Err(::enum_discrim::__private::sea_orm::DbErr::Type(
+						::enum_discrim::__private::alloc::format!(
+							"unexpected value for {} enum: {}",
+							::core::stringify!(#ident),
+							value,
+						),
+					)),
+				}
+			}
+
+			#[inline]
+			fn db_type() -> ::enum_discrim::__private::sea_orm::ColumnDef {
+				::enum_discrim::__private::sea_orm::ColumnTypeTrait::def(
+					::enum_discrim::__private::sea_orm::ColumnType::#column_ty,
+				)
+			}
+		}
+	}
+	.into())
+}
@@ -0,0 +1,627 @@
+//! Provides the code generation for the [`SerdeRepr`](crate::derive_serde_repr) derive macro
+
+use crate::{EnumModel, NamedEnum};
+use proc_macro::TokenStream;
+
+/// Derives a type with `serialize` and `deserialize` associated functions for `#[serde(with = "...")]`,
+/// representing the enum as its discriminant or as the name of its variant
+///
+/// The functions are associated to a type rather than put in a module, as a module could only refer to the enum with `super::`,
+/// which does not resolve for enums declared inside a function.
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	use crate::options::{EnumOptions, SerdeMode};
+	use darling::FromAttributes;
+	use syn::DeriveInput;
+
+	let input: DeriveInput = syn::parse(item.clone())?;
+	match EnumOptions::from_attributes(&input.attrs)?.serde {
+		SerdeMode::Value => as_value(item),
+		SerdeMode::Name => as_name(item, &input),
+		SerdeMode::Tagged => as_tagged(item),
+		SerdeMode::HumanReadable => as_name_or_repr(item),
+		SerdeMode::Key => as_key(item),
+	}
+}
+
+/// Generates the expression converting the given discriminant into a variant, accepting the same values as the `TryFrom` derive macro
+///
+/// The discriminants of the variants and the values given with `#[discrim(alias = ...)]` are converted into `Ok(variant)`.
+/// Other values are converted into the `#[discrim(default)]` variant if any, or into an error of the given type otherwise,
+/// whose `serde::de::Error` trait must be in scope as `Error`.
+fn decode(
+	model: &EnumModel,
+	discriminant: proc_macro2::TokenStream,
+	error: proc_macro2::TokenStream,
+) -> darling::Result<proc_macro2::TokenStream> {
+	let ident = &model.ident;
+	let (idents, values): (Vec<_>, Vec<_>) = model
+		.variants
+		.iter()
+		.filter(|variant| variant.is_decoded())
+		.map(|variant| (&variant.ident, &variant.value))
+		.unzip();
+	let (alias_patterns, alias_idents): (Vec<_>, Vec<_>) =
+		crate::try_from::alias_patterns(model).into_iter().unzip();
+	let fallback = match crate::try_from::default_variant(model)? {
+		Some(default) => quote::quote!(::core::result::Result::Ok(#ident::#default)),
+		None => quote::quote! {
+			::core::result::Result::Err(<#error as Error>::custom(::core::concat!(
+				"Tried to convert an invalid value into a ",
+				::core::stringify!(#ident),
+			)))
+		},
+	};
+
+	Ok(quote::quote! {
+		match #discriminant {
+			#(#values => ::core::result::Result::Ok(#ident::#idents),)*
+			#(#alias_patterns => ::core::result::Result::Ok(#ident::#alias_idents),)*
+			_ => #fallback,
+		}
+	})
+}
+
+/// Derives the type representing the enum as its discriminant
+fn as_value(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let portability = model.check_portability()?;
+	let decode = decode(&model, quote::quote!(value), quote::quote!(D::Error))?;
+	let EnumModel {
+		ident,
+		vis,
+		repr,
+		variants,
+		..
+	} = model;
+	let module = quote::format_ident!("{}_as_repr", crate::case::snake_case(&ident.to_string()));
+	let doc = format!(
+		"Serializes and deserializes [`{ident}`] as its discriminant, for use with `#[serde(with = \"{module}\")]`"
+	);
+	let serialize_arms = variants
+		.iter()
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(#ident::#name => #value,)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#portability
+
+		#[doc = #doc]
+		#[allow(non_camel_case_types)]
+		#[derive(Debug, Clone, Copy)]
+		#vis struct #module;
+
+		#[allow(dead_code)]
+		impl #module {
+			/// Serializes the enum as its discriminant
+			#[inline]
+			pub fn serialize<S>(value: &#ident, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+			where
+				S: ::enum_discrim::__private::serde::Serializer,
+			{
+				let value: #repr = match value {
+					#(#serialize_arms)*
+				};
+				::enum_discrim::__private::serde::Serialize::serialize(&value, serializer)
+			}
+
+			/// Deserializes the enum from its discriminant
+			#[inline]
+			pub fn deserialize<'de, D>(deserializer: D) -> ::core::result::Result<#ident, D::Error>
+			where
+				D: ::enum_discrim::__private::serde::Deserializer<'de>,
+			{
+				use ::enum_discrim::__private::serde::de::Error;
+
+				let value: #repr = ::enum_discrim::__private::serde::Deserialize::deserialize(deserializer)?;
+				#decode
+			}
+		}
+	}
+	.into())
+}
+
+/// Derives the type representing the enum as the name of its variant
+fn as_name(item: TokenStream, input: &syn::DeriveInput) -> darling::Result<TokenStream> {
+	let NamedEnum {
+		ident, variants, ..
+	} = NamedEnum::parse(item)?;
+	let vis = &input.vis;
+	let module = quote::format_ident!("{}_as_name", crate::case::snake_case(&ident.to_string()));
+	let doc = format!(
+		"Serializes and deserializes [`{ident}`] as the name of its variant, for use with `#[serde(with = \"{module}\")]`"
+	);
+	let names = variants
+		.iter()
+		.map(|variant| &variant.name)
+		.collect::<Vec<_>>();
+	let idents = variants
+		.iter()
+		.map(|variant| &variant.ident)
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#[doc = #doc]
+		#[allow(non_camel_case_types)]
+		#[derive(Debug, Clone, Copy)]
+		#vis struct #module;
+
+		#[allow(dead_code)]
+		impl #module {
+			/// Serializes the enum as the name of its variant
+			#[inline]
+			pub fn serialize<S>(value: &#ident, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+			where
+				S: ::enum_discrim::__private::serde::Serializer,
+			{
+				serializer.serialize_str(match value {
+					#(#ident::#idents => #names,)*
+				})
+			}
+
+			/// Deserializes the enum from the name of its variant
+			#[inline]
+			pub fn deserialize<'de, D>(deserializer: D) -> ::core::result::Result<#ident, D::Error>
+			where
+				D: ::enum_discrim::__private::serde::Deserializer<'de>,
+			{
+				use ::enum_discrim::__private::serde::de::{Error, Visitor};
+
+				/// Names of all the variants
+				const VARIANTS: &[&str] = &[#(#names),*];
+
+				/// Visitor expecting the name of a variant
+				struct NameVisitor;
+				impl<'de> Visitor<'de> for NameVisitor {
+					type Value = #ident;
+
+					fn expecting(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+						f.write_str(::core::concat!("a variant name of ", ::core::stringify!(#ident)))
+					}
+
+					fn visit_str<E>(self, value: &str) -> ::core::result::Result<Self::Value, E>
+					where
+						E: Error,
+					{
+						match value {
+							#(#names => ::core::result::Result::Ok(#ident::#idents),)*
+							_ => ::core::result::Result::Err(E::unknown_variant(value, VARIANTS)),
+						}
+					}
+				}
+
+				deserializer.deserialize_str(NameVisitor)
+			}
+		}
+	}
+	.into())
+}
+
+/// Derives the type representing the enum as a struct of its discriminant and the fields of its variant
+///
+/// The fields are represented as nothing for unit variants, as the only field,
+/// or as a tuple of all fields in declaration order.
+fn as_tagged(item: TokenStream) -> darling::Result<TokenStream> {
+	use syn::Fields;
+
+	let model = EnumModel::parse(item)?;
+	let portability = model.check_portability()?;
+	let EnumModel {
+		ident,
+		vis,
+		generics,
+		repr,
+		variants,
+		..
+	} = model;
+	if !generics.params.is_empty() {
+		return Err(darling::Error::custom(
+			"`#[discrim(serde = \"tagged\")]` is not supported on generic enums",
+		)
+		.with_span(&generics.params));
+	}
+	let module = quote::format_ident!("{}_as_tagged", crate::case::snake_case(&ident.to_string()));
+	let doc = format!(
+		"Serializes and deserializes [`{ident}`] as its discriminant and the fields of its variant, for use with `#[serde(with = \"{module}\")]`"
+	);
+
+	let (serialize_arms, deserialize_arms): (Vec<_>, Vec<_>) = variants
+		.iter()
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			let bindings = variant
+				.fields
+				.iter()
+				.enumerate()
+				.map(|(index, field)| {
+					field
+						.ident
+						.clone()
+						.unwrap_or_else(|| quote::format_ident!("_{}", index))
+				})
+				.collect::<Vec<_>>();
+			let types = variant
+				.fields
+				.iter()
+				.map(|field| &field.ty)
+				.collect::<Vec<_>>();
+			let pattern = match &variant.fields {
+				Fields::Named(_) => quote::quote!(#ident::#name { #(#bindings),* }),
+				Fields::Unnamed(_) => quote::quote!(#ident::#name(#(#bindings),*)),
+				Fields::Unit => quote::quote!(#ident::#name),
+			};
+			let (content, content_ty, construct) = match bindings.as_slice() {
+				[] => (quote::quote!(&()), quote::quote!(()), pattern.clone()),
+				[binding] => (
+					quote::quote!(#binding),
+					quote::quote!(#(#types)*),
+					quote::quote!({
+						let #binding = content;
+						#pattern
+					}),
+				),
+				_ => {
+					let indices = (0..bindings.len()).map(syn::Index::from);
+					(
+						quote::quote!(&(#(#bindings,)*)),
+						quote::quote!((#(#types,)*)),
+						quote::quote!({
+							#(let #bindings = content.#indices;)*
+							#pattern
+						}),
+					)
+				}
+			};
+
+			(
+				quote::quote! {
+					#pattern => {
+						let mut state = serializer.serialize_struct(::core::stringify!(#ident), 2)?;
+						state.serialize_field(#module::TAG, &#value)?;
+						state.serialize_field(#module::CONTENT, #content)?;
+						state.end()
+					}
+				},
+				variant.is_decoded().then(|| {
+					quote::quote! {
+						#value => {
+							let content: #content_ty = content.next()?;
+							::core::result::Result::Ok(#construct)
+						}
+					}
+				}),
+			)
+		})
+		.unzip();
+
+	Ok(quote::quote! {
+		#portability
+
+		#[doc = #doc]
+		#[allow(non_camel_case_types)]
+		#[derive(Debug, Clone, Copy)]
+		#vis struct #module;
+
+		#[allow(dead_code)]
+		impl #module {
+			/// Name of the field containing the discriminant
+			const TAG: &'static str = "tag";
+			/// Name of the field containing the fields of the variant
+			const CONTENT: &'static str = "content";
+			/// Names of all the fields
+			const FIELDS: &'static [&'static str] = &[Self::TAG, Self::CONTENT];
+
+			/// Serializes the enum as a struct of its discriminant and the fields of its variant
+			#[inline]
+			pub fn serialize<S>(value: &#ident, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+			where
+				S: ::enum_discrim::__private::serde::Serializer,
+			{
+				use ::enum_discrim::__private::serde::ser::SerializeStruct;
+
+				match value {
+					#(#serialize_arms)*
+				}
+			}
+
+			/// Deserializes the enum from a struct of its discriminant and the fields of its variant
+			///
+			/// When represented as a map, the discriminant *must* come before the fields.
+			#[inline]
+			pub fn deserialize<'de, D>(deserializer: D) -> ::core::result::Result<#ident, D::Error>
+			where
+				D: ::enum_discrim::__private::serde::Deserializer<'de>,
+			{
+				use ::enum_discrim::__private::serde::{
+					de::{Error, MapAccess, SeqAccess, Visitor},
+					Deserialize,
+				};
+
+				/// Source of the fields of the variant, once the discriminant has been read
+				trait Content<'de> {
+					/// Error returned by the underlying access
+					type Error: Error;
+
+					/// Reads the fields of the variant
+					fn next<T>(self) -> ::core::result::Result<T, Self::Error>
+					where
+						T: Deserialize<'de>;
+				}
+				/// Fields read from the second element of a sequence
+				struct SeqContent<A>(A);
+				impl<'de, A> Content<'de> for SeqContent<A>
+				where
+					A: SeqAccess<'de>,
+				{
+					type Error = A::Error;
+
+					#[inline]
+					fn next<T>(mut self) -> ::core::result::Result<T, Self::Error>
+					where
+						T: Deserialize<'de>,
+					{
+						self.0
+							.next_element()?
+							.ok_or_else(|| A::Error::invalid_length(1, &"a discriminant and its fields"))
+					}
+				}
+				/// Fields read from the `content` entry of a map
+				struct MapContent<A>(A);
+				impl<'de, A> Content<'de> for MapContent<A>
+				where
+					A: MapAccess<'de>,
+				{
+					type Error = A::Error;
+
+					#[inline]
+					fn next<T>(mut self) -> ::core::result::Result<T, Self::Error>
+					where
+						T: Deserialize<'de>,
+					{
+						match self.0.next_key::<::enum_discrim::__private::alloc::string::String>()? {
+							::core::option::Option::Some(key) if key == #module::CONTENT => self.0.next_value(),
+							_ => ::core::result::Result::Err(A::Error::missing_field(#module::CONTENT)),
+						}
+					}
+				}
+
+				/// Builds the variant with the given discriminant from the given fields
+				#[inline]
+				fn build<'de, C>(tag: #repr, content: C) -> ::core::result::Result<#ident, C::Error>
+				where
+					C: Content<'de>,
+				{
+					match tag {
+						#(#deserialize_arms)*
+						_ => ::core::result::Result::Err(C::Error::custom(::core::concat!(
+							"Tried to convert an invalid value into a ",
+							::core::stringify!(#ident),
+						))),
+					}
+				}
+
+				/// Visitor expecting a discriminant followed by the fields of the variant
+				struct TaggedVisitor;
+				impl<'de> Visitor<'de> for TaggedVisitor {
+					type Value = #ident;
+
+					fn expecting(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+						f.write_str(::core::concat!("a tagged ", ::core::stringify!(#ident)))
+					}
+
+					fn visit_seq<A>(self, mut seq: A) -> ::core::result::Result<Self::Value, A::Error>
+					where
+						A: SeqAccess<'de>,
+					{
+						let tag = seq
+							.next_element()?
+							.ok_or_else(|| A::Error::invalid_length(0, &self))?;
+						build(tag, SeqContent(seq))
+					}
+
+					fn visit_map<A>(self, mut map: A) -> ::core::result::Result<Self::Value, A::Error>
+					where
+						A: MapAccess<'de>,
+					{
+						match map.next_key::<::enum_discrim::__private::alloc::string::String>()? {
+							::core::option::Option::Some(key) if key == #module::TAG => {}
+							_ => return ::core::result::Result::Err(A::Error::missing_field(#module::TAG)),
+						}
+						let tag = map.next_value()?;
+						build(tag, MapContent(map))
+					}
+				}
+
+				deserializer.deserialize_struct(::core::stringify!(#ident), Self::FIELDS, TaggedVisitor)
+			}
+		}
+	}
+	.into())
+}
+
+/// Derives `Serialize` and `Deserialize` impl blocks representing the enum as its discriminant formatted as a string
+///
+/// Unlike the other representations, these are trait impls rather than associated functions,
+/// as map keys cannot be given `#[serde(with = "...")]`.
+fn as_key(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let portability = model.check_portability()?;
+	let decode = decode(&model, quote::quote!(discriminant), quote::quote!(E))?;
+	let EnumModel {
+		ident,
+		repr,
+		variants,
+		..
+	} = model;
+	let idents = variants
+		.iter()
+		.map(|variant| &variant.ident)
+		.collect::<Vec<_>>();
+	let values = variants
+		.iter()
+		.map(|variant| &variant.value)
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#portability
+
+		#[automatically_derived]
+		impl ::enum_discrim::__private::serde::Serialize for #ident {
+			#[inline]
+			fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+			where
+				S: ::enum_discrim::__private::serde::Serializer,
+			{
+				let value: #repr = match self {
+					#(#ident::#idents => #values,)*
+				};
+				serializer.collect_str(&value)
+			}
+		}
+
+		#[automatically_derived]
+		impl<'de> ::enum_discrim::__private::serde::Deserialize<'de> for #ident {
+			#[inline]
+			fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+			where
+				D: ::enum_discrim::__private::serde::Deserializer<'de>,
+			{
+				use ::enum_discrim::__private::serde::de::{Error, Unexpected, Visitor};
+
+				/// Visitor expecting a discriminant formatted as a string
+				struct KeyVisitor;
+				impl<'de> Visitor<'de> for KeyVisitor {
+					type Value = #ident;
+
+					fn expecting(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+						f.write_str(::core::concat!("a stringified discriminant of ", ::core::stringify!(#ident)))
+					}
+
+					fn visit_str<E>(self, value: &str) -> ::core::result::Result<Self::Value, E>
+					where
+						E: Error,
+					{
+						let discriminant: #repr = value
+							.parse()
+							.map_err(|_| E::invalid_value(Unexpected::Str(value), &self))?;
+						#decode
+					}
+				}
+
+				deserializer.deserialize_str(KeyVisitor)
+			}
+		}
+	}
+	.into())
+}
+
+/// Derives the type representing the enum as the name of its variant in human-readable formats,
+/// and as its discriminant in other formats
+fn as_name_or_repr(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let portability = model.check_portability()?;
+	let decode = decode(&model, quote::quote!(value), quote::quote!(D::Error))?;
+	let EnumModel {
+		ident,
+		vis,
+		repr,
+		variants,
+		..
+	} = model;
+	let module = quote::format_ident!(
+		"{}_as_name_or_repr",
+		crate::case::snake_case(&ident.to_string())
+	);
+	let doc = format!(
+		"Serializes and deserializes [`{ident}`] as the name of its variant in human-readable formats, and as its discriminant otherwise, for use with `#[serde(with = \"{module}\")]`"
+	);
+	let names = variants
+		.iter()
+		.map(|variant| &variant.name)
+		.collect::<Vec<_>>();
+	let idents = variants
+		.iter()
+		.map(|variant| &variant.ident)
+		.collect::<Vec<_>>();
+	let values = variants
+		.iter()
+		.map(|variant| &variant.value)
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		#portability
+
+		#[doc = #doc]
+		#[allow(non_camel_case_types)]
+		#[derive(Debug, Clone, Copy)]
+		#vis struct #module;
+
+		#[allow(dead_code)]
+		impl #module {
+			/// Serializes the enum as the name of its variant in human-readable formats, and as its discriminant otherwise
+			#[inline]
+			pub fn serialize<S>(value: &#ident, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+			where
+				S: ::enum_discrim::__private::serde::Serializer,
+			{
+				if serializer.is_human_readable() {
+					serializer.serialize_str(match value {
+						#(#ident::#idents => #names,)*
+					})
+				} else {
+					let value: #repr = match value {
+						#(#ident::#idents => #values,)*
+					};
+					::enum_discrim::__private::serde::Serialize::serialize(&value, serializer)
+				}
+			}
+
+			/// Deserializes the enum from the name of its variant in human-readable formats, and from its discriminant otherwise
+			#[inline]
+			pub fn deserialize<'de, D>(deserializer: D) -> ::core::result::Result<#ident, D::Error>
+			where
+				D: ::enum_discrim::__private::serde::Deserializer<'de>,
+			{
+				use ::enum_discrim::__private::serde::{
+					de::{Error, Visitor},
+					Deserialize,
+				};
+
+				/// Names of all the variants
+				const VARIANTS: &[&str] = &[#(#names),*];
+
+				/// Visitor expecting the name of a variant
+				struct NameVisitor;
+				impl<'de> Visitor<'de> for NameVisitor {
+					type Value = #ident;
+
+					fn expecting(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+						f.write_str(::core::concat!("a variant name of ", ::core::stringify!(#ident)))
+					}
+
+					fn visit_str<E>(self, value: &str) -> ::core::result::Result<Self::Value, E>
+					where
+						E: Error,
+					{
+						match value {
+							#(#names => ::core::result::Result::Ok(#ident::#idents),)*
+							_ => ::core::result::Result::Err(E::unknown_variant(value, VARIANTS)),
+						}
+					}
+				}
+
+				if deserializer.is_human_readable() {
+					deserializer.deserialize_str(NameVisitor)
+				} else {
+					let value: #repr = Deserialize::deserialize(deserializer)?;
+					#decode
+				}
+			}
+		}
+	}
+	.into())
+}
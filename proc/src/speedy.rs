@@ -0,0 +1,124 @@
+//! Provides the code generation for the [`Readable`](crate::derive_speedy_readable) and [`Writable`](crate::derive_speedy_writable) derive macros
+
+use crate::{options::Endianness, EnumModel};
+use proc_macro::TokenStream;
+
+/// Derives an impl `Writable` block, writing the discriminant with the width of the primitive representation
+pub(crate) fn writable(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let portability = model.check_portability()?;
+	let EnumModel {
+		ident,
+		options,
+		repr,
+		variants,
+		..
+	} = model;
+	let arms = variants
+		.iter()
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(Self::#name => #value,)
+		})
+		.collect::<Vec<_>>();
+	let write = match options.endianness {
+		None => quote::quote!(writer.write_value(&value)),
+		Some(Endianness::Little) => quote::quote!(writer.write_bytes(&value.to_le_bytes())),
+		Some(Endianness::Big) => quote::quote!(writer.write_bytes(&value.to_be_bytes())),
+	};
+
+	Ok(quote::quote! {
+		#portability
+
+		#[automatically_derived]
+		impl<C> ::enum_discrim::__private::speedy::Writable<C> for #ident
+		where
+			C: ::enum_discrim::__private::speedy::Context,
+		{
+			#[inline]
+			fn write_to<W>(&self, writer: &mut W) -> ::core::result::Result<(), C::Error>
+			where
+				W: ?Sized + ::enum_discrim::__private::speedy::Writer<C>,
+			{
+				let value: #repr = match self {
+					#(#arms)*
+				};
+				#write
+			}
+
+			#[inline]
+			fn bytes_needed(&self) -> ::core::result::Result<usize, C::Error> {
+				::core::result::Result::Ok(::core::mem::size_of::<#repr>())
+			}
+		}
+	}
+	.into())
+}
+
+/// Derives an impl `Readable` block, reading the discriminant with the width of the primitive representation
+pub(crate) fn readable(item: TokenStream) -> darling::Result<TokenStream> {
+	let model = EnumModel::parse_unit(item)?;
+	let portability = model.check_portability()?;
+	let EnumModel {
+		ident,
+		options,
+		repr,
+		variants,
+		..
+	} = model;
+	let arms = variants
+		.iter()
+		.filter(|variant| variant.is_decoded())
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(#value => ::core::result::Result::Ok(Self::#name),)
+		})
+		.collect::<Vec<_>>();
+	let read = |from_bytes| {
+		quote::quote! {{
+			let mut bytes = [0; ::core::mem::size_of::<#repr>()];
+			reader.read_bytes(&mut bytes)?;
+			#repr::#from_bytes(bytes)
+		}}
+	};
+	let read = match options.endianness {
+		None => quote::quote!(reader.read_value()?),
+		Some(Endianness::Little) => read(quote::quote!(from_le_bytes)),
+		Some(Endianness::Big) => read(quote::quote!(from_be_bytes)),
+	};
+
+	Ok(quote::quote! {
+		#portability
+
+		#[automatically_derived]
+		impl<'a, C> ::enum_discrim::__private::speedy::Readable<'a, C> for #ident
+		where
+			C: ::enum_discrim::__private::speedy::Context,
+		{
+			#[inline]
+			fn read_from<R>(reader: &mut R) -> ::core::result::Result<Self, C::Error>
+			where
+				R: ::enum_discrim::__private::speedy::Reader<'a, C>,
+			{
+				let value: #repr = #read;
+				match value {
+					#(#arms)*
+					_ => ::core::result::Result::Err(::core::convert::From::from(
+						::enum_discrim::__private::speedy::Error::custom(::core::concat!(
+							"Tried to convert an invalid value into a ",
+							::core::stringify!(#ident),
+						)),
+					)),
+				}
+			}
+
+			#[inline]
+			fn minimum_bytes_needed() -> usize {
+				::core::mem::size_of::<#repr>()
+			}
+		}
+	}
+	.into())
+}
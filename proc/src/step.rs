@@ -0,0 +1,82 @@
+//! Provides the code generation for the [`Step`](crate::derive_step) derive macro
+
+use crate::EnumModel;
+use proc_macro::TokenStream;
+
+/// Derives an impl `Step` block, stepping through the variants in declaration order
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let EnumModel {
+		ident, variants, ..
+	} = EnumModel::parse_unit(item)?;
+
+	let mut errors = darling::Error::accumulator();
+	let mut previous: Option<i128> = None;
+	for variant in &variants {
+		let value = variant.number.parse::<i128>().ok();
+		if let (Some(previous), Some(value)) = (previous, value) {
+			if previous.checked_add(1) != Some(value) {
+				errors.push(
+					darling::Error::custom(
+						"`Step` requires contiguous discriminants, increasing in declaration order",
+					)
+					.with_span(&variant.ident),
+				);
+			}
+		}
+		previous = value;
+	}
+	errors.finish()?;
+
+	let index_arms = variants.iter().enumerate().map(|(index, variant)| {
+		let name = &variant.ident;
+		quote::quote!(Self::#name => #index,)
+	});
+	let variant_arms = variants.iter().enumerate().map(|(index, variant)| {
+		let name = &variant.ident;
+		quote::quote!(#index => ::core::option::Option::Some(Self::#name),)
+	});
+
+	Ok(quote::quote! {
+		#[doc(hidden)]
+		impl #ident {
+			/// Returns the position of the given variant in the enum declaration
+			#[inline]
+			const fn __step_index(&self) -> usize {
+				match *self {
+					#(#index_arms)*
+				}
+			}
+
+			/// Returns the variant declared at the given position, if any
+			#[inline]
+			const fn __step_variant(index: usize) -> ::core::option::Option<Self> {
+				match index {
+					#(#variant_arms)*
+					_ => ::core::option::Option::None,
+				}
+			}
+		}
+
+		#[automatically_derived]
+		impl ::core::iter::Step for #ident {
+			#[inline]
+			fn steps_between(start: &Self, end: &Self) -> (usize, ::core::option::Option<usize>) {
+				match end.__step_index().checked_sub(start.__step_index()) {
+					::core::option::Option::Some(steps) => (steps, ::core::option::Option::Some(steps)),
+					::core::option::Option::None => (0, ::core::option::Option::None),
+				}
+			}
+
+			#[inline]
+			fn forward_checked(start: Self, count: usize) -> ::core::option::Option<Self> {
+				Self::__step_variant(start.__step_index().checked_add(count)?)
+			}
+
+			#[inline]
+			fn backward_checked(start: Self, count: usize) -> ::core::option::Option<Self> {
+				Self::__step_variant(start.__step_index().checked_sub(count)?)
+			}
+		}
+	}
+	.into())
+}
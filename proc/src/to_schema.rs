@@ -0,0 +1,74 @@
+//! Provides the code generation for the [`ToSchema`](crate::derive_to_schema) derive macro
+
+use crate::{EnumModel, PrimitiveRepresentation};
+use proc_macro::TokenStream;
+
+/// Derives an impl block for the `ToSchema` trait of `utoipa`, describing the enum as an integer
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	use proc_macro2::Literal;
+
+	let EnumModel {
+		ident,
+		repr,
+		variants,
+		..
+	} = EnumModel::parse_unit(item)?;
+
+	let mut accumulator = darling::Error::accumulator();
+	let values = variants
+		.iter()
+		.filter_map(|variant| {
+			accumulator.handle(
+				variant
+					.number
+					.parse::<i64>()
+					.map(Literal::i64_suffixed)
+					.map_err(|_| {
+						darling::Error::custom("Discriminant does not fit in an OpenAPI integer")
+							.with_span(&variant.ident)
+					}),
+			)
+		})
+		.collect::<Vec<_>>();
+	accumulator.finish()?;
+	let description = variants
+		.iter()
+		.map(|variant| format!("- `{}`: {}", variant.number, variant.name))
+		.collect::<Vec<_>>()
+		.join("\n");
+	let format = match repr {
+		PrimitiveRepresentation::u8
+		| PrimitiveRepresentation::u16
+		| PrimitiveRepresentation::i8
+		| PrimitiveRepresentation::i16
+		| PrimitiveRepresentation::i32 => quote::quote!(Int32),
+		_ => quote::quote!(Int64),
+	};
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl<'__s> ::enum_discrim::__private::utoipa::ToSchema<'__s> for #ident {
+			fn schema() -> (
+				&'__s str,
+				::enum_discrim::__private::utoipa::openapi::RefOr<
+					::enum_discrim::__private::utoipa::openapi::schema::Schema,
+				>,
+			) {
+				use ::enum_discrim::__private::utoipa::openapi::{
+					ObjectBuilder, SchemaFormat, KnownFormat, SchemaType,
+				};
+
+				(
+					::core::stringify!(#ident),
+					ObjectBuilder::new()
+						.schema_type(SchemaType::Integer)
+						.format(::core::option::Option::Some(SchemaFormat::KnownFormat(KnownFormat::#format)))
+						.enum_values(::core::option::Option::Some([#(#values),*]))
+						.description(::core::option::Option::Some(#description))
+						.into(),
+				)
+			}
+		}
+	}
+	.into())
+}
@@ -1,63 +1,562 @@
-//! Provides [the parsing struct](TryFromInput) for the [`TryFrom`](crate::derive_try_from) derive macro
+//! Provides the code generation for the [`TryFrom`](crate::derive_try_from) derive macro
 
-use crate::PrimitiveRepresentation;
-use darling::{ast::Data, util::SpannedValue, FromAttributes, FromDeriveInput};
+use crate::{options::Lookup, EnumModel, PrimitiveRepresentation};
 use proc_macro::TokenStream;
-use syn::{Attribute, Ident, Variant};
-
-/// Parsing struct for the [`TryFrom`](crate::derive_try_from) derive macro
-#[derive(Debug, FromDeriveInput)]
-#[darling(supports(enum_unit), forward_attrs(repr))]
-struct TryFromInput {
-	/// Enum identifier
-	ident: Ident,
-	/// Variants contained in the enum
-	data: Data<SpannedValue<Variant>, ()>,
-	/// Forwarded attributes
-	attrs: Vec<Attribute>,
+use syn::Ident;
+
+/// Maximum number of entries of the table generated with `#[discrim(lookup = "table")]`
+const MAX_TABLE_LEN: i128 = 1 << 16;
+
+/// Generates the expression checking whether `value` is a discriminant, for the strategies other than [`Lookup::Match`]
+/// and for `#[discrim(const_time)]`
+///
+/// These strategies convert valid values with a transmutation, so they require a `#[repr(inttype)]` attribute.
+fn is_valid(model: &EnumModel) -> darling::Result<proc_macro2::TokenStream> {
+	let EnumModel {
+		ident,
+		options,
+		variants,
+		..
+	} = model;
+
+	if model.repr_c || options.logical_repr.is_some() {
+		return Err(darling::Error::custom(
+			"this lookup strategy requires a `#[repr(inttype)]` attribute",
+		)
+		.with_span(ident));
+	}
+	if options.const_time {
+		if options.lookup != Lookup::Match {
+			return Err(darling::Error::custom(
+				"`#[discrim(const_time)]` cannot be combined with `#[discrim(lookup = \"...\")]`",
+			)
+			.with_span(ident));
+		}
+
+		let values = variants
+			.iter()
+			.filter(|variant| !variant.options.skip)
+			.map(|variant| &variant.value);
+		// Each comparison yields 1 if the value is equal to the discriminant, and 0 otherwise, without branching
+		return Ok(quote::quote! {{
+			let mut valid: u128 = 0;
+			#({
+				let diff = (value ^ #values) as u128;
+				valid |= ((diff | diff.wrapping_neg()) >> 127) ^ 1;
+			})*
+			::core::hint::black_box(valid) == 1
+		}});
+	}
+	model.require_numbers("this lookup strategy")?;
+	let mut errors = darling::Error::accumulator();
+	let mut values = variants
+		.iter()
+		.filter(|variant| !variant.options.skip)
+		.filter_map(|variant| match variant.number.parse::<i128>() {
+			Ok(number) => Some((number, &variant.value)),
+			Err(_) => {
+				errors.push(
+					darling::Error::custom("discriminant is too large for this lookup strategy")
+						.with_span(&variant.ident),
+				);
+				None
+			}
+		})
+		.collect::<Vec<_>>();
+	errors.finish()?;
+	values.sort_unstable_by_key(|(number, _)| *number);
+	let (Some((min, min_value)), Some((max, max_value))) = (values.first(), values.last()) else {
+		return Err(darling::Error::custom(
+			"`#[discrim(lookup = \"...\")]` strategies other than `match` require at least one variant",
+		)
+		.with_span(ident));
+	};
+
+	let repr = model.repr_ty();
+	match options.lookup {
+		Lookup::Match => {
+			unreachable!("`#[discrim(lookup = \"match\")]` does not check the validity beforehand")
+		}
+		Lookup::Range => {
+			if max - min + 1 != values.len() as i128 {
+				return Err(darling::Error::custom(
+					"`#[discrim(lookup = \"range\")]` requires contiguous discriminants",
+				)
+				.with_span(ident));
+			}
+
+			Ok(quote::quote!((#min_value..=#max_value).contains(&value)))
+		}
+		Lookup::Table => {
+			let len = max - min + 1;
+			if len > MAX_TABLE_LEN {
+				return Err(darling::Error::custom(format!(
+					"`#[discrim(lookup = \"table\")]` requires discriminants spanning at most {MAX_TABLE_LEN} values"
+				))
+				.with_span(ident));
+			}
+			let mut table = vec![false; len as usize];
+			for (number, _) in &values {
+				table[(number - min) as usize] = true;
+			}
+			let len = len as usize;
+
+			Ok(quote::quote! {{
+				static TABLE: [bool; #len] = [#(#table),*];
+				::enum_discrim::tables::dense_contains(&TABLE, #min_value, value)
+			}})
+		}
+		Lookup::BinarySearch => {
+			let len = values.len();
+			let values = values.iter().map(|(_, value)| value);
+
+			Ok(quote::quote! {{
+				static VALUES: [#repr; #len] = [#(#values),*];
+				::enum_discrim::tables::sorted_contains(&VALUES, value)
+			}})
+		}
+	}
+}
+
+/// Returns the variant marked with `#[discrim(default)]`, if any
+pub(crate) fn default_variant(model: &EnumModel) -> darling::Result<Option<&Ident>> {
+	let mut defaults = model
+		.variants
+		.iter()
+		.filter(|variant| variant.options.default);
+	let default = defaults.next();
+
+	let mut errors = darling::Error::accumulator();
+	for variant in defaults {
+		errors.push(
+			darling::Error::custom("another variant is already the default one")
+				.with_span(&variant.ident),
+		);
+	}
+	if let Some(variant) = default.filter(|variant| variant.options.skip) {
+		errors.push(
+			darling::Error::custom("the default variant cannot be skipped")
+				.with_span(&variant.ident),
+		);
+	}
+	errors.finish_with(default.map(|variant| &variant.ident))
+}
+
+/// Returns the pattern of the values given with `#[discrim(alias = ...)]` for each variant having some, along with the variant
+pub(crate) fn alias_patterns(model: &EnumModel) -> Vec<(proc_macro2::TokenStream, &Ident)> {
+	model
+		.variants
+		.iter()
+		.filter(|variant| !variant.options.aliases.is_empty())
+		.map(|variant| {
+			let aliases = variant
+				.options
+				.aliases
+				.iter()
+				.map(|alias| crate::options::int_literal(alias.0));
+			(quote::quote!(#(#aliases)|*), &variant.ident)
+		})
+		.collect()
+}
+
+/// Generates the expression of the values accepted by `TryFrom`, attached to its errors
+///
+/// The values are typed as the primitive representation before being widened,
+/// so that opaque discriminants, which are only known by the compiler, are widened the same way as the others.
+/// They are widened into the 128 bits integer type of the same signedness, as `RawValue` does.
+fn expected(model: &EnumModel) -> proc_macro2::TokenStream {
+	let repr = model.repr_ty();
+	let (variant, wide) = if model.repr.is_signed() {
+		(quote::quote!(Signed), quote::quote!(i128))
+	} else {
+		(quote::quote!(Unsigned), quote::quote!(u128))
+	};
+	let discriminants = model
+		.variants
+		.iter()
+		.filter(|variant| variant.is_decoded())
+		.map(|variant| &variant.value);
+	let aliases = model
+		.variants
+		.iter()
+		.flat_map(|variant| &variant.options.aliases)
+		.map(|alias| crate::options::int_literal(alias.0));
+
+	quote::quote! {{
+		#[allow(clippy::cast_lossless, clippy::unnecessary_cast)]
+		const EXPECTED: &[::enum_discrim::RawValue] = &[
+			#(::enum_discrim::RawValue::#variant((#discriminants) as #repr as #wide),)*
+			#(::enum_discrim::RawValue::#variant((#aliases) as #repr as #wide)),*
+		];
+		EXPECTED
+	}}
 }
 
-/// Derives a [`TryFrom<repr>`] impl block
+/// Derives a [`TryFrom<repr>`] impl block, or a [`From<repr>`] impl block if a variant is the default one
 pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
-	use syn::DeriveInput;
-
-	let item: DeriveInput = syn::parse(item)?;
-	let TryFromInput { ident, data, attrs } = TryFromInput::from_derive_input(&item)?;
-	let Data::Enum(data) = data else {
-		unreachable!()
-	};
-
-	let repr = PrimitiveRepresentation::from_attributes(&attrs)?;
-	/// Generates a match arm for each given type
-	macro_rules! arms_with_ty {
-		($( $ty:ident ),* $(,)?) => {
-			match repr {$(
-				PrimitiveRepresentation::$ty => crate::scan_variants::<$ty>(&data)?
-					.into_iter()
-					.map(|(variant, value)| {
-						let span = variant.span();
-						let name = &variant.ident;
-						quote::quote_spanned!(span=> #value => Ok(Self::#name),)
-					})
-					.collect::<Vec<_>>(),
-			)*}
-		};
-	}
-	let arms = arms_with_ty![u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize];
+	let model = EnumModel::parse_unit_opaque(item)?;
+	let EnumModel {
+		ident,
+		vis,
+		options,
+		variants,
+		..
+	} = &model;
+
+	let repr = model.repr_ty();
+	let default = default_variant(&model)?;
+	// With a default variant, `From<repr>` is generated instead, returning the default variant instead of errors
+	let accept = |variant: proc_macro2::TokenStream| match default {
+		Some(_) => variant,
+		None => quote::quote!(::core::result::Result::Ok(#variant)),
+	};
+	if options.map_err.is_some() && options.error.is_none() {
+		return Err(darling::Error::custom(
+			"`#[discrim(map_err = \"...\")]` requires `#[discrim(error = \"...\")]`",
+		)
+		.with_span(ident));
+	}
+	if options.try_from_f64 && options.error.is_some() {
+		return Err(darling::Error::custom(
+			"`#[discrim(try_from_f64)]` cannot be combined with `#[discrim(error = \"...\")]`, as its error wraps a `TryFromError`",
+		)
+		.with_span(ident));
+	}
+	let base_error = if options.typed_error {
+		quote::quote!(::enum_discrim::TryFromError<Self>)
+	} else {
+		quote::quote!(::enum_discrim::TryFromError)
+	};
+	let error_ty = options
+		.error
+		.as_ref()
+		.map_or_else(|| base_error.clone(), |error| quote::quote!(#error));
+	let expected = expected(&model);
+	// A custom error is converted from `TryFromError` with the given function, or with `From` otherwise
+	let error = |constructor: &str, with_value: bool| {
+		let constructor = quote::format_ident!("{}", constructor);
+		let with_value = with_value.then(|| quote::quote!(.with_value(value)));
+		// Written in angle brackets, so that the default type parameter applies in expressions as well
+		let error = quote::quote!(
+			<#base_error>::#constructor(::core::stringify!(#ident))
+				#with_value
+				.with_expected(#expected)
+		);
+		match (&options.error, &options.map_err) {
+			(_, Some(map_err)) => quote::quote!(#map_err(#error)),
+			(Some(_), None) => {
+				quote::quote!(<#error_ty as ::core::convert::From<#base_error>>::from(#error))
+			}
+			(None, None) => error,
+		}
+	};
+	let out_of_range = error("new_out_of_range", false);
+	let reject = match default {
+		Some(default) => quote::quote!(Self::#default),
+		None => {
+			let error = error("new", true);
+			quote::quote!(::core::result::Result::Err(#error))
+		}
+	};
+	let arms = variants
+		.iter()
+		.filter(|variant| variant.is_decoded())
+		.map(|variant| {
+			let span = variant.span;
+			let name = &variant.ident;
+			let value = &variant.value;
+			let variant = accept(quote::quote!(Self::#name));
+			quote::quote_spanned!(span=> #value => #variant,)
+		});
+	let (alias_patterns, alias_variants): (Vec<_>, Vec<_>) = alias_patterns(&model)
+		.into_iter()
+		.map(|(pattern, name)| (pattern, accept(quote::quote!(Self::#name))))
+		.unzip();
+	let skipped_values = model.skipped_values();
+	let skipped = (default.is_none() && !skipped_values.is_empty()).then(|| {
+		(
+			quote::quote!(#(#skipped_values)|*),
+			error("new_skipped", true),
+		)
+	});
+	let reserved = (default.is_none() && !options.reserved.is_empty()).then(|| {
+		let ranges = &options.reserved;
+		(quote::quote!(#(#ranges)|*), error("new_reserved", true))
+	});
+	let (rejected_patterns, rejected_errors): (Vec<_>, Vec<_>) =
+		skipped.into_iter().chain(reserved).unzip();
+	let on_unknown = options
+		.on_unknown
+		.as_ref()
+		.map(|on_unknown| quote::quote!(#on_unknown(value);));
+	let body = if options.lookup == Lookup::Match && !options.const_time {
+		quote::quote! {
+			match value {
+				#(#arms)*
+				#(#alias_patterns => #alias_variants,)*
+				#(#rejected_patterns => ::core::result::Result::Err(#rejected_errors),)*
+				_ => {
+					#on_unknown
+					#reject
+				}
+			}
+		}
+	} else {
+		let is_valid = is_valid(&model)?;
+		let aliases = (!alias_patterns.is_empty()).then(|| {
+			quote::quote! {
+				match value {
+					#(#alias_patterns => return #alias_variants,)*
+					_ => {}
+				}
+			}
+		});
+		let transmuted =
+			accept(quote::quote!(unsafe { ::core::mem::transmute::<#repr, Self>(value) }));
+
+		quote::quote! {
+			#[allow(
+				clippy::cast_possible_truncation,
+				clippy::cast_sign_loss,
+				clippy::cast_lossless
+			)]
+			let is_valid: bool = #is_valid;
+			// With `#[discrim(const_time)]`, only the lookup above is constant-time, this branch reveals its outcome
+			if is_valid {
+				// SAFETY: The value is the discriminant of a variant, and our macro resolves to `compile_error!` if `#[repr(inttype)]` is missing
+				return #transmuted;
+			}
+			#aliases
+			#(
+				if ::core::matches!(value, #rejected_patterns) {
+					return ::core::result::Result::Err(#rejected_errors);
+				}
+			)*
+			#on_unknown
+			#reject
+		}
+	};
+	let convert = |value: proc_macro2::TokenStream| match default {
+		Some(_) => {
+			quote::quote!(::core::result::Result::Ok(<Self as ::core::convert::From<#repr>>::from(#value)))
+		}
+		None => quote::quote!(<Self as ::core::convert::TryFrom<#repr>>::try_from(#value)),
+	};
+
+	let try_from_f64 = options.try_from_f64.then(|| {
+		let convert = convert(quote::quote!(value as #repr));
+
+		quote::quote! {
+			#[automatically_derived]
+			impl ::core::convert::TryFrom<f64> for #ident {
+				type Error = ::enum_discrim::TryFromFloatError;
+
+				#[inline]
+				fn try_from(value: f64) -> ::core::result::Result<Self, Self::Error> {
+					if value % 1.0 != 0.0 {
+						return ::core::result::Result::Err(Self::Error::NotIntegral);
+					}
+					if !(value >= #repr::MIN as f64 && value < #repr::MAX as f64 + 1.0) {
+						return ::core::result::Result::Err(Self::Error::Invalid(
+							::enum_discrim::TryFromError::new_out_of_range(::core::stringify!(#ident)),
+						));
+					}
+
+					#convert.map_err(|err: #base_error| Self::Error::Invalid(err.into_untyped()))
+				}
+			}
+		}
+	});
+
+	if options.kani_proofs && default.is_some() {
+		return Err(darling::Error::custom(
+			"`#[discrim(kani_proofs)]` cannot be combined with a `#[discrim(default)]` variant, as unknown values do not round-trip",
+		)
+		.with_span(ident));
+	}
+	let kani_proofs = options.kani_proofs.then(|| {
+		let harness =
+			quote::format_ident!("{}_round_trip", crate::case::snake_case(&ident.to_string()));
+
+		quote::quote! {
+			/// Proves that any value either is rejected or round-trips through `Into`
+			#[cfg(kani)]
+			#[::kani::proof]
+			fn #harness() {
+				let value: #repr = ::kani::any();
+				if let ::core::result::Result::Ok(variant) =
+					<#ident as ::core::convert::TryFrom<#repr>>::try_from(value)
+				{
+					::core::assert_eq!(<#repr as ::core::convert::From<#ident>>::from(variant), value);
+				}
+			}
+		}
+	});
+
+	let try_from_char = if options.char {
+		if model.repr_c
+			|| !matches!(
+				model.repr,
+				PrimitiveRepresentation::u8 | PrimitiveRepresentation::u32
+			) {
+			return Err(darling::Error::custom(
+				"`#[discrim(char)]` requires a `#[repr(u8)]` or `#[repr(u32)]` enum",
+			)
+			.with_span(ident));
+		}
+
+		let convert = convert(quote::quote!(value));
+		Some(quote::quote! {
+			#[automatically_derived]
+			impl ::core::convert::TryFrom<char> for #ident {
+				type Error = #error_ty;
+
+				#[inline]
+				fn try_from(value: char) -> ::core::result::Result<Self, Self::Error> {
+					let value = <#repr as ::core::convert::TryFrom<char>>::try_from(value)
+						.map_err(|_| #out_of_range)?;
+					#convert
+				}
+			}
+		})
+	} else {
+		None
+	};
+
+	let try_from_c = model
+		.c_types()?
+		.into_iter()
+		.map(|(c_ty, _)| {
+			let convert = convert(quote::quote!(value));
+			quote::quote! {
+				#[automatically_derived]
+				impl ::core::convert::TryFrom<#c_ty> for #ident {
+					type Error = #error_ty;
+
+					#[inline]
+					fn try_from(value: #c_ty) -> ::core::result::Result<Self, Self::Error> {
+						let value = <#repr as ::core::convert::TryFrom<#c_ty>>::try_from(value)
+							.map_err(|_| #out_of_range)?;
+						#convert
+					}
+				}
+			}
+		})
+		.collect::<Vec<_>>();
+
+	let try_from_newtype = options.newtype_ident(ident).map(|newtype| match default {
+		Some(_) => quote::quote! {
+			#[automatically_derived]
+			impl ::core::convert::From<#newtype> for #ident {
+				#[inline]
+				fn from(value: #newtype) -> Self {
+					<Self as ::core::convert::From<#repr>>::from(value.0)
+				}
+			}
+		},
+		None => quote::quote! {
+			#[automatically_derived]
+			impl ::core::convert::TryFrom<#newtype> for #ident {
+				type Error = #error_ty;
+
+				#[inline]
+				fn try_from(value: #newtype) -> ::core::result::Result<Self, Self::Error> {
+					<Self as ::core::convert::TryFrom<#repr>>::try_from(value.0)
+				}
+			}
+		},
+	});
+
+	let conversions = if default.is_some() {
+		quote::quote! {
+			#[automatically_derived]
+			impl ::core::convert::From<#repr> for #ident {
+				#[inline]
+				fn from(value: #repr) -> Self {
+					#body
+				}
+			}
+
+			#[automatically_derived]
+			impl ::core::convert::From<&#repr> for #ident {
+				#[inline]
+				fn from(value: &#repr) -> Self {
+					<Self as ::core::convert::From<#repr>>::from(*value)
+				}
+			}
+
+			#[automatically_derived]
+			impl ::core::convert::From<&mut #repr> for #ident {
+				#[inline]
+				fn from(value: &mut #repr) -> Self {
+					<Self as ::core::convert::From<#repr>>::from(*value)
+				}
+			}
+		}
+	} else {
+		quote::quote! {
+			#[automatically_derived]
+			impl ::core::convert::TryFrom<#repr> for #ident {
+				type Error = #error_ty;
+
+				#[inline]
+				fn try_from(value: #repr) -> ::core::result::Result<Self, Self::Error> {
+					#body
+				}
+			}
+
+			#[automatically_derived]
+			impl ::core::convert::TryFrom<&#repr> for #ident {
+				type Error = #error_ty;
+
+				#[inline]
+				fn try_from(value: &#repr) -> ::core::result::Result<Self, Self::Error> {
+					<Self as ::core::convert::TryFrom<#repr>>::try_from(*value)
+				}
+			}
+
+			#[automatically_derived]
+			impl ::core::convert::TryFrom<&mut #repr> for #ident {
+				type Error = #error_ty;
+
+				#[inline]
+				fn try_from(value: &mut #repr) -> ::core::result::Result<Self, Self::Error> {
+					<Self as ::core::convert::TryFrom<#repr>>::try_from(*value)
+				}
+			}
+		}
+	};
+	let convert = convert(quote::quote!(value));
 
 	Ok(quote::quote! {
-		#[automatically_derived]
-		impl TryFrom<#repr> for #ident {
-			type Error = enum_discrim::TryFromError;
+		#conversions
 
+		impl #ident {
+			/// Converts any value convertible into the primitive representation
+			///
+			/// # Errors
+			/// Returns an error if the value is out of range of the primitive representation,
+			/// or if it is not a valid discriminant.
 			#[inline]
-			fn try_from(value: #repr) -> Result<Self, Self::Error> {
-				match value {
-					#(#arms)*
-					_ => Err(Self::Error::new(stringify!(#ident))),
-				}
+			#vis fn try_from_any<T>(value: T) -> ::core::result::Result<Self, #error_ty>
+			where
+				T: ::core::convert::TryInto<#repr>,
+			{
+				let value = ::core::convert::TryInto::try_into(value)
+					.map_err(|_| #out_of_range)?;
+				#convert
 			}
 		}
+
+		#try_from_f64
+
+		#try_from_char
+
+		#(#try_from_c)*
+
+		#try_from_newtype
+
+		#kani_proofs
 	}
 	.into())
 }
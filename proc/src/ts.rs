@@ -0,0 +1,62 @@
+//! Provides the code generation for the [`TS`](crate::derive_ts) derive macro
+
+use crate::EnumModel;
+use proc_macro::TokenStream;
+
+/// Derives an impl block for the `TS` trait of `ts-rs`, exporting the enum as a TypeScript const enum
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let EnumModel {
+		ident, variants, ..
+	} = EnumModel::parse_unit(item)?;
+
+	let decl = format!(
+		"const enum {ident} {{ {} }}",
+		variants
+			.iter()
+			.map(|variant| format!("{} = {}", variant.name, variant.number))
+			.collect::<Vec<_>>()
+			.join(", "),
+	);
+	let inline = if variants.is_empty() {
+		"never".to_owned()
+	} else {
+		variants
+			.iter()
+			.map(|variant| variant.number.as_str())
+			.collect::<Vec<_>>()
+			.join(" | ")
+	};
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl ::enum_discrim::__private::ts_rs::TS for #ident {
+			type WithoutGenerics = Self;
+
+			#[inline]
+			fn name() -> ::enum_discrim::__private::alloc::string::String {
+				::enum_discrim::__private::alloc::string::ToString::to_string(::core::stringify!(#ident))
+			}
+
+			#[inline]
+			fn decl() -> ::enum_discrim::__private::alloc::string::String {
+				::enum_discrim::__private::alloc::string::ToString::to_string(#decl)
+			}
+
+			#[inline]
+			fn decl_concrete() -> ::enum_discrim::__private::alloc::string::String {
+				Self::decl()
+			}
+
+			#[inline]
+			fn inline() -> ::enum_discrim::__private::alloc::string::String {
+				::enum_discrim::__private::alloc::string::ToString::to_string(#inline)
+			}
+
+			#[inline]
+			fn inline_flattened() -> ::enum_discrim::__private::alloc::string::String {
+				Self::inline()
+			}
+		}
+	}
+	.into())
+}
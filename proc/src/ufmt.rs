@@ -0,0 +1,63 @@
+//! Provides the code generation for the [`UFmt`](crate::derive_ufmt) derive macro
+
+use crate::EnumModel;
+use proc_macro::TokenStream;
+
+/// Derives impl `uDisplay` and `uDebug` blocks, writing precomputed strings so that no formatting happens at runtime
+///
+/// `uDisplay` writes the name of the variant, and `uDebug` also writes its discriminant in hexadecimal.
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let EnumModel {
+		ident, variants, ..
+	} = EnumModel::parse_unit(item)?;
+
+	let display_arms = variants.iter().map(|variant| {
+		let name = &variant.ident;
+		let value = &variant.name;
+		quote::quote!(Self::#name => #value,)
+	});
+	let debug_arms = variants.iter().map(|variant| {
+		let name = &variant.ident;
+		let value = format!(
+			"{}(={})",
+			variant.name,
+			crate::attribute::hex(&variant.number)
+		);
+		quote::quote!(Self::#name => #value,)
+	});
+
+	Ok(quote::quote! {
+		#[automatically_derived]
+		impl ::enum_discrim::__private::ufmt::uDisplay for #ident {
+			#[inline]
+			fn fmt<W>(
+				&self,
+				f: &mut ::enum_discrim::__private::ufmt::Formatter<'_, W>,
+			) -> ::core::result::Result<(), W::Error>
+			where
+				W: ::enum_discrim::__private::ufmt::uWrite + ?Sized,
+			{
+				f.write_str(match *self {
+					#(#display_arms)*
+				})
+			}
+		}
+
+		#[automatically_derived]
+		impl ::enum_discrim::__private::ufmt::uDebug for #ident {
+			#[inline]
+			fn fmt<W>(
+				&self,
+				f: &mut ::enum_discrim::__private::ufmt::Formatter<'_, W>,
+			) -> ::core::result::Result<(), W::Error>
+			where
+				W: ::enum_discrim::__private::ufmt::uWrite + ?Sized,
+			{
+				f.write_str(match *self {
+					#(#debug_arms)*
+				})
+			}
+		}
+	}
+	.into())
+}
@@ -0,0 +1,79 @@
+//! Provides the code generation for the [`Valuable`](crate::derive_valuable) derive macro
+
+use crate::EnumModel;
+use proc_macro::TokenStream;
+
+/// Derives impl `Valuable` and `Enumerable` blocks for `valuable`, exposing the name and the discriminant of each variant
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	let EnumModel {
+		ident,
+		repr,
+		variants,
+		..
+	} = EnumModel::parse_unit(item)?;
+	let names = variants
+		.iter()
+		.map(|variant| &variant.name)
+		.collect::<Vec<_>>();
+	let variant_arms = variants
+		.iter()
+		.enumerate()
+		.map(|(ordinal, variant)| {
+			let name = &variant.ident;
+			quote::quote!(Self::#name => Variant::Static(&VARIANTS[#ordinal]),)
+		})
+		.collect::<Vec<_>>();
+	let value_arms = variants
+		.iter()
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = &variant.value;
+			quote::quote!(Self::#name => #value,)
+		})
+		.collect::<Vec<_>>();
+
+	Ok(quote::quote! {
+		const _: () = {
+			use ::enum_discrim::__private::valuable::{
+				EnumDef, Enumerable, Fields, Valuable, Value, Variant, VariantDef, Visit,
+			};
+
+			/// Definitions of the variants, each having the discriminant as its only field
+			static VARIANTS: &[VariantDef<'static>] = &[
+				#(VariantDef::new(#names, Fields::Unnamed(1)),)*
+			];
+
+			#[automatically_derived]
+			impl Enumerable for #ident {
+				#[inline]
+				fn definition(&self) -> EnumDef<'_> {
+					EnumDef::new_static(::core::stringify!(#ident), VARIANTS)
+				}
+
+				#[inline]
+				fn variant(&self) -> Variant<'_> {
+					match self {
+						#(#variant_arms)*
+					}
+				}
+			}
+
+			#[automatically_derived]
+			impl Valuable for #ident {
+				#[inline]
+				fn as_value(&self) -> Value<'_> {
+					Value::Enumerable(self)
+				}
+
+				#[inline]
+				fn visit(&self, visitor: &mut dyn Visit) {
+					let value: #repr = match self {
+						#(#value_arms)*
+					};
+					visitor.visit_unnamed_fields(&[Valuable::as_value(&value)]);
+				}
+			}
+		};
+	}
+	.into())
+}
@@ -0,0 +1,88 @@
+//! Provides the code generation for the [`Varint`](crate::derive_varint) derive macro
+
+use crate::{EnumModel, PrimitiveRepresentation};
+use proc_macro::TokenStream;
+
+/// Derives an impl block containing functions to encode and decode the discriminant as a LEB128 varint
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	use proc_macro2::Literal;
+
+	let EnumModel {
+		ident,
+		vis,
+		repr,
+		variants,
+		..
+	} = EnumModel::parse_unit(item)?;
+	if !matches!(
+		repr,
+		PrimitiveRepresentation::u8
+			| PrimitiveRepresentation::u16
+			| PrimitiveRepresentation::u32
+			| PrimitiveRepresentation::u64
+			| PrimitiveRepresentation::usize
+	) {
+		return Err(darling::Error::custom(format!(
+			"varints are only supported for unsigned representations of at most 64 bits, not `{repr}`"
+		))
+		.with_span(&ident));
+	}
+
+	let (encode_arms, decode_arms): (Vec<_>, Vec<_>) = variants
+		.iter()
+		.map(|variant| {
+			let name = &variant.ident;
+			let value = variant
+				.number
+				.parse()
+				.map(Literal::u64_suffixed)
+				.map_err(|err| darling::Error::custom(err).with_span(name))?;
+			Ok((
+				quote::quote!(Self::#name => #value,),
+				variant.is_decoded().then(
+					|| quote::quote!(#value => ::core::result::Result::Ok((Self::#name, len)),),
+				),
+			))
+		})
+		.collect::<darling::Result<Vec<_>>>()?
+		.into_iter()
+		.unzip();
+
+	Ok(quote::quote! {
+		impl #ident {
+			/// Encodes the discriminant of the given variant as a varint into the given buffer,
+			/// returning the number of bytes written
+			///
+			/// # Panics
+			/// Panics if the buffer is too short to hold the varint,
+			/// which is never the case with [`MAX_LEN`](::enum_discrim::varint::MAX_LEN) bytes.
+			#[inline]
+			#vis fn encode_varint(&self, buf: &mut [u8]) -> usize {
+				let value: u64 = match self {
+					#(#encode_arms)*
+				};
+				::enum_discrim::varint::encode(value, buf)
+			}
+
+			/// Decodes a variant from the varint at the start of the given buffer,
+			/// returning it with the number of bytes read
+			///
+			/// # Errors
+			/// Returns an error if the buffer does not start with a valid varint,
+			/// or if the varint is not a valid discriminant.
+			#[inline]
+			#vis fn decode_varint(
+				buf: &[u8],
+			) -> ::core::result::Result<(Self, usize), ::enum_discrim::varint::VarintError> {
+				let (value, len) = ::enum_discrim::varint::decode(buf)?;
+				match value {
+					#(#decode_arms)*
+					_ => ::core::result::Result::Err(::enum_discrim::varint::VarintError::Invalid(
+						::enum_discrim::TryFromError::new(::core::stringify!(#ident)),
+					)),
+				}
+			}
+		}
+	}
+	.into())
+}
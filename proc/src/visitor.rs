@@ -0,0 +1,100 @@
+//! Provides [the parsing struct](VisitorInput) for the [`Visitor`](crate::derive_visitor) derive macro
+
+use darling::{ast::Data, util::SpannedValue, FromDeriveInput};
+use proc_macro::TokenStream;
+use syn::{Generics, Ident, Variant, Visibility};
+
+/// Parsing struct for the [`Visitor`](crate::derive_visitor) derive macro
+#[derive(Debug, FromDeriveInput)]
+#[darling(supports(enum_any))]
+struct VisitorInput {
+	/// Enum identifier
+	ident: Ident,
+	/// Enum visibility
+	vis: Visibility,
+	/// Enum generics
+	generics: Generics,
+	/// Variants contained in the enum
+	data: Data<SpannedValue<Variant>, ()>,
+}
+
+/// Derives a visitor trait with a method for each variant, and an `accept` function dispatching to it
+pub(crate) fn derive(item: TokenStream) -> darling::Result<TokenStream> {
+	use syn::{ext::IdentExt, DeriveInput, Fields};
+
+	let item: DeriveInput = syn::parse(item)?;
+	let VisitorInput {
+		ident,
+		vis,
+		generics,
+		data,
+	} = VisitorInput::from_derive_input(&item)?;
+	let Data::Enum(data) = data else {
+		unreachable!()
+	};
+
+	let visitor = quote::format_ident!("{}Visitor", ident);
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	let predicates = where_clause.map(|where_clause| &where_clause.predicates);
+	let (methods, arms): (Vec<_>, Vec<_>) = data
+		.iter()
+		.map(|variant| {
+			let span = variant.span();
+			let name = &variant.ident;
+			let method = quote::format_ident!(
+				"visit_{}",
+				crate::case::snake_case(&name.unraw().to_string()),
+				span = name.span()
+			);
+			let doc = format!("Visits the [{name}]({ident}::{name}) variant");
+			let (bindings, types): (Vec<_>, Vec<_>) = variant
+				.fields
+				.iter()
+				.enumerate()
+				.map(|(index, field)| {
+					let binding = field
+						.ident
+						.clone()
+						.unwrap_or_else(|| quote::format_ident!("_{}", index));
+					(binding, &field.ty)
+				})
+				.unzip();
+			let pattern = match &variant.fields {
+				Fields::Named(_) => quote::quote!(Self::#name { #(#bindings),* }),
+				Fields::Unnamed(_) => quote::quote!(Self::#name(#(#bindings),*)),
+				Fields::Unit => quote::quote!(Self::#name),
+			};
+
+			(
+				quote::quote_spanned! {span=>
+					#[doc = #doc]
+					fn #method(&mut self, #(#bindings: &#types),*);
+				},
+				quote::quote_spanned!(span=> #pattern => visitor.#method(#(#bindings),*),),
+			)
+		})
+		.unzip();
+	let doc = format!("Visitor of the variants of [`{ident}`]");
+
+	Ok(quote::quote! {
+		#[doc = #doc]
+		#vis trait #visitor #impl_generics #where_clause {
+			#(#methods)*
+		}
+
+		impl #impl_generics #ident #ty_generics #where_clause {
+			/// Calls the method of the given visitor matching the variant, with references to its fields
+			#[inline]
+			#vis fn accept<V>(&self, visitor: &mut V)
+			where
+				V: #visitor #ty_generics,
+				#predicates
+			{
+				match self {
+					#(#arms)*
+				}
+			}
+		}
+	}
+	.into())
+}
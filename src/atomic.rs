@@ -0,0 +1,228 @@
+//! Provides [`AtomicEnum`], an atomic storage cell for enums deriving [`Into`](crate::Into) and [`TryFrom`](crate::TryFrom)
+
+use std::{
+	fmt::{self, Debug, Formatter},
+	marker::PhantomData,
+	sync::atomic::{
+		AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize, AtomicU16, AtomicU32, AtomicU64,
+		AtomicU8, AtomicUsize, Ordering,
+	},
+};
+
+/// Primitive representation backed by an atomic integer type
+///
+/// This trait is implemented for every [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations)
+/// which has a stable atomic counterpart (i.e. all of them but `u128` and `i128`).
+pub trait AtomicRepr: Copy + Eq {
+	/// Matching atomic integer type
+	type Atomic: Debug;
+
+	/// Creates a new atomic integer initialized with `self`
+	fn new_atomic(self) -> Self::Atomic;
+	/// Loads a value from the atomic integer
+	fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+	/// Stores a value into the atomic integer
+	fn store(atomic: &Self::Atomic, value: Self, order: Ordering);
+	/// Stores a value into the atomic integer, returning the previous value
+	fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+	/// Stores a value into the atomic integer if the current value is the same as `current`
+	///
+	/// # Errors
+	/// Returns the current value if it is not the same as `current`.
+	fn compare_exchange(
+		atomic: &Self::Atomic,
+		current: Self,
+		new: Self,
+		success: Ordering,
+		failure: Ordering,
+	) -> Result<Self, Self>;
+}
+/// Generates an impl [`AtomicRepr`] block for each given pair of types
+macro_rules! impl_atomic_repr {
+	($( $ty:ty => $atomic:ty ),* $(,)?) => {
+		$(
+			impl AtomicRepr for $ty {
+				type Atomic = $atomic;
+
+				#[inline]
+				fn new_atomic(self) -> Self::Atomic {
+					<$atomic>::new(self)
+				}
+
+				#[inline]
+				fn load(atomic: &Self::Atomic, order: Ordering) -> Self {
+					atomic.load(order)
+				}
+
+				#[inline]
+				fn store(atomic: &Self::Atomic, value: Self, order: Ordering) {
+					atomic.store(value, order);
+				}
+
+				#[inline]
+				fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+					atomic.swap(value, order)
+				}
+
+				#[inline]
+				fn compare_exchange(
+					atomic: &Self::Atomic,
+					current: Self,
+					new: Self,
+					success: Ordering,
+					failure: Ordering,
+				) -> Result<Self, Self> {
+					atomic.compare_exchange(current, new, success, failure)
+				}
+			}
+		)*
+	};
+}
+impl_atomic_repr![
+	u8 => AtomicU8,
+	u16 => AtomicU16,
+	u32 => AtomicU32,
+	u64 => AtomicU64,
+	usize => AtomicUsize,
+	i8 => AtomicI8,
+	i16 => AtomicI16,
+	i32 => AtomicI32,
+	i64 => AtomicI64,
+	isize => AtomicIsize,
+];
+
+/// Enum which may be safely stored in an atomic integer
+///
+/// This trait is automatically implemented for all enums deriving both [`Into`](crate::Into) and [`TryFrom`](crate::TryFrom).
+pub trait AtomicEnumRepr<R>: Copy + Into<R> + TryFrom<R>
+where
+	R: AtomicRepr,
+{
+}
+impl<E, R> AtomicEnumRepr<R> for E
+where
+	E: Copy + Into<R> + TryFrom<R>,
+	R: AtomicRepr,
+{
+}
+
+/// Atomic storage cell for an enum, backed by the atomic integer matching its primitive representation
+///
+/// Values are converted with [`Into<R>`] on store, and validated with [`TryFrom<R>`] on load.
+///
+/// # Example
+/// ```
+/// use enum_discrim::{atomic::AtomicEnum, Into, TryFrom};
+/// use std::sync::atomic::Ordering;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Into, TryFrom)]
+/// #[repr(u8)]
+/// enum State {
+///     Idle,
+///     Running,
+///     Stopped,
+/// }
+///
+/// let state = AtomicEnum::<State, u8>::new(State::Idle);
+/// assert_eq!(state.load(Ordering::Acquire).unwrap(), State::Idle);
+/// state.store(State::Running, Ordering::Release);
+/// assert_eq!(
+///     state
+///         .compare_exchange(State::Running, State::Stopped, Ordering::AcqRel, Ordering::Acquire)
+///         .unwrap(),
+///     Ok(State::Running),
+/// );
+/// assert_eq!(state.load(Ordering::Acquire).unwrap(), State::Stopped);
+/// ```
+pub struct AtomicEnum<E, R>
+where
+	E: AtomicEnumRepr<R>,
+	R: AtomicRepr,
+{
+	/// Underlying atomic integer
+	atomic: R::Atomic,
+	/// Marker of the stored enum
+	_enum: PhantomData<E>,
+}
+impl<E, R> AtomicEnum<E, R>
+where
+	E: AtomicEnumRepr<R>,
+	R: AtomicRepr,
+{
+	/// Creates a new atomic cell initialized with the given value
+	#[inline]
+	pub fn new(value: E) -> Self {
+		Self {
+			atomic: R::new_atomic(value.into()),
+			_enum: PhantomData,
+		}
+	}
+
+	/// Loads the value of the cell
+	///
+	/// # Errors
+	/// Returns the error of the [`TryFrom<R>`] implementation
+	/// if the stored integer is not a valid discriminant.
+	#[inline]
+	pub fn load(&self, order: Ordering) -> Result<E, <E as TryFrom<R>>::Error> {
+		E::try_from(R::load(&self.atomic, order))
+	}
+
+	/// Stores a value into the cell
+	#[inline]
+	pub fn store(&self, value: E, order: Ordering) {
+		R::store(&self.atomic, value.into(), order);
+	}
+
+	/// Stores a value into the cell, returning the previous value
+	///
+	/// # Errors
+	/// Returns the error of the [`TryFrom<R>`] implementation
+	/// if the previously stored integer is not a valid discriminant.
+	#[inline]
+	pub fn swap(&self, value: E, order: Ordering) -> Result<E, <E as TryFrom<R>>::Error> {
+		E::try_from(R::swap(&self.atomic, value.into(), order))
+	}
+
+	/// Stores a value into the cell if the current value is the same as `current`
+	///
+	/// The inner [`Result`] is [`Ok`] with the previous value if it was updated,
+	/// and [`Err`] with the current value otherwise.
+	///
+	/// # Errors
+	/// Returns the error of the [`TryFrom<R>`] implementation
+	/// if the previously stored integer is not a valid discriminant.
+	#[inline]
+	pub fn compare_exchange(
+		&self,
+		current: E,
+		new: E,
+		success: Ordering,
+		failure: Ordering,
+	) -> Result<Result<E, E>, <E as TryFrom<R>>::Error> {
+		match R::compare_exchange(&self.atomic, current.into(), new.into(), success, failure) {
+			Ok(previous) => E::try_from(previous).map(Ok),
+			Err(previous) => E::try_from(previous).map(Err),
+		}
+	}
+
+	/// Consumes the cell and returns the contained value
+	///
+	/// # Errors
+	/// Returns the error of the [`TryFrom<R>`] implementation
+	/// if the stored integer is not a valid discriminant.
+	#[inline]
+	pub fn into_inner(self) -> Result<E, <E as TryFrom<R>>::Error> {
+		self.load(Ordering::Relaxed)
+	}
+}
+impl<E, R> Debug for AtomicEnum<E, R>
+where
+	E: AtomicEnumRepr<R>,
+	R: AtomicRepr,
+{
+	#[inline]
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("AtomicEnum").field(&self.atomic).finish()
+	}
+}
@@ -0,0 +1,115 @@
+//! Provides [`try_from_slice`] and [`try_from_iter`], converting many raw values at once
+//!
+//! The conversions stop at the first invalid value, reporting its position in a [`BulkError`].
+
+use std::{
+	error::Error,
+	fmt::{self, Debug, Display, Formatter},
+};
+
+/// Error returned by [`try_from_slice`] and [`try_from_iter`]
+#[derive(Debug, Clone, Copy)]
+pub struct BulkError<R, Err> {
+	/// Position of the invalid value
+	index: usize,
+	/// Invalid value
+	value: R,
+	/// Error returned by the conversion of the invalid value
+	error: Err,
+}
+impl<R, Err> BulkError<R, Err> {
+	/// Returns the position of the invalid value
+	#[inline]
+	pub const fn index(&self) -> usize {
+		self.index
+	}
+
+	/// Returns the invalid value
+	#[inline]
+	pub const fn value(&self) -> &R {
+		&self.value
+	}
+
+	/// Returns the error returned by the conversion of the invalid value
+	#[inline]
+	pub const fn error(&self) -> &Err {
+		&self.error
+	}
+}
+impl<R, Err> Display for BulkError<R, Err>
+where
+	R: Display,
+	Err: Display,
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"Invalid value {} at index {}: {}",
+			self.value, self.index, self.error
+		)
+	}
+}
+impl<R, Err> Error for BulkError<R, Err>
+where
+	R: Debug + Display,
+	Err: Error + 'static,
+{
+	#[inline]
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		Some(&self.error)
+	}
+}
+
+/// Converts all the given raw values into enum variants
+///
+/// # Errors
+/// Returns a [`BulkError`] holding the position of the first value which cannot be converted.
+///
+/// # Example
+/// ```
+/// use enum_discrim::{bulk::try_from_slice, TryFrom};
+///
+/// #[derive(Debug, PartialEq, Eq, TryFrom)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+/// }
+///
+/// assert_eq!(try_from_slice::<E, u8>(&[2, 0]).unwrap(), [E::B, E::A]);
+///
+/// let err = try_from_slice::<E, u8>(&[0, 2, 1]).unwrap_err();
+/// assert_eq!(err.index(), 2);
+/// assert_eq!(*err.value(), 1);
+/// ```
+#[inline]
+pub fn try_from_slice<E, R>(values: &[R]) -> Result<Vec<E>, BulkError<R, E::Error>>
+where
+	E: TryFrom<R>,
+	R: Copy,
+{
+	try_from_iter(values.iter().copied())
+}
+
+/// Converts all the raw values of the given iterator into enum variants
+///
+/// # Errors
+/// Returns a [`BulkError`] holding the position of the first value which cannot be converted.
+pub fn try_from_iter<E, R, I>(values: I) -> Result<Vec<E>, BulkError<R, E::Error>>
+where
+	E: TryFrom<R>,
+	R: Copy,
+	I: IntoIterator<Item = R>,
+{
+	values
+		.into_iter()
+		.enumerate()
+		.map(|(index, value)| {
+			E::try_from(value).map_err(|error| BulkError {
+				index,
+				value,
+				error,
+			})
+		})
+		.collect()
+}
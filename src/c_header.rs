@@ -0,0 +1,52 @@
+//! Provides [`c_header`], rendering C declarations of enums deriving [`CHeader`](crate::CHeader)
+//!
+//! This is meant to be called from a build script or a test,
+//! so that C headers stay in sync with the Rust enums.
+
+/// Description of an enum as C declarations
+///
+/// This trait is implemented by the [`CHeader`](macro@crate::CHeader) derive macro.
+pub trait CEnum {
+	/// Name of the enum
+	const NAME: &'static str;
+	/// C type matching the primitive representation of the enum (from `<stdint.h>` or `<stddef.h>`)
+	const C_TYPE: &'static str;
+	/// Name and discriminant (as a C expression of type [`C_TYPE`](Self::C_TYPE)) of each variant, in declaration order
+	const VARIANTS: &'static [(&'static str, &'static str)];
+}
+
+/// Renders the C declarations of the given enum
+///
+/// The enum is declared as a `typedef` of its primitive representation,
+/// and each variant as a `#define` named `{Enum}_{Variant}`.
+/// The rendered declarations require `<stdint.h>` and `<stddef.h>`.
+///
+/// # Example
+/// ```
+/// use enum_discrim::{c_header::c_header, CHeader};
+///
+/// #[derive(CHeader)]
+/// #[repr(u8)]
+/// enum Status {
+///     Ok,
+///     NotFound = 4,
+/// }
+///
+/// assert_eq!(
+///     c_header::<Status>(),
+///     "typedef uint8_t Status;\n#define Status_Ok UINT8_C(0)\n#define Status_NotFound UINT8_C(4)\n",
+/// );
+/// ```
+pub fn c_header<E>() -> String
+where
+	E: CEnum,
+{
+	use std::fmt::Write;
+
+	let mut header = format!("typedef {} {};\n", E::C_TYPE, E::NAME);
+	for (name, value) in E::VARIANTS {
+		// Writing into a `String` never fails
+		let _ = writeln!(header, "#define {}_{name} {value}", E::NAME);
+	}
+	header
+}
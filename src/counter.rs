@@ -0,0 +1,157 @@
+//! Provides [`DiscriminantCounter`], a histogram counting occurrences of each variant of an enum
+
+use crate::Ordinal;
+use std::{
+	fmt::{self, Debug, Formatter},
+	marker::PhantomData,
+};
+
+/// Histogram counting occurrences of each variant of an enum deriving [`Ordinal`](macro@crate::Ordinal)
+///
+/// # Example
+/// ```
+/// use enum_discrim::{counter::DiscriminantCounter, Ordinal};
+///
+/// #[derive(Debug, PartialEq, Eq, Ordinal)]
+/// enum Message {
+///     Ping,
+///     Data,
+///     Close,
+/// }
+///
+/// let mut counter = DiscriminantCounter::new();
+/// counter.inc(&Message::Data);
+/// counter.inc(&Message::Ping);
+/// counter.inc(&Message::Data);
+/// assert_eq!(counter.get(&Message::Data), 2);
+/// assert_eq!(
+///     counter.iter().collect::<Vec<_>>(),
+///     [(Message::Ping, 1), (Message::Data, 2), (Message::Close, 0)],
+/// );
+/// ```
+pub struct DiscriminantCounter<E>
+where
+	E: Ordinal,
+{
+	/// Counters, indexed by ordinal
+	counts: Box<[u64]>,
+	/// Marker of the counted enum
+	_enum: PhantomData<E>,
+}
+impl<E> DiscriminantCounter<E>
+where
+	E: Ordinal,
+{
+	/// Creates a new counter with all variants at zero
+	#[inline]
+	pub fn new() -> Self {
+		Self {
+			counts: vec![0; E::COUNT].into_boxed_slice(),
+			_enum: PhantomData,
+		}
+	}
+
+	/// Increments the counter of the given variant by one
+	#[inline]
+	pub fn inc(&mut self, variant: &E) {
+		self.add(variant, 1);
+	}
+
+	/// Increments the counter of the given variant by `n`
+	///
+	/// The counter saturates at [`u64::MAX`].
+	#[inline]
+	pub fn add(&mut self, variant: &E, n: u64) {
+		let count = &mut self.counts[variant.ordinal()];
+		*count = count.saturating_add(n);
+	}
+
+	/// Returns the counter of the given variant
+	#[inline]
+	pub fn get(&self, variant: &E) -> u64 {
+		self.counts[variant.ordinal()]
+	}
+
+	/// Returns the sum of all counters
+	#[inline]
+	pub fn total(&self) -> u64 {
+		self.counts
+			.iter()
+			.fold(0, |total, count| total.saturating_add(*count))
+	}
+
+	/// Resets all counters to zero
+	#[inline]
+	pub fn clear(&mut self) {
+		self.counts.fill(0);
+	}
+
+	/// Returns an iterator over all variants and their counters, in declaration order
+	#[inline]
+	pub fn iter(&self) -> impl Iterator<Item = (E, u64)> + '_ {
+		self.counts
+			.iter()
+			.enumerate()
+			.filter_map(|(ordinal, count)| {
+				E::from_ordinal(ordinal).map(|variant| (variant, *count))
+			})
+	}
+}
+impl<E> Default for DiscriminantCounter<E>
+where
+	E: Ordinal,
+{
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+impl<E> Clone for DiscriminantCounter<E>
+where
+	E: Ordinal,
+{
+	#[inline]
+	fn clone(&self) -> Self {
+		Self {
+			counts: self.counts.clone(),
+			_enum: PhantomData,
+		}
+	}
+}
+impl<E> Debug for DiscriminantCounter<E>
+where
+	E: Ordinal + Debug,
+{
+	#[inline]
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_map().entries(self.iter()).finish()
+	}
+}
+impl<E> Extend<E> for DiscriminantCounter<E>
+where
+	E: Ordinal,
+{
+	#[inline]
+	fn extend<I>(&mut self, iter: I)
+	where
+		I: IntoIterator<Item = E>,
+	{
+		for variant in iter {
+			self.inc(&variant);
+		}
+	}
+}
+impl<E> FromIterator<E> for DiscriminantCounter<E>
+where
+	E: Ordinal,
+{
+	#[inline]
+	fn from_iter<I>(iter: I) -> Self
+	where
+		I: IntoIterator<Item = E>,
+	{
+		let mut counter = Self::new();
+		counter.extend(iter);
+		counter
+	}
+}
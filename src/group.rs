@@ -0,0 +1,79 @@
+//! Provides [`group_by_discriminant`] and [`partition_by_discriminant`], splitting iterators of enum variants by discriminant
+//!
+//! The discriminant of each variant is returned by a given function,
+//! such as the `discriminant` function generated by the [`Discriminants`](macro@crate::Discriminants) derive macro,
+//! so that enums with fields can be grouped as well.
+
+use std::collections::BTreeMap;
+
+/// Groups the variants of the given iterator into buckets of variants with the same discriminant
+///
+/// The buckets are sorted by discriminant, and the variants keep their relative order within each bucket.
+///
+/// # Example
+/// ```
+/// use enum_discrim::{group::group_by_discriminant, Discriminants};
+///
+/// #[derive(Debug, PartialEq, Eq, Discriminants)]
+/// #[repr(u8)]
+/// enum Message {
+///     Ping = 1,
+///     Data(Vec<u8>) = 2,
+/// }
+///
+/// let messages = [Message::Data(vec![1]), Message::Ping, Message::Data(vec![2])];
+/// let groups = group_by_discriminant(messages, Message::discriminant);
+/// assert_eq!(groups[&1], [Message::Ping]);
+/// assert_eq!(groups[&2], [Message::Data(vec![1]), Message::Data(vec![2])]);
+/// ```
+pub fn group_by_discriminant<E, R, I, F>(variants: I, mut discriminant: F) -> BTreeMap<R, Vec<E>>
+where
+	I: IntoIterator<Item = E>,
+	F: FnMut(&E) -> R,
+	R: Ord,
+{
+	let mut groups = BTreeMap::<R, Vec<E>>::new();
+	for variant in variants {
+		groups
+			.entry(discriminant(&variant))
+			.or_default()
+			.push(variant);
+	}
+	groups
+}
+
+/// Splits the variants of the given iterator into the ones with the given discriminant, and the other ones
+///
+/// The variants keep their relative order within each part.
+///
+/// # Example
+/// ```
+/// use enum_discrim::{group::partition_by_discriminant, Discriminants};
+///
+/// #[derive(Debug, PartialEq, Eq, Discriminants)]
+/// #[repr(u8)]
+/// enum Message {
+///     Ping = 1,
+///     Data(Vec<u8>) = 2,
+/// }
+///
+/// let messages = [Message::Data(vec![1]), Message::Ping, Message::Data(vec![2])];
+/// let (data, others) = partition_by_discriminant(messages, Message::discriminant, Message::Data_D);
+/// assert_eq!(data, [Message::Data(vec![1]), Message::Data(vec![2])]);
+/// assert_eq!(others, [Message::Ping]);
+/// ```
+#[inline]
+pub fn partition_by_discriminant<E, R, I, F>(
+	variants: I,
+	mut discriminant: F,
+	value: R,
+) -> (Vec<E>, Vec<E>)
+where
+	I: IntoIterator<Item = E>,
+	F: FnMut(&E) -> R,
+	R: PartialEq,
+{
+	variants
+		.into_iter()
+		.partition(|variant| discriminant(variant) == value)
+}
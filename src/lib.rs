@@ -48,10 +48,15 @@
 )]
 #![forbid(unsafe_code, clippy::missing_panics_doc, clippy::missing_errors_doc)]
 
-use std::{
-	error::Error,
-	fmt::{self, Display, Formatter},
-};
+// Public, so that it may be re-exported to the generated code by `__private`
+#[doc(hidden)]
+pub extern crate alloc;
+// Allows the generated code to refer to `::enum_discrim` from within this crate
+extern crate self as enum_discrim;
+
+// Dev-dependencies only used in doctests
+#[cfg(test)]
+use {serde as _, serde_json as _};
 
 /// Derives an impl block containing the discriminants of all enum variants as consts
 ///
@@ -74,7 +79,23 @@ use std::{
 /// }
 /// ```
 ///
-/// The only restriction is that you *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// The only restriction is that you *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations)
+/// or the [`C` representation](https://doc.rust-lang.org/reference/type-layout.html#reprc-enums-with-fields) for your enum.
+/// With `#[repr(C)]` alone, the discriminant type is [`c_int`](core::ffi::c_int):
+/// ```
+/// use enum_discrim::Discriminants;
+/// use std::ffi::c_int;
+///
+/// #[derive(Discriminants)]
+/// #[repr(C)]
+/// enum E {
+///     A(u64),
+///     B { b: bool },
+/// }
+///
+/// assert_eq!(E::B_D, 1 as c_int);
+/// assert_eq!(E::B { b: true }.discriminant(), 1 as c_int);
+/// ```
 /// ```compile_fail
 /// use enum_discrim::Discriminants;
 ///
@@ -90,21 +111,426 @@ use std::{
 /// }
 /// ```
 ///
+/// Discriminants may be integer literals of any radix, which are kept as written in the generated consts,
+/// or const expressions combining them with `-`, `+`, `<<`, `>>` and `|`, which are evaluated by the macro:
+/// ```
+/// use enum_discrim::Discriminants;
+///
+/// #[derive(Discriminants)]
+/// #[repr(i16)]
+/// enum Flags {
+///     Read = 1 << 0,
+///     Write = 1 << 1,
+///     ReadWrite = (1 << 0) | (1 << 1),
+///     Invalid = -0x100 + 1,
+/// }
+///
+/// assert_eq!(Flags::ReadWrite_D, 3);
+/// assert_eq!(Flags::Invalid_D, -255);
+/// ```
+///
+/// Discriminants may also be paths to consts, which are passed through verbatim to the generated code,
+/// leaving their evaluation to the compiler.
+/// The variants following them then need an explicit discriminant.
+/// Only this macro, [`Into`](macro@Into) and [`TryFrom`](macro@TryFrom) support these discriminants,
+/// without the options requiring their values (e.g. `#[discrim(next_free)]`):
+/// ```
+/// use enum_discrim::{Discriminants, TryFrom};
+///
+/// const BASE: u8 = 0x40;
+///
+/// #[derive(Debug, PartialEq, Eq, Discriminants, TryFrom)]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Load = BASE,
+///     Store = 2,
+///     Jump,
+/// }
+///
+/// assert_eq!(Opcode::Load_D, 0x40);
+/// assert_eq!(Opcode::try_from(0x40).unwrap(), Opcode::Load);
+/// assert_eq!(Opcode::try_from(3).unwrap(), Opcode::Jump);
+/// ```
+/// ```compile_fail
+/// use enum_discrim::Discriminants;
+///
+/// const BASE: u8 = 0x40;
+///
+/// #[derive(Discriminants)]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Load = BASE,
+///     // COMPILE ERROR: variants following a path discriminant need an explicit discriminant
+///     Store,
+/// }
+/// ```
+///
+/// The `discriminant` function is also provided by the [`Discriminant`] trait, for generic code:
+/// ```
+/// use enum_discrim::{Discriminant, Discriminants};
+///
+/// #[derive(Discriminants)]
+/// #[repr(u8)]
+/// enum Message {
+///     Ping = 1,
+///     Data(Vec<u8>) = 2,
+/// }
+///
+/// fn tags<E: Discriminant>(variants: &[E]) -> Vec<E::Repr> {
+///     variants.iter().map(Discriminant::discriminant).collect()
+/// }
+///
+/// assert_eq!(tags(&[Message::Data(vec![]), Message::Ping]), [2, 1]);
+/// ```
+///
+/// Alternatively, `#[discrim(logical_repr = "...")]` declares the type of the discriminants
+/// without changing the layout of the enum.
+/// The discriminant of an instance is then obtained by matching on its variant:
+/// ```
+/// use enum_discrim::{Discriminants, Into, TryFrom};
+///
+/// #[derive(Debug, PartialEq, Eq, Clone, Copy, Discriminants, Into, TryFrom)]
+/// #[discrim(logical_repr = "u16")]
+/// enum E {
+///     A = 1,
+///     B = 300,
+/// }
+///
+/// assert_eq!(E::B.discriminant(), 300_u16);
+/// assert_eq!(u16::from(E::A), 1);
+/// assert_eq!(E::try_from(300_u16).unwrap(), E::B);
+/// ```
+///
+/// With `#[discrim(logical_repr = "...")]`, `#[discrim(value = ...)]` overrides the discriminant of a variant.
+/// Along with `#[discrim(allow_aliases)]`, it lets several variants share a discriminant
+/// (which the compiler rejects for actual discriminants), e.g. for synonyms of legacy protocols.
+/// Conversions into a discriminant still work for every variant,
+/// while conversions from a discriminant return the first variant declared with it, or the one marked with `#[discrim(canonical)]`:
+/// ```
+/// use enum_discrim::{Discriminants, Into, TryFrom};
+///
+/// #[derive(Debug, PartialEq, Eq, Discriminants, Into, TryFrom)]
+/// #[discrim(logical_repr = "u8", allow_aliases)]
+/// enum Command {
+///     #[discrim(value = 1)]
+///     Quit,
+///     #[discrim(value = 1)]
+///     Exit,
+///     #[discrim(value = 2)]
+///     Help,
+///     #[discrim(value = 2, canonical)]
+///     Usage,
+/// }
+///
+/// assert_eq!(u8::from(Command::Exit), 1);
+/// assert_eq!(Command::try_from(1_u8).unwrap(), Command::Quit);
+/// assert_eq!(Command::try_from(2_u8).unwrap(), Command::Usage);
+/// assert_eq!(Command::name_of(2), Some("Usage"));
+/// ```
+/// Without this option, variants sharing a discriminant are rejected.
+///
+/// With `#[discrim(logical_repr = "...")]`, `#[discrim(hash_values = "fnv1a32")]` also assigns each variant
+/// without an explicit discriminant the 32-bit [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/index.html) hash of its name,
+/// following the `#[discrim(rename_all = "...")]` and `#[discrim(rename = "...")]` options.
+/// These discriminants do not change when variants are reordered or inserted,
+/// and colliding discriminants are rejected at compile time:
+/// ```
+/// use enum_discrim::{Discriminants, TryFrom};
+///
+/// #[derive(Debug, PartialEq, Eq, Discriminants, TryFrom)]
+/// #[discrim(logical_repr = "u32", hash_values = "fnv1a32")]
+/// enum Message {
+///     Ping,
+///     Data,
+///     Close = 7,
+/// }
+///
+/// assert_eq!(Message::Ping_D, 0x7FB7_F0A9);
+/// assert_eq!(Message::Close.discriminant(), 7);
+/// assert_eq!(Message::try_from(0x3F52_79C5).unwrap(), Message::Data);
+/// ```
+///
 /// ## Generated consts
 /// This macro generates one const item for each variant of your enum.
-/// Each const will take its related variant's name with a `_D` suffix.
+/// Each const will take its related variant's name with a `_D` suffix,
+/// unless another name is given with `#[discrim(const_name = "...")]` on the variant:
+/// ```
+/// use enum_discrim::Discriminants;
+///
+/// #[derive(Discriminants)]
+/// #[repr(u8)]
+/// enum E {
+///     #[discrim(const_name = "LEGACY_OK")]
+///     Ok,
+///     Error,
+/// }
+///
+/// assert_eq!(E::LEGACY_OK, 0);
+/// assert_eq!(E::Error_D, 1);
+/// ```
+/// The name of each const is spanned to its variant, and the doc comments of the variant are appended to its docs,
+/// so that IDEs navigate from the const to the variant and show the docs of the variant on hover.
+/// The names of the generated predicates, accessors and markers are also spanned to their variants,
+/// and the markers also get the doc comments of their variants.
+///
+/// With `#[discrim(next_free)]`, a `NEXT_FREE_DISCRIMINANT` const is also generated,
+/// holding the smallest value greater than the highest discriminant that is not within a reserved range,
+/// so that new variants can be given an unused discriminant.
+/// With `#[discrim(next_free = "gap")]`, it holds the smallest free value greater than the lowest discriminant instead:
+/// ```
+/// use enum_discrim::Discriminants;
+///
+/// #[derive(Discriminants)]
+/// #[discrim(next_free)]
+/// #[repr(u8)]
+/// enum Max {
+///     A = 1,
+///     B = 4,
+/// }
 ///
-/// ## Generated function
-/// In addition to the generated consts, a function with the following signature is generated:
+/// #[derive(Discriminants)]
+/// #[discrim(next_free = "gap", reserved = "2")]
+/// #[repr(u8)]
+/// enum Gap {
+///     A = 1,
+///     B = 4,
+/// }
+///
+/// assert_eq!(Max::NEXT_FREE_DISCRIMINANT, 5);
+/// assert_eq!(Gap::NEXT_FREE_DISCRIMINANT, 3);
+/// ```
+///
+/// ## Writing a manifest
+/// With the `manifest` feature, `#[discrim(manifest = "...")]` makes this macro write a JSON manifest of the enum
+/// to the given path, relative to the root of your crate, so that external code generators can read the authoritative discriminants.
+/// Without a path, `#[discrim(manifest)]` writes it to `OUT_DIR/<enum>.discrim.json`, which requires your crate to have a build script.
+/// For example, with `#[discrim(manifest = "target/kind.json", rename_all = "lowercase")]` on `#[repr(u8)] enum Kind { Ping = 1, Data }`:
+/// ```json
+/// {
+///     "name": "Kind",
+///     "repr": "u8",
+///     "variants": [
+///         { "ident": "Ping", "name": "ping", "value": 1 },
+///         { "ident": "Data", "name": "data", "value": 2 }
+///     ]
+/// }
+/// ```
+///
+/// ## Generated functions
+/// In addition to the generated consts, functions with the following signatures are generated:
 /// ```
 /// # #[allow(non_camel_case_types)]
 /// # type repr = ();
-/// # trait Discriminants {
+/// # struct E;
+/// # impl E {
 /// fn discriminant(&self) -> repr
-/// # ; }
+/// # { todo!() }
+/// const fn name_of(value: repr) -> Option<&'static str>
+/// # { todo!() }
+/// # }
 /// ```
 /// where `repr` is the [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) of your enum.
 ///
+/// `name_of` returns the name of the variant with the given discriminant, without constructing the enum.
+/// The name follows the `#[discrim(rename_all = "...")]` and `#[discrim(rename = "...")]` options.
+/// As it is a `const fn`, it may be called in const contexts:
+/// ```
+/// use enum_discrim::Discriminants;
+///
+/// #[derive(Discriminants)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+/// }
+///
+/// const NAME: Option<&str> = E::name_of(2);
+/// assert_eq!(NAME, Some("B"));
+/// assert_eq!(E::name_of(1), None);
+/// ```
+///
+/// ## Generated categories
+/// With `#[discrim(category = "...")]` on each variant, a `category` function is also generated,
+/// returning the given variant of another enum:
+/// ```
+/// use enum_discrim::Discriminants;
+///
+/// #[derive(Debug, PartialEq, Eq)]
+/// enum ErrorClass {
+///     Client,
+///     Server,
+/// }
+///
+/// #[derive(Discriminants)]
+/// #[repr(u16)]
+/// enum Status {
+///     #[discrim(category = "ErrorClass::Client")]
+///     NotFound = 404,
+///     #[discrim(category = "ErrorClass::Client")]
+///     Gone = 410,
+///     #[discrim(category = "ErrorClass::Server")]
+///     Internal = 500,
+/// }
+///
+/// assert_eq!(Status::Gone.category(), ErrorClass::Client);
+/// assert_eq!(Status::Internal.category(), ErrorClass::Server);
+/// ```
+/// The return type is the given path without its last segment, and must be the same for all variants.
+///
+/// ## Generated newtype
+/// With `#[discrim(newtype)]`, a transparent newtype wrapping the discriminant is also generated.
+/// It is named after the enum with a `Discriminant` suffix, unless another name is given with `#[discrim(newtype = "...")]`.
+///
+/// The newtype implements [`Display`](std::fmt::Display), `From<E>`, `From<&E>` and `Into<repr>`.
+/// If your enum also derives [`TryFrom`], `TryFrom<newtype>` is also implemented for your enum,
+/// accepting the same values as `TryFrom<repr>`.
+/// ```
+/// use enum_discrim::{Discriminants, TryFrom};
+///
+/// #[derive(Debug, PartialEq, Eq, Discriminants, TryFrom)]
+/// #[discrim(newtype)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// let tag = EDiscriminant::from(E::B);
+/// assert_eq!(tag, EDiscriminant(2));
+/// assert_eq!(tag.to_string(), "2");
+/// assert_eq!(E::try_from(EDiscriminant(3)).unwrap(), E::C);
+/// assert!(E::try_from(EDiscriminant(1)).is_err());
+/// ```
+///
+/// ## Generated kind enum
+/// With `#[discrim(kind)]`, a fieldless enum with a unit variant for each variant is also generated,
+/// e.g. to be stored in maps or matched on without the fields.
+/// It is named after the enum with a `Kind` suffix, unless another name is given with `#[discrim(kind = "...")]`.
+///
+/// The kind enum has the same discriminants as your enum, returned by its `discriminant` const function,
+/// and implements `From<E>` and `From<&E>`.
+/// ```
+/// use enum_discrim::Discriminants;
+///
+/// #[derive(Discriminants)]
+/// #[discrim(kind = "MessageKind")]
+/// #[repr(u8)]
+/// enum Message {
+///     Ping = 1,
+///     Data(Vec<u8>) = 2,
+///     Close { code: u16 } = 4,
+/// }
+///
+/// let kind = MessageKind::from(&Message::Data(vec![1, 2]));
+/// assert_eq!(kind, MessageKind::Data);
+/// assert_eq!(kind.discriminant(), 2);
+/// assert_eq!(MessageKind::Close as u8, 4);
+/// ```
+///
+/// ## Generated predicates
+/// With `#[discrim(predicates)]`, an `is_*` function is also generated for each variant,
+/// named after the variant in `snake_case`:
+/// ```
+/// use enum_discrim::Discriminants;
+///
+/// #[derive(Discriminants)]
+/// #[discrim(predicates)]
+/// #[repr(u8)]
+/// enum Message {
+///     Ping,
+///     DataChunk(Vec<u8>),
+///     Close { code: u16 },
+/// }
+///
+/// assert!(Message::Ping.is_ping());
+/// assert!(Message::DataChunk(vec![]).is_data_chunk());
+/// assert!(!Message::Close { code: 0 }.is_ping());
+/// ```
+///
+/// ## Generated accessors
+/// With `#[discrim(accessors)]`, `as_*` and `into_*` functions are also generated for each variant with fields,
+/// named after the variant in `snake_case`.
+/// They return the only field of the variant, or a tuple of all its fields:
+/// ```
+/// use enum_discrim::Discriminants;
+///
+/// #[derive(Discriminants)]
+/// #[discrim(accessors)]
+/// #[repr(u8)]
+/// enum Message {
+///     Ping,
+///     Data(Vec<u8>),
+///     Close { code: u16, reason: String },
+/// }
+///
+/// assert_eq!(Message::Data(vec![1, 2]).as_data(), Some(&vec![1, 2]));
+/// assert_eq!(Message::Ping.as_data(), None);
+/// assert_eq!(
+///     Message::Close { code: 1000, reason: "bye".to_owned() }.into_close(),
+///     Some((1000, "bye".to_owned())),
+/// );
+/// ```
+///
+/// ## Generated `Debug` impl block
+/// With `#[discrim(debug)]`, a [`Debug`](core::fmt::Debug) impl block is also generated, instead of deriving it.
+/// Each variant is rendered with its discriminant in hexadecimal, followed by its fields:
+/// ```
+/// use enum_discrim::Discriminants;
+///
+/// #[derive(Discriminants)]
+/// #[discrim(debug)]
+/// #[repr(u8)]
+/// enum Packet {
+///     Ping = 0x01,
+///     Data { len: u16 } = 0x2A,
+///     Ack(u32),
+/// }
+///
+/// assert_eq!(format!("{:?}", Packet::Ping), "Ping(=0x1)");
+/// assert_eq!(format!("{:?}", Packet::Data { len: 3 }), "Data(=0x2A) { len: 3 }");
+/// assert_eq!(format!("{:?}", Packet::Ack(7)), "Ack(=0x2B)(7)");
+/// ```
+///
+/// ## Generated markers
+/// With `#[discrim(markers)]`, a zero-sized marker type is also generated for each variant,
+/// named after the enum and the variant with a `Variant` suffix.
+/// Each marker has a `DISCRIMINANT` const and implements the [`VariantMarker`] trait,
+/// so that APIs can be keyed on a variant at compile time:
+/// ```
+/// use enum_discrim::{Discriminants, VariantMarker};
+///
+/// #[derive(Discriminants)]
+/// #[discrim(markers)]
+/// #[repr(u8)]
+/// enum Message {
+///     Ping = 1,
+///     Data(Vec<u8>) = 4,
+/// }
+///
+/// struct Channel<M: VariantMarker<Enum = Message, Repr = u8>>(std::marker::PhantomData<M>);
+/// impl<M: VariantMarker<Enum = Message, Repr = u8>> Channel<M> {
+///     fn tag(&self) -> u8 {
+///         M::DISCRIMINANT
+///     }
+/// }
+///
+/// assert_eq!(MessagePingVariant::DISCRIMINANT, 1);
+/// assert_eq!(Channel::<MessageDataVariant>(std::marker::PhantomData).tag(), 4);
+/// ```
+/// ```compile_fail
+/// use enum_discrim::Discriminants;
+///
+/// #[derive(Discriminants)]
+/// // COMPILE ERROR: markers are not supported on generic enums
+/// #[discrim(markers)]
+/// #[repr(u8)]
+/// enum E<T> {
+///     A,
+///     B(T),
+/// }
+/// ```
+///
 /// # Example
 /// Given the first code block in the [Usage](#usage) section,
 /// the macro would roughly generate the following code:
@@ -127,6 +553,15 @@ use std::{
 ///         # 0
 ///     }
 ///
+///     const fn name_of(value: u8) -> Option<&'static str> {
+///         match value {
+///             0 => Some("A"),
+///             2 => Some("B"),
+///             3 => Some("C"),
+///             _ => None,
+///         }
+///     }
+///
 ///     const A_D: u8 = 0;
 ///     const B_D: u8 = 2;
 ///     const C_D: u8 = 3;
@@ -192,53 +627,157 @@ pub use enum_discrim_proc::Discriminants;
 /// assert_eq!(u8::from(E::B), 2_u8);
 /// assert_eq!(u8::from(E::C), 3_u8);
 /// ```
+///
+/// ## Converting into narrower integers
+/// With `#[discrim(narrow = "...")]`, a `From<Self>` impl block is also generated for the given integer type,
+/// which may be narrower than the primitive representation. The option may be repeated.
+/// All discriminants *must* fit in the given type, which is checked at compile time:
+/// ```
+/// use enum_discrim::Into;
+///
+/// #[derive(Into)]
+/// #[discrim(narrow = "u8", narrow = "i16")]
+/// #[repr(u32)]
+/// enum Flag {
+///     Syn = 0x02,
+///     Ack = 0x10,
+/// }
+///
+/// assert_eq!(u32::from(Flag::Ack), 0x10_u32);
+/// assert_eq!(u8::from(Flag::Ack), 0x10_u8);
+/// assert_eq!(i16::from(Flag::Syn), 0x02_i16);
+/// ```
+/// ```compile_fail
+/// use enum_discrim::Into;
+///
+/// #[derive(Into)]
+/// #[discrim(narrow = "u8")]
+/// #[repr(u32)]
+/// enum E {
+///     A,
+///     // COMPILE ERROR: discriminant does not fit in `u8`
+///     B = 0x100,
+/// }
+/// ```
+///
+/// ## Converting into chars
+/// With `#[discrim(char)]` on a `#[repr(u8)]` or `#[repr(u32)]` enum, a `From<Self> for char` impl block is also generated.
+/// All discriminants *must* be valid Unicode scalar values, which is checked at compile time
+/// (every `u8` is, as it converts into a char in the Latin-1 range):
+/// ```
+/// use enum_discrim::Into;
+///
+/// #[derive(Into)]
+/// #[discrim(char)]
+/// #[repr(u8)]
+/// enum Command {
+///     Quit = 0x71,
+///     Help = 0x3F,
+/// }
+///
+/// assert_eq!(char::from(Command::Quit), 'q');
+/// assert_eq!(char::from(Command::Help), '?');
+/// ```
+/// ```compile_fail
+/// use enum_discrim::Into;
+///
+/// #[derive(Into)]
+/// #[discrim(char)]
+/// #[repr(u32)]
+/// enum E {
+///     A,
+///     // COMPILE ERROR: surrogate code point
+///     B = 0xD800,
+/// }
+/// ```
+///
+/// ## Converting into C integers
+/// With `#[discrim(c_int)]` or `#[discrim(c_uint)]`, a `From<Self> for c_int` or `From<Self> for c_uint` impl block is also generated,
+/// whatever the primitive representation, so that variants can be passed to C functions without casts.
+/// All discriminants *must* fit in the C type, which is checked at compile time.
+/// As `c_int` and `c_uint` are aliases of `i32` and `u32` on mainstream targets, they cannot be combined with these representations:
+/// ```
+/// use enum_discrim::Into;
+/// use std::ffi::c_int;
+///
+/// #[derive(Clone, Copy, Into)]
+/// #[discrim(c_int)]
+/// #[repr(u8)]
+/// enum Mode {
+///     Read = 1,
+///     Write = 2,
+/// }
+///
+/// extern "C" fn open(mode: c_int) -> c_int {
+///     mode
+/// }
+///
+/// assert_eq!(open(Mode::Write.into()), 2);
+/// ```
 pub use enum_discrim_proc::Into;
 
 /// Derives a [`TryFrom<repr>`] impl block
 ///
-/// # Usage
-/// You may use this macro on enums with *only* unit variants:
+/// [`TryFrom<&repr>`](TryFrom) and [`TryFrom<&mut repr>`](TryFrom) impl blocks are also generated,
+/// so that the enum can be converted from iterators over slices of the primitive representation.
+///
+/// A `try_from_any` function is also generated, converting from any type implementing [`TryInto<repr>`](TryInto)
+/// (e.g. any integer type), so that values of other widths need no cast:
 /// ```
 /// use enum_discrim::TryFrom;
 ///
-/// #[derive(TryFrom)]
+/// #[derive(Debug, PartialEq, Eq, TryFrom)]
 /// #[repr(u8)]
 /// enum E {
 ///     A,
 ///     B = 2,
-///     C,
 /// }
+///
+/// assert_eq!(E::try_from_any(2_i64).unwrap(), E::B);
+/// assert_eq!(E::try_from_any(0_usize).unwrap(), E::A);
+/// assert!(E::try_from_any(258_u32).is_err());
+/// assert!(E::try_from_any(-1_i32).is_err());
 /// ```
-/// ```compile_fail
-/// use enum_discrim::TryFrom;
 ///
-/// #[derive(TryFrom)]
+/// The [`kind`](TryFromError::kind) of the returned error tells why a value was rejected:
+/// ```
+/// use enum_discrim::{TryFrom, TryFromErrorKind};
+///
+/// #[derive(Debug, TryFrom)]
+/// #[discrim(reserved = "0x80..=0xFF")]
 /// #[repr(u8)]
-/// // COMPILE ERROR: Not all variants are unit
 /// enum E {
 ///     A,
-///     B(u8) = 2,
-///     C { n: usize },
+///     B = 2,
 /// }
+///
+/// assert_eq!(E::try_from(1).unwrap_err().kind(), TryFromErrorKind::Unknown);
+/// assert_eq!(E::try_from(0x90).unwrap_err().kind(), TryFromErrorKind::Reserved);
+/// assert_eq!(E::try_from_any(300_u16).unwrap_err().kind(), TryFromErrorKind::OutOfRange);
 /// ```
-/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
-/// ```compile_fail
-/// use enum_discrim::TryFrom;
 ///
-/// #[derive(TryFrom)]
-/// // COMPILE ERROR: missing #[repr]
+/// Its [`value`](TryFromError::value) accessor returns the rejected value, which is also shown by its [`Display`](std::fmt::Display) implementation,
+/// unless it was out of the range of the primitive representation:
+/// ```
+/// use enum_discrim::{RawValue, TryFrom};
+///
+/// #[derive(Debug, TryFrom)]
+/// #[repr(i8)]
 /// enum E {
 ///     A,
 ///     B = 2,
-///     C,
 /// }
+///
+/// assert_eq!(E::try_from(-3).unwrap_err().value(), Some(RawValue::Signed(-3)));
+/// assert_eq!(E::try_from_any(300_u16).unwrap_err().value(), None);
 /// ```
 ///
-/// # Example
+/// Its [`expected`](TryFromError::expected) accessor returns the values which would have been accepted,
+/// which are also listed by its [`Display`](std::fmt::Display) implementation:
 /// ```
-/// use enum_discrim::TryFrom;
+/// use enum_discrim::{RawValue, TryFrom};
 ///
-/// #[derive(Debug, PartialEq, Eq, TryFrom)]
+/// #[derive(Debug, TryFrom)]
 /// #[repr(u8)]
 /// enum E {
 ///     A,
@@ -246,73 +785,3613 @@ pub use enum_discrim_proc::Into;
 ///     C,
 /// }
 ///
-/// assert_eq!(E::try_from(0).unwrap(), E::A);
+/// let err = E::try_from(1).unwrap_err();
+/// assert_eq!(
+///     err.expected(),
+///     &[RawValue::Unsigned(0), RawValue::Unsigned(2), RawValue::Unsigned(3)]
+/// );
+/// assert!(err.to_string().ends_with("(got 1), expected one of 0, 2, 3"));
+/// ```
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::TryFrom;
+///
+/// #[derive(TryFrom)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// ```compile_fail
+/// use enum_discrim::TryFrom;
+///
+/// #[derive(TryFrom)]
+/// #[repr(u8)]
+/// // COMPILE ERROR: Not all variants are unit
+/// enum E {
+///     A,
+///     B(u8) = 2,
+///     C { n: usize },
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::TryFrom;
+///
+/// #[derive(TryFrom)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::TryFrom;
+///
+/// #[derive(Debug, PartialEq, Eq, TryFrom)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// assert_eq!(E::try_from(0).unwrap(), E::A);
 /// assert_eq!(E::try_from(2).unwrap(), E::B);
 /// assert_eq!(E::try_from(3).unwrap(), E::C);
 /// assert!(E::try_from(1).is_err());
+///
+/// let values = [0_u8, 3];
+/// let variants = values.iter().map(E::try_from).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(variants, [E::A, E::C]);
+/// ```
+///
+/// With [`TryFromReprExt`] in scope, integers can also be converted with a method call,
+/// which reads better in chains of conversions:
+/// ```
+/// use enum_discrim::{TryFrom, TryFromError, TryFromReprExt};
+///
+/// #[derive(Debug, PartialEq, Eq, TryFrom)]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Load = 0x2A,
+///     Store,
+/// }
+///
+/// fn parse(bytes: &[u8]) -> Result<Opcode, TryFromError> {
+///     bytes[0].to_enum::<Opcode>()
+/// }
+///
+/// assert_eq!(parse(&[0x2A]).unwrap(), Opcode::Load);
+/// assert!(parse(&[0x00]).is_err());
+/// ```
+///
+/// ## Observing invalid values
+/// With `#[discrim(on_unknown = "...")]`, the given function is called with the invalid value before the error is returned:
+/// ```
+/// use enum_discrim::TryFrom;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static UNKNOWN: AtomicUsize = AtomicUsize::new(0);
+/// fn count_unknown(_value: u8) {
+///     UNKNOWN.fetch_add(1, Ordering::Relaxed);
+/// }
+///
+/// #[derive(TryFrom)]
+/// #[discrim(on_unknown = "count_unknown")]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+/// }
+///
+/// assert!(E::try_from(1).is_err());
+/// assert!(E::try_from(2).is_ok());
+/// assert_eq!(UNKNOWN.load(Ordering::Relaxed), 1);
+/// ```
+///
+/// ## Using a custom error type
+/// With `#[discrim(error = "...")]`, the generated impl blocks return the given error type instead of [`TryFromError`].
+/// The errors are converted with the function given with `#[discrim(map_err = "...")]`,
+/// or with the `From<TryFromError>` implementation of the error type otherwise:
+/// ```
+/// use enum_discrim::{TryFrom, TryFromError};
+///
+/// #[derive(Debug, PartialEq, Eq)]
+/// enum ProtocolError {
+///     BadOpcode(&'static str),
+/// }
+/// impl From<TryFromError> for ProtocolError {
+///     fn from(err: TryFromError) -> Self {
+///         Self::BadOpcode(err.enum_name())
+///     }
+/// }
+///
+/// #[derive(Debug, PartialEq, Eq, TryFrom)]
+/// #[discrim(error = "ProtocolError")]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Nop,
+///     Load,
+/// }
+///
+/// assert_eq!(Opcode::try_from(2), Err(ProtocolError::BadOpcode("Opcode")));
+/// ```
+/// `#[discrim(try_from_f64)]` is not supported with a custom error type, as its error wraps a [`TryFromError`].
+///
+/// ## Using a distinct error type
+/// With `#[discrim(typed_error)]`, the generated impl blocks return a `TryFromError<Self>` instead of [`TryFromError`],
+/// so that errors may be converted with a `From` implementation targeting this enum.
+/// Combined with `#[discrim(error = "...")]`, the custom error type is converted from `TryFromError<Self>` as well:
+/// ```
+/// use enum_discrim::{TryFrom, TryFromError};
+///
+/// #[derive(Debug, PartialEq, Eq)]
+/// enum ProtocolError {
+///     BadOpcode,
+///     BadFlags,
+/// }
+/// impl From<TryFromError<Opcode>> for ProtocolError {
+///     fn from(_: TryFromError<Opcode>) -> Self {
+///         Self::BadOpcode
+///     }
+/// }
+/// impl From<TryFromError<Flags>> for ProtocolError {
+///     fn from(_: TryFromError<Flags>) -> Self {
+///         Self::BadFlags
+///     }
+/// }
+///
+/// #[derive(Debug, TryFrom)]
+/// #[discrim(typed_error)]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Nop,
+///     Load,
+/// }
+///
+/// #[derive(Debug, TryFrom)]
+/// #[discrim(typed_error)]
+/// #[repr(u8)]
+/// enum Flags {
+///     None,
+///     Ack,
+/// }
+///
+/// fn parse(opcode: u8, flags: u8) -> Result<(Opcode, Flags), ProtocolError> {
+///     Ok((Opcode::try_from(opcode)?, Flags::try_from(flags)?))
+/// }
+///
+/// assert_eq!(parse(2, 0).unwrap_err(), ProtocolError::BadOpcode);
+/// assert_eq!(parse(0, 2).unwrap_err(), ProtocolError::BadFlags);
+/// ```
+/// Such errors are converted into a [`TryFromError`] with their [`into_untyped`](TryFromError::into_untyped) method.
+///
+/// ## Reserving values
+/// With `#[discrim(reserved = "...")]`, the values within the given range are rejected with an error
+/// whose [`is_reserved`](TryFromError::is_reserved) accessor returns `true`,
+/// so that values set aside for future variants can be told apart from invalid ones.
+/// The range is written as a single integer, `start..=end` or `start..end`, and the option may be repeated:
+/// ```
+/// use enum_discrim::TryFrom;
+///
+/// #[derive(Debug, PartialEq, Eq, TryFrom)]
+/// #[discrim(reserved = "0xF0..=0xFF", reserved = "3")]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Nop,
+///     Load,
+///     Store,
+/// }
+///
+/// assert_eq!(Opcode::try_from(1).unwrap(), Opcode::Load);
+/// assert!(Opcode::try_from(0xF3).unwrap_err().is_reserved());
+/// assert!(Opcode::try_from(3).unwrap_err().is_reserved());
+/// assert!(!Opcode::try_from(4).unwrap_err().is_reserved());
+/// ```
+/// No variant may have a discriminant within a reserved range, including implicit discriminants:
+/// ```compile_fail
+/// use enum_discrim::TryFrom;
+///
+/// #[derive(TryFrom)]
+/// #[discrim(reserved = "2..4")]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B,
+///     // COMPILE ERROR: discriminant is within a reserved range
+///     C,
+/// }
 /// ```
+///
+/// ## Accepting aliases
+/// With `#[discrim(alias = ...)]` on a variant, the given value is also converted into this variant,
+/// e.g. for codes of legacy protocols.
+/// As with `#[discrim(value = ...)]`, aliases are written as integers or strings, and the option may be repeated.
+/// The actual discriminant is still the one used by [`Into`](macro@Into) and [`Discriminants`](macro@Discriminants):
+/// ```
+/// use enum_discrim::{Into, TryFrom};
+///
+/// #[derive(Debug, PartialEq, Eq, Into, TryFrom)]
+/// #[repr(u8)]
+/// enum Command {
+///     Quit = 1,
+///     #[discrim(alias = 7, alias = 9)]
+///     Help = 2,
+/// }
+///
+/// assert_eq!(Command::try_from(7).unwrap(), Command::Help);
+/// assert_eq!(Command::try_from(9).unwrap(), Command::Help);
+/// assert_eq!(u8::from(Command::Help), 2);
+/// ```
+/// Aliases may neither be reserved nor be the discriminant or another alias of any variant:
+/// ```compile_fail
+/// use enum_discrim::TryFrom;
+///
+/// #[derive(TryFrom)]
+/// #[repr(u8)]
+/// enum Command {
+///     Quit = 1,
+///     // COMPILE ERROR: alias 1 is already the discriminant or an alias of a variant
+///     #[discrim(alias = 1)]
+///     Help = 2,
+/// }
+/// ```
+///
+/// ## Falling back to a default variant
+/// With `#[discrim(default)]` on a variant, all the values which are not the discriminant of another variant
+/// are converted into this one, including the reserved ones.
+/// As the conversion cannot fail, `From<repr>` is then implemented instead of `TryFrom<repr>`:
+/// ```
+/// use enum_discrim::TryFrom;
+///
+/// #[derive(Debug, PartialEq, Eq, TryFrom)]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Nop,
+///     Load,
+///     #[discrim(default)]
+///     Unknown = 0xFF,
+/// }
+///
+/// assert_eq!(Opcode::from(1), Opcode::Load);
+/// assert_eq!(Opcode::from(0x42), Opcode::Unknown);
+/// ```
+///
+/// ## Skipping variants
+/// With `#[discrim(skip)]`, a variant is never converted from its discriminant, e.g. for internal sentinel variants.
+/// Its discriminant is rejected with an error of kind [`Skipped`](TryFromErrorKind::Skipped),
+/// and [`Discriminants`](macro@Discriminants) does not generate a const for it.
+/// Skipped variants still take a discriminant, so the following variants are numbered as usual,
+/// and [`Into`](macro@Into) still converts them, as it cannot fail:
+/// ```
+/// use enum_discrim::{Into, TryFrom, TryFromErrorKind};
+///
+/// #[derive(Debug, PartialEq, Eq, Into, TryFrom)]
+/// #[repr(u8)]
+/// enum State {
+///     Idle,
+///     #[discrim(skip)]
+///     Uninitialized,
+///     Running,
+/// }
+///
+/// assert_eq!(State::try_from(2).unwrap(), State::Running);
+/// assert_eq!(State::try_from(1).unwrap_err().kind(), TryFromErrorKind::Skipped);
+/// assert_eq!(u8::from(State::Uninitialized), 1);
+/// ```
+///
+/// ## Choosing the lookup strategy
+/// With `#[discrim(lookup = "...")]`, the strategy used to find the variant of a value can be forced:
+/// - `"match"` (default) matches the value against each discriminant;
+/// - `"range"` checks the value against the bounds of the discriminants, which *must* be contiguous;
+/// - `"table"` indexes a static table of booleans spanning from the lowest to the highest discriminant;
+/// - `"binary_search"` searches the value in a static sorted array of the discriminants.
+///
+/// The `"table"` and `"binary_search"` strategies call into the functions of the [`tables`] module,
+/// which are shared by all enums with the same primitive representation,
+/// reducing the size of binaries deriving many enums.
+///
+/// The strategies other than `"match"` convert valid values with a transmutation,
+/// so they require a `#[repr(inttype)]` attribute, and are not supported with `#[discrim(logical_repr = "...")]`:
+/// ```
+/// use enum_discrim::TryFrom;
+///
+/// #[derive(Debug, PartialEq, Eq, TryFrom)]
+/// #[discrim(lookup = "table")]
+/// #[repr(u8)]
+/// enum E {
+///     A = 0x10,
+///     B = 0x12,
+///     C = 0x1F,
+/// }
+///
+/// assert_eq!(E::try_from(0x12).unwrap(), E::B);
+/// assert!(E::try_from(0x11).is_err());
+/// assert!(E::try_from(0xFF).is_err());
+/// ```
+/// ```compile_fail
+/// use enum_discrim::TryFrom;
+///
+/// #[derive(TryFrom)]
+/// #[discrim(lookup = "range")]
+/// #[repr(u8)]
+/// // COMPILE ERROR: discriminants are not contiguous
+/// enum E {
+///     A,
+///     B = 2,
+/// }
+/// ```
+///
+/// ## Converting in constant time
+/// With `#[discrim(const_time)]`, the value is compared with every discriminant without branching,
+/// so that the conversion time of valid values does not depend on the value,
+/// e.g. when parsing identifiers of cryptographic algorithms.
+/// Only this lookup is constant-time: the conversion then branches on whether the value is valid,
+/// and invalid values are checked against the aliases and the reserved and skipped values with ordinary comparisons.
+/// The outcome of the conversion (valid, alias, reserved or unknown) may thus be observed through timing.
+/// As with `#[discrim(lookup = "...")]`, a `#[repr(inttype)]` attribute is required:
+/// ```
+/// use enum_discrim::TryFrom;
+///
+/// #[derive(Debug, PartialEq, Eq, TryFrom)]
+/// #[discrim(const_time)]
+/// #[repr(u16)]
+/// enum CipherSuite {
+///     Aes128GcmSha256 = 0x1301,
+///     Aes256GcmSha384 = 0x1302,
+///     ChaCha20Poly1305Sha256 = 0x1303,
+/// }
+///
+/// assert_eq!(CipherSuite::try_from(0x1302).unwrap(), CipherSuite::Aes256GcmSha384);
+/// assert!(CipherSuite::try_from(0x1304).is_err());
+/// ```
+///
+/// ## Converting from floats
+/// With `#[discrim(try_from_f64)]`, a `TryFrom<f64>` impl block is also generated.
+/// The number must be an integer within the range of the primitive representation,
+/// otherwise a [`TryFromFloatError`] is returned:
+/// ```
+/// use enum_discrim::{TryFrom, TryFromFloatError};
+///
+/// #[derive(Debug, PartialEq, Eq, TryFrom)]
+/// #[discrim(try_from_f64)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+/// }
+///
+/// assert_eq!(E::try_from(2.0).unwrap(), E::B);
+/// assert!(matches!(E::try_from(2.5), Err(TryFromFloatError::NotIntegral)));
+/// assert!(matches!(E::try_from(1.0), Err(TryFromFloatError::Invalid(_))));
+/// assert!(matches!(E::try_from(256.0), Err(TryFromFloatError::Invalid(_))));
+/// ```
+///
+/// ## Converting from chars
+/// With `#[discrim(char)]` on a `#[repr(u8)]` or `#[repr(u32)]` enum, a `TryFrom<char>` impl block is also generated,
+/// converting the Unicode scalar value of the char.
+/// Chars out of the range of the primitive representation are rejected with an out-of-range [`TryFromError`]:
+/// ```
+/// use enum_discrim::{TryFrom, TryFromErrorKind};
+///
+/// #[derive(Debug, PartialEq, Eq, TryFrom)]
+/// #[discrim(char)]
+/// #[repr(u8)]
+/// enum Command {
+///     Quit = 0x71,
+///     Help = 0x3F,
+/// }
+///
+/// assert_eq!(Command::try_from('q').unwrap(), Command::Quit);
+/// assert_eq!(Command::try_from('x').unwrap_err().kind(), TryFromErrorKind::Unknown);
+/// assert_eq!(Command::try_from('é').unwrap_err().kind(), TryFromErrorKind::Unknown);
+/// assert_eq!(Command::try_from('€').unwrap_err().kind(), TryFromErrorKind::OutOfRange);
+/// ```
+///
+/// ## Converting from C integers
+/// With `#[discrim(c_int)]` or `#[discrim(c_uint)]`, a `TryFrom<c_int>` or `TryFrom<c_uint>` impl block is also generated.
+/// Values out of the range of the primitive representation are rejected with an out-of-range [`TryFromError`]:
+/// ```
+/// use enum_discrim::{TryFrom, TryFromErrorKind};
+/// use std::ffi::c_int;
+///
+/// #[derive(Debug, PartialEq, Eq, TryFrom)]
+/// #[discrim(c_int)]
+/// #[repr(u8)]
+/// enum Mode {
+///     Read = 1,
+///     Write = 2,
+/// }
+///
+/// assert_eq!(Mode::try_from(2 as c_int).unwrap(), Mode::Write);
+/// assert_eq!(Mode::try_from(3 as c_int).unwrap_err().kind(), TryFromErrorKind::Unknown);
+/// assert_eq!(Mode::try_from(-1 as c_int).unwrap_err().kind(), TryFromErrorKind::OutOfRange);
+/// ```
+///
+/// ## Converting from the discriminant newtype
+/// With `#[discrim(newtype)]`, a `TryFrom<newtype>` impl block converting the newtype generated by [`Discriminants`](macro@Discriminants)
+/// is also generated, accepting the same values as `TryFrom<repr>` (or a `From<newtype>` impl block if a variant is the default one):
+/// ```
+/// use enum_discrim::{Discriminants, TryFrom};
+///
+/// #[derive(Debug, PartialEq, Eq, Discriminants, TryFrom)]
+/// #[discrim(newtype)]
+/// #[repr(u8)]
+/// enum Mode {
+///     Read = 1,
+///     #[discrim(alias = 3)]
+///     Write = 2,
+/// }
+///
+/// assert_eq!(Mode::try_from(ModeDiscriminant(3)).unwrap(), Mode::Write);
+/// assert!(Mode::try_from(ModeDiscriminant(4)).is_err());
+/// ```
+///
+/// ## Proving conversions
+/// With `#[discrim(kani_proofs)]`, a `#[cfg(kani)]` [Kani](https://model-checking.github.io/kani/) proof harness is also generated.
+/// The harness proves that every value of the primitive representation is either rejected,
+/// or converted into a variant that converts back into the same value, so the enum *must* also derive [`Into`](macro@Into):
+/// ```
+/// use enum_discrim::{Into, TryFrom};
+///
+/// #[derive(Into, TryFrom)]
+/// #[discrim(kani_proofs)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+/// }
+/// ```
+/// The harness is named after the enum with a `_round_trip` suffix,
+/// so it can be run with `cargo kani --harness e_round_trip`.
+/// You may need to declare the `kani` configuration in the `[lints.rust]` section of your manifest
+/// to silence the `unexpected_cfgs` lint.
 pub use enum_discrim_proc::TryFrom;
 
-/// Error returned by [`TryFrom`](crate::TryFrom) implementations
-#[derive(Debug, Clone, Copy)]
-pub struct TryFromError {
-	/// Enum identifier
-	ident: &'static str,
-}
-impl TryFromError {
-	#[doc(hidden)]
-	#[inline]
-	pub const fn new(ident: &'static str) -> Self {
-		Self { ident }
+/// Derives a [`FromStr`](std::str::FromStr) impl block
+///
+/// The accepted strings are the names of the variants,
+/// following the `#[discrim(rename_all = "...")]` and `#[discrim(rename = "...")]` options.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::FromStr;
+///
+/// #[derive(FromStr)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// ```compile_fail
+/// use enum_discrim::FromStr;
+///
+/// #[derive(FromStr)]
+/// // COMPILE ERROR: Not all variants are unit
+/// enum E {
+///     A,
+///     B(u8),
+///     C { n: usize },
+/// }
+/// ```
+///
+/// ## Case-insensitive parsing
+/// With `#[discrim(ascii_case_insensitive)]`, the names are compared ignoring ASCII case:
+/// ```
+/// use enum_discrim::FromStr;
+///
+/// #[derive(Debug, PartialEq, Eq, FromStr)]
+/// #[discrim(ascii_case_insensitive)]
+/// enum Level {
+///     Error,
+///     Warning,
+/// }
+///
+/// assert_eq!("error".parse::<Level>().unwrap(), Level::Error);
+/// assert_eq!("ERROR".parse::<Level>().unwrap(), Level::Error);
+/// assert_eq!("Warning".parse::<Level>().unwrap(), Level::Warning);
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::FromStr;
+///
+/// #[derive(Debug, PartialEq, Eq, FromStr)]
+/// #[discrim(rename_all = "kebab-case")]
+/// enum E {
+///     NotFound,
+///     #[discrim(rename = "gone")]
+///     NoLongerAvailable,
+/// }
+///
+/// assert_eq!("not-found".parse::<E>().unwrap(), E::NotFound);
+/// assert_eq!("gone".parse::<E>().unwrap(), E::NoLongerAvailable);
+/// assert!("NotFound".parse::<E>().is_err());
+/// ```
+pub use enum_discrim_proc::FromStr;
+
+/// Derives [`From<Self>`](From) and [`From<&Self>`](From) impl blocks for `&'static str`
+///
+/// The returned strings are the names of the variants,
+/// following the `#[discrim(rename_all = "...")]` and `#[discrim(rename = "...")]` options,
+/// so that the enum is accepted by APIs generic over `Into<&'static str>`.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::IntoStaticStr;
+///
+/// #[derive(IntoStaticStr)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// ```compile_fail
+/// use enum_discrim::IntoStaticStr;
+///
+/// #[derive(IntoStaticStr)]
+/// // COMPILE ERROR: Not all variants are unit
+/// enum E {
+///     A,
+///     B(u8),
+///     C { n: usize },
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::IntoStaticStr;
+///
+/// #[derive(IntoStaticStr)]
+/// #[discrim(rename_all = "kebab-case")]
+/// enum E {
+///     NotFound,
+///     #[discrim(rename = "gone")]
+///     NoLongerAvailable,
+/// }
+///
+/// fn label(value: impl Into<&'static str>) -> &'static str {
+///     value.into()
+/// }
+///
+/// assert_eq!(label(E::NotFound), "not-found");
+/// assert_eq!(<&str>::from(&E::NoLongerAvailable), "gone");
+/// ```
+pub use enum_discrim_proc::IntoStaticStr;
+
+/// Rewrites an enum according to the given arguments
+///
+/// # Usage
+/// You may use this attribute macro on enums with a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations),
+/// alongside any derive macro of this crate.
+///
+/// ## Documenting discriminants
+/// With `#[enum_discrim(doc_values)]`, the discriminant of each variant is appended to its documentation
+/// (as `Discriminant: 0x2A`), so that the final values are visible in rustdoc:
+/// ```
+/// use enum_discrim::{enum_discrim, Discriminants};
+///
+/// #[enum_discrim(doc_values)]
+/// #[derive(Discriminants)]
+/// #[repr(u8)]
+/// enum E {
+///     /// First variant
+///     A,
+///     B = 0x2A,
+///     C,
+/// }
+///
+/// assert_eq!(E::C_D, 0x2B);
+/// ```
+/// ```compile_fail
+/// use enum_discrim::enum_discrim;
+///
+/// #[enum_discrim(doc_values)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+pub use enum_discrim_proc::enum_discrim;
+
+/// Derives a visitor trait for the enum, and an `accept` function dispatching to it
+///
+/// The trait is named after the enum with a `Visitor` suffix, and declares a `visit_*` method for each variant
+/// (named after the variant in `snake_case`), taking references to its fields.
+/// Adding a variant to the enum thus forces all visitors to handle it.
+///
+/// # Usage
+/// You may use this macro on any enum:
+/// ```
+/// use enum_discrim::Visitor;
+///
+/// #[derive(Visitor)]
+/// enum E<T> {
+///     A,
+///     B(T, u8),
+///     C { n: usize },
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::Visitor;
+///
+/// #[derive(Visitor)]
+/// enum Shape {
+///     Point,
+///     Circle(f64),
+///     Rect { width: f64, height: f64 },
+/// }
+///
+/// struct Area(f64);
+/// impl ShapeVisitor for Area {
+///     fn visit_point(&mut self) {}
+///     fn visit_circle(&mut self, radius: &f64) {
+///         self.0 += std::f64::consts::PI * radius * radius;
+///     }
+///     fn visit_rect(&mut self, width: &f64, height: &f64) {
+///         self.0 += width * height;
+///     }
+/// }
+///
+/// let mut area = Area(0.0);
+/// Shape::Rect { width: 2.0, height: 3.0 }.accept(&mut area);
+/// Shape::Point.accept(&mut area);
+/// assert_eq!(area.0, 6.0);
+/// ```
+pub use enum_discrim_proc::Visitor;
+
+/// Derives an impl [`Ordinal`](trait@Ordinal) block
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::Ordinal;
+///
+/// #[derive(Ordinal)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// ```compile_fail
+/// use enum_discrim::Ordinal;
+///
+/// #[derive(Ordinal)]
+/// // COMPILE ERROR: Not all variants are unit
+/// enum E {
+///     A,
+///     B(u8),
+///     C { n: usize },
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::Ordinal;
+///
+/// #[derive(Debug, PartialEq, Eq, Ordinal)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// assert_eq!(E::COUNT, 3);
+/// assert_eq!(E::B.ordinal(), 1);
+/// assert_eq!(E::from_ordinal(2), Some(E::C));
+/// assert_eq!(E::from_ordinal(3), None);
+/// ```
+pub use enum_discrim_proc::Ordinal;
+
+/// Derives [`PartialEq<repr>`] and [`PartialOrd<repr>`] impl blocks, and their reverse
+///
+/// This allows comparing variants with raw values, e.g. thresholds coming from a configuration.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::ReprCmp;
+///
+/// #[derive(ReprCmp)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::ReprCmp;
+///
+/// #[derive(ReprCmp)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::ReprCmp;
+///
+/// #[derive(ReprCmp)]
+/// #[repr(u8)]
+/// enum Level {
+///     Debug,
+///     Info,
+///     Warn = 3,
+///     Error,
+/// }
+///
+/// let threshold = 3_u8;
+/// assert!(Level::Error >= threshold);
+/// assert!(Level::Info < threshold);
+/// assert!(threshold == Level::Warn);
+/// ```
+pub use enum_discrim_proc::ReprCmp;
+
+/// Derives an impl [`Step`](core::iter::Step) block, so that ranges of variants can be iterated
+///
+/// This derive macro requires a nightly compiler, and the `step_trait` feature to be enabled in your crate.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants, whose discriminants are contiguous
+/// and increase in declaration order. The enum *must* also implement [`Clone`] and [`PartialOrd`]:
+/// ```ignore
+/// #![feature(step_trait)]
+///
+/// use enum_discrim::Step;
+///
+/// #[derive(Clone, PartialEq, PartialOrd, Step)]
+/// #[repr(u8)]
+/// enum E {
+///     A = 1,
+///     B,
+///     C,
+/// }
+/// ```
+/// ```compile_fail
+/// #![feature(step_trait)]
+///
+/// use enum_discrim::Step;
+///
+/// #[derive(Clone, PartialEq, PartialOrd, Step)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     // COMPILE ERROR: discriminants are not contiguous
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```ignore
+/// #![feature(step_trait)]
+///
+/// use enum_discrim::Step;
+///
+/// #[derive(Debug, Clone, PartialEq, PartialOrd, Step)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B,
+///     C,
+///     D,
+/// }
+///
+/// assert_eq!((E::A..=E::C).collect::<Vec<_>>(), [E::A, E::B, E::C]);
+/// assert_eq!((E::B..).collect::<Vec<_>>(), [E::B, E::C, E::D]);
+/// ```
+#[cfg(feature = "nightly")]
+pub use enum_discrim_proc::Step;
+
+/// Derives an impl block for the [`Describe`](trait@Describe) trait, exposing static metadata of the enum
+///
+/// The metadata contains the name of the enum, its primitive representation,
+/// and the name and discriminant of each variant, so that generic code
+/// (e.g. pretty-printers or schema exporters) can handle any enum deriving this macro.
+///
+/// # Usage
+/// You may use this macro on enums with a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations):
+/// ```
+/// use enum_discrim::Describe;
+///
+/// #[derive(Describe)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B(u8) = 2,
+///     C { n: usize },
+/// }
+/// ```
+/// ```compile_fail
+/// use enum_discrim::Describe;
+///
+/// #[derive(Describe)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::Describe;
+///
+/// #[derive(Describe)]
+/// #[repr(i16)]
+/// enum E {
+///     A = -1,
+///     B = 2,
+///     C,
+/// }
+///
+/// fn describe<T: Describe>() -> String {
+///     let info = T::enum_info();
+///     let variants = info
+///         .variants()
+///         .iter()
+///         .map(|variant| format!("{} = {}", variant.name(), variant.value()))
+///         .collect::<Vec<_>>();
+///     format!("{}: {} {{ {} }}", info.name(), info.repr(), variants.join(", "))
+/// }
+///
+/// assert_eq!(describe::<E>(), "E: i16 { A = -1, B = 2, C = 3 }");
+/// assert_eq!(E::enum_info().name_of(2), Some("B"));
+/// ```
+pub use enum_discrim_proc::Describe;
+
+/// Derives an impl [`CEnum`](c_header::CEnum) block, used by [`c_header`](c_header::c_header) to render C declarations
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants and a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations)
+/// other than `u128` and `i128`:
+/// ```
+/// use enum_discrim::CHeader;
+///
+/// #[derive(CHeader)]
+/// #[repr(i32)]
+/// enum E {
+///     A = -1,
+///     B,
+/// }
+/// ```
+/// ```compile_fail
+/// use enum_discrim::CHeader;
+///
+/// #[derive(CHeader)]
+/// // COMPILE ERROR: `u128` has no equivalent C type
+/// #[repr(u128)]
+/// enum E {
+///     A,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::{c_header::c_header, CHeader};
+///
+/// #[derive(CHeader)]
+/// #[repr(i32)]
+/// enum E {
+///     A = -1,
+///     B,
+/// }
+///
+/// assert_eq!(
+///     c_header::<E>(),
+///     "typedef int32_t E;\n#define E_A INT32_C(-1)\n#define E_B INT32_C(0)\n",
+/// );
+/// ```
+pub use enum_discrim_proc::CHeader;
+
+/// Derives an impl block containing functions to encode and decode the discriminant as a [LEB128](https://en.wikipedia.org/wiki/LEB128) varint
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants and an unsigned [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations)
+/// of at most 64 bits:
+/// ```
+/// use enum_discrim::Varint;
+///
+/// #[derive(Varint)]
+/// #[repr(u32)]
+/// enum E {
+///     A,
+///     B = 300,
+/// }
+/// ```
+/// ```compile_fail
+/// use enum_discrim::Varint;
+///
+/// #[derive(Varint)]
+/// // COMPILE ERROR: signed representation
+/// #[repr(i32)]
+/// enum E {
+///     A,
+///     B = 300,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::{varint, Varint};
+///
+/// #[derive(Debug, PartialEq, Eq, Varint)]
+/// #[repr(u32)]
+/// enum E {
+///     A,
+///     B = 300,
+/// }
+///
+/// let mut buf = [0; varint::MAX_LEN];
+/// let len = E::B.encode_varint(&mut buf);
+/// assert_eq!(buf[..len], [0xAC, 0x02]);
+/// assert_eq!(E::decode_varint(&buf[..len]).unwrap(), (E::B, 2));
+/// assert!(matches!(E::decode_varint(&[0x01]), Err(varint::VarintError::Invalid(_))));
+/// ```
+pub use enum_discrim_proc::Varint;
+
+/// Derives [`TryFrom<repr>`] and [`From<Self>`](From) impl blocks for a newtype wrapping a primitive integer,
+/// accepting only the values within the given ranges
+///
+/// This macro is meant for protocol codes whose value space is too large to be declared as an enum.
+/// The accepted ranges *must* be given with `#[discrim(valid = "...")]`, written as a single integer, `start..=end` or `start..end`,
+/// and the option may be repeated. Named values can be given with `#[discrim(consts(NAME = value, ...))]`,
+/// which generates a const for each of them, checked at compile time to be within the accepted ranges.
+/// A `get` const function returning the wrapped value is also generated.
+///
+/// # Usage
+/// You may use this macro on tuple structs with a single primitive integer field:
+/// ```
+/// use enum_discrim::Newtype;
+///
+/// #[derive(Newtype)]
+/// #[discrim(valid = "1..=65535")]
+/// struct Port(u16);
+/// ```
+/// ```compile_fail
+/// use enum_discrim::Newtype;
+///
+/// #[derive(Newtype)]
+/// #[discrim(valid = "1..=65535", consts(RESERVED = 0))]
+/// // COMPILE ERROR: RESERVED is not within the valid ranges
+/// struct Port(u16);
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::Newtype;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Newtype)]
+/// #[discrim(valid = "100..=103", valid = "200..=226", valid = "300..400")]
+/// #[discrim(consts(OK = 200, NOT_MODIFIED = 304))]
+/// struct StatusCode(u16);
+///
+/// assert_eq!(StatusCode::try_from(200).unwrap(), StatusCode::OK);
+/// assert_eq!(u16::from(StatusCode::NOT_MODIFIED), 304);
+/// assert_eq!(StatusCode::try_from(399).unwrap().get(), 399);
+/// assert!(StatusCode::try_from(404).is_err());
+/// ```
+pub use enum_discrim_proc::Newtype;
+
+/// Derives an impl block containing a `BITS` const, and `from_bits` and `into_bits` const functions
+///
+/// The number of bits *must* be declared with `#[discrim(bits = N)]`, and all discriminants *must* fit in it.
+/// The bits are exchanged as the smallest unsigned integer type holding `N` bits,
+/// so that the enum can be used as a field of a [`bitfield-struct`](https://docs.rs/bitfield-struct) struct.
+/// `from_bits` panics if the bits are not the discriminant of any variant.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::Bits;
+///
+/// #[derive(Bits)]
+/// #[discrim(bits = 2)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// ```compile_fail
+/// use enum_discrim::Bits;
+///
+/// #[derive(Bits)]
+/// #[discrim(bits = 2)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     // COMPILE ERROR: discriminant does not fit in 2 bits
+///     B = 4,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::Bits;
+///
+/// #[derive(Debug, PartialEq, Eq, Bits)]
+/// #[discrim(bits = 3)]
+/// #[repr(u16)]
+/// enum Kind {
+///     Data,
+///     Ack = 5,
+/// }
+///
+/// assert_eq!(Kind::BITS, 3);
+/// assert_eq!(Kind::Ack.into_bits(), 5_u8);
+/// assert_eq!(Kind::from_bits(0), Kind::Data);
+/// ```
+pub use enum_discrim_proc::Bits;
+
+/// Derives impl blocks for the `InputType` and `OutputType` traits of [`async_graphql`]
+///
+/// The enum is exposed as a GraphQL enum type named after the Rust enum,
+/// whose values are the names of the variants.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::GraphQLEnum;
+///
+/// #[derive(GraphQLEnum)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// The names of the variants follow `#[discrim(rename_all = "...")]` and `#[discrim(rename = "...")]`:
+/// ```
+/// use async_graphql::{InputType, Name, Value};
+/// use enum_discrim::GraphQLEnum;
+///
+/// #[derive(GraphQLEnum)]
+/// #[discrim(rename_all = "SCREAMING_SNAKE_CASE")]
+/// enum E {
+///     NotFound,
+///     #[discrim(rename = "GONE")]
+///     NoLongerAvailable,
+/// }
+///
+/// assert_eq!(E::NotFound.to_value(), Value::Enum(Name::new("NOT_FOUND")));
+/// assert_eq!(E::NoLongerAvailable.to_value(), Value::Enum(Name::new("GONE")));
+/// ```
+///
+/// # Example
+/// ```
+/// use async_graphql::{InputType, Name, Value};
+/// use enum_discrim::GraphQLEnum;
+///
+/// #[derive(Debug, PartialEq, Eq, GraphQLEnum)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// assert_eq!(E::B.to_value(), Value::Enum(Name::new("B")));
+/// assert_eq!(E::parse(Some(Value::Enum(Name::new("C")))).unwrap(), E::C);
+/// assert!(E::parse(Some(Value::Enum(Name::new("D")))).is_err());
+/// ```
+#[cfg(feature = "async-graphql")]
+pub use enum_discrim_proc::GraphQLEnum;
+
+/// Derives impl blocks for the enum reflection traits of [`bevy_reflect`]
+///
+/// The generated impl blocks are the same as what `bevy_reflect`'s own derive macro would generate,
+/// i.e. `Reflect`, `Enum`, `FromReflect`, `Typed`, `TypePath` and `GetTypeRegistration`.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::Reflect;
+///
+/// #[derive(Reflect)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use bevy_reflect::{Enum, FromReflect, Reflect as _};
+/// use enum_discrim::Reflect;
+///
+/// #[derive(Debug, PartialEq, Eq, Reflect)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// assert_eq!(E::B.variant_name(), "B");
+/// assert_eq!(E::C.variant_index(), 2);
+/// let mut value = E::A;
+/// value.apply(&E::C);
+/// assert_eq!(value, E::C);
+/// assert_eq!(E::from_reflect(&E::B), Some(E::B));
+/// ```
+#[cfg(feature = "bevy_reflect")]
+pub use enum_discrim_proc::Reflect;
+
+/// Derives a type of helper functions to (de)serialize the enum as its discriminant with [`serde`]
+///
+/// The generated type is named after the enum in `snake_case`, with an `_as_repr` suffix,
+/// and has the same visibility as the enum.
+/// Its `serialize` and `deserialize` associated functions are found by `#[serde(with = "...")]` just like those of a module,
+/// even when the enum is declared inside a function.
+/// It is meant to be used with `#[serde(with = "...")]` on individual fields,
+/// without changing how the enum itself is (de)serialized.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::SerdeRepr;
+///
+/// #[derive(SerdeRepr)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::SerdeRepr;
+///
+/// #[derive(SerdeRepr)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::SerdeRepr;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Eq, SerdeRepr)]
+/// #[repr(u8)]
+/// enum MyEnum {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// struct S {
+///     #[serde(with = "my_enum_as_repr")]
+///     e: MyEnum,
+/// }
+///
+/// assert_eq!(serde_json::to_string(&S { e: MyEnum::B }).unwrap(), r#"{"e":2}"#);
+/// assert_eq!(serde_json::from_str::<S>(r#"{"e":3}"#).unwrap(), S { e: MyEnum::C });
+/// assert!(serde_json::from_str::<S>(r#"{"e":1}"#).is_err());
+/// ```
+/// The discriminants are deserialized like [`TryFrom`](macro@TryFrom) converts them:
+/// the values given with `#[discrim(alias = ...)]` are accepted as well,
+/// and other values are deserialized into the `#[discrim(default)]` variant, if any.
+/// ```
+/// use enum_discrim::SerdeRepr;
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, PartialEq, Eq, SerdeRepr)]
+/// #[repr(u8)]
+/// enum MyEnum {
+///     #[discrim(alias = 10)]
+///     A = 1,
+///     #[discrim(default)]
+///     Unknown = 0,
+/// }
+///
+/// #[derive(Debug, PartialEq, Eq, Deserialize)]
+/// struct S {
+///     #[serde(with = "my_enum_as_repr")]
+///     e: MyEnum,
+/// }
+///
+/// assert_eq!(serde_json::from_str::<S>(r#"{"e":10}"#).unwrap(), S { e: MyEnum::A });
+/// assert_eq!(serde_json::from_str::<S>(r#"{"e":3}"#).unwrap(), S { e: MyEnum::Unknown });
+/// ```
+///
+/// ## Representing as names
+/// With `#[discrim(serde = "name")]`, the enum is represented as the name of its variant instead (`#[discrim(serde = "value")]` being the default).
+/// The generated type then has an `_as_name` suffix, and the names follow the `#[discrim(rename_all = "...")]` and `#[discrim(rename = "...")]` options.
+/// In this case, the primitive representation is not needed:
+/// ```
+/// use enum_discrim::SerdeRepr;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Eq, SerdeRepr)]
+/// #[discrim(serde = "name", rename_all = "snake_case")]
+/// enum MyEnum {
+///     NotFound,
+///     Gone,
+/// }
+///
+/// #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// struct S {
+///     #[serde(with = "my_enum_as_name")]
+///     e: MyEnum,
+/// }
+///
+/// assert_eq!(serde_json::to_string(&S { e: MyEnum::NotFound }).unwrap(), r#"{"e":"not_found"}"#);
+/// assert_eq!(serde_json::from_str::<S>(r#"{"e":"gone"}"#).unwrap(), S { e: MyEnum::Gone });
+/// assert!(serde_json::from_str::<S>(r#"{"e":"Gone"}"#).is_err());
+/// ```
+///
+/// ## Representing with fields
+/// With `#[discrim(serde = "tagged")]`, enums with fields are supported too:
+/// the enum is represented as a struct with a `tag` field containing the discriminant,
+/// and a `content` field containing the fields of the variant
+/// (nothing for unit variants, the only field, or a tuple of all fields in declaration order).
+/// The generated type then has an `_as_tagged` suffix:
+/// ```
+/// use enum_discrim::SerdeRepr;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Eq, SerdeRepr)]
+/// #[discrim(serde = "tagged")]
+/// #[repr(u8)]
+/// enum Message {
+///     Ping = 1,
+///     Data(Vec<u8>) = 4,
+///     Close { code: u16, reason: String },
+/// }
+///
+/// #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// struct S {
+///     #[serde(with = "message_as_tagged")]
+///     m: Message,
+/// }
+///
+/// let s = S { m: Message::Data(vec![1, 2]) };
+/// assert_eq!(serde_json::to_string(&s).unwrap(), r#"{"m":{"tag":4,"content":[1,2]}}"#);
+/// assert_eq!(serde_json::from_str::<S>(r#"{"m":{"tag":4,"content":[1,2]}}"#).unwrap(), s);
+/// assert_eq!(
+///     serde_json::from_str::<S>(r#"{"m":{"tag":5,"content":[1000,"bye"]}}"#).unwrap(),
+///     S { m: Message::Close { code: 1000, reason: "bye".to_owned() } },
+/// );
+/// assert!(serde_json::from_str::<S>(r#"{"m":{"tag":2,"content":null}}"#).is_err());
+/// ```
+/// When represented as a map, the `tag` field *must* come before the `content` field.
+///
+/// ## Representing depending on the format
+/// With `#[discrim(serde = "human_readable")]`, the enum is represented as the name of its variant
+/// in human-readable formats (e.g. JSON or YAML), and as its discriminant in other formats (e.g. bincode or postcard),
+/// as reported by [`Serializer::is_human_readable`](serde::Serializer::is_human_readable).
+/// The generated type then has an `_as_name_or_repr` suffix:
+/// ```
+/// use enum_discrim::SerdeRepr;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Eq, SerdeRepr)]
+/// #[discrim(serde = "human_readable", rename_all = "snake_case")]
+/// #[repr(u8)]
+/// enum MyEnum {
+///     NotFound = 4,
+///     Gone = 10,
+/// }
+///
+/// #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// struct S {
+///     #[serde(with = "my_enum_as_name_or_repr")]
+///     e: MyEnum,
+/// }
+///
+/// assert_eq!(serde_json::to_string(&S { e: MyEnum::NotFound }).unwrap(), r#"{"e":"not_found"}"#);
+/// assert_eq!(serde_json::from_str::<S>(r#"{"e":"gone"}"#).unwrap(), S { e: MyEnum::Gone });
+/// ```
+///
+/// ## Representing as map keys
+/// With `#[discrim(serde = "key")]`, the enum is represented as its discriminant formatted as a string,
+/// which makes it usable as a key of maps serialized into formats only supporting string keys (e.g. JSON).
+/// As map keys cannot be given `#[serde(with = "...")]`, no type is generated:
+/// the enum implements [`Serialize`](serde::Serialize) and [`Deserialize`](serde::Deserialize) itself instead.
+/// ```
+/// use enum_discrim::SerdeRepr;
+/// use std::collections::BTreeMap;
+///
+/// #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, SerdeRepr)]
+/// #[discrim(serde = "key")]
+/// #[repr(u8)]
+/// enum MyEnum {
+///     A = 1,
+///     B = 2,
+/// }
+///
+/// let map = BTreeMap::from([(MyEnum::A, "a"), (MyEnum::B, "b")]);
+/// assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"1":"a","2":"b"}"#);
+/// assert_eq!(serde_json::from_str::<BTreeMap<MyEnum, &str>>(r#"{"2":"b"}"#).unwrap(), BTreeMap::from([(MyEnum::B, "b")]));
+/// assert!(serde_json::from_str::<BTreeMap<MyEnum, &str>>(r#"{"3":"c"}"#).is_err());
+/// ```
+#[cfg(feature = "serde")]
+pub use enum_discrim_proc::SerdeRepr;
+
+/// Derives an impl block for the `TS` trait of [`ts_rs`]
+///
+/// The enum is exported as a TypeScript `const enum` whose members have the same values as the discriminants.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::TS;
+///
+/// #[derive(TS)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::TS;
+///
+/// #[derive(TS)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::TS;
+/// use ts_rs::TS as _;
+///
+/// #[derive(TS)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// assert_eq!(E::decl(), "const enum E { A = 0, B = 2, C = 3 }");
+/// assert_eq!(E::inline(), "0 | 2 | 3");
+/// ```
+#[cfg(feature = "ts-rs")]
+pub use enum_discrim_proc::TS;
+
+/// Derives an impl block for the `ToSchema` trait of [`utoipa`]
+///
+/// The enum is described as an integer schema whose enumerated values are the discriminants,
+/// with the names of the variants listed in its description.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::ToSchema;
+///
+/// #[derive(ToSchema)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::ToSchema;
+///
+/// #[derive(ToSchema)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+#[cfg(feature = "utoipa")]
+pub use enum_discrim_proc::ToSchema;
+
+/// Derives an impl `BorshSerialize` block for [`borsh`]
+///
+/// Unlike `borsh`'s own derive macro, the discriminant is written with the width of the primitive representation
+/// instead of as a `u8` index.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::BorshSerialize;
+///
+/// #[derive(BorshSerialize)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::BorshSerialize;
+///
+/// #[derive(BorshSerialize)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::{BorshDeserialize, BorshSerialize};
+///
+/// #[derive(Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// assert_eq!(borsh::to_vec(&E::B).unwrap(), [2, 0]);
+/// assert_eq!(borsh::from_slice::<E>(&[3, 0]).unwrap(), E::C);
+/// assert!(borsh::from_slice::<E>(&[1, 0]).is_err());
+/// ```
+///
+/// ## Platform-dependent representations
+/// As the width of `usize` and `isize` differs between targets, this macro warns about such representations,
+/// like the other derive macros writing the discriminant to a wire format.
+/// With `#[discrim(deny_platform_dependent)]`, it fails instead:
+/// ```compile_fail
+/// use enum_discrim::BorshSerialize;
+///
+/// #[derive(BorshSerialize)]
+/// #[discrim(deny_platform_dependent)]
+/// // COMPILE ERROR: platform-dependent representation
+/// #[repr(usize)]
+/// enum E {
+///     A,
+///     B = 2,
+/// }
+/// ```
+#[cfg(feature = "borsh")]
+pub use enum_discrim_proc::BorshSerialize;
+
+/// Derives an impl `BorshDeserialize` block for [`borsh`]
+///
+/// Unlike `borsh`'s own derive macro, the discriminant is read with the width of the primitive representation
+/// instead of as a `u8` index.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::BorshDeserialize;
+///
+/// #[derive(BorshDeserialize)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::BorshDeserialize;
+///
+/// #[derive(BorshDeserialize)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+#[cfg(feature = "borsh")]
+pub use enum_discrim_proc::BorshDeserialize;
+
+/// Derives an impl `Encode` block for [`minicbor`]
+///
+/// The enum is encoded as its discriminant, i.e. as a CBOR integer.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::CborEncode;
+///
+/// #[derive(CborEncode)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::CborEncode;
+///
+/// #[derive(CborEncode)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::{CborDecode, CborEncode};
+///
+/// #[derive(Debug, PartialEq, Eq, CborEncode, CborDecode)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C = 300,
+/// }
+///
+/// let mut buf = [0; 3];
+/// minicbor::encode(E::C, &mut buf[..]).unwrap();
+/// assert_eq!(buf, [0x19, 0x01, 0x2C]);
+/// assert_eq!(minicbor::decode::<E>(&buf).unwrap(), E::C);
+/// assert_eq!(minicbor::decode::<E>(&[0x02]).unwrap(), E::B);
+/// assert!(minicbor::decode::<E>(&[0x01]).is_err());
+/// ```
+#[cfg(feature = "minicbor")]
+pub use enum_discrim_proc::CborEncode;
+
+/// Derives an impl `Decode` block for [`minicbor`]
+///
+/// The enum is decoded from its discriminant, i.e. from a CBOR integer.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::CborDecode;
+///
+/// #[derive(CborDecode)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::CborDecode;
+///
+/// #[derive(CborDecode)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+#[cfg(feature = "minicbor")]
+pub use enum_discrim_proc::CborDecode;
+
+/// Derives impl `From<enum> for Value` and `TryFrom<&Value> for enum` blocks for [`serde_json`]
+///
+/// The enum is converted into its discriminant, or into the name of its variant with `#[discrim(serde = "name")]`.
+/// Both forms are accepted when converting back.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::JsonValue;
+///
+/// #[derive(JsonValue)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::JsonValue;
+///
+/// #[derive(JsonValue)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::JsonValue;
+/// use serde_json::{json, Value};
+///
+/// #[derive(Debug, PartialEq, Eq, JsonValue)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// #[derive(Debug, PartialEq, Eq, JsonValue)]
+/// #[discrim(serde = "name", rename_all = "lowercase")]
+/// #[repr(u16)]
+/// enum F {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// assert_eq!(Value::from(E::B), json!(2));
+/// assert_eq!(Value::from(F::B), json!("b"));
+/// assert_eq!(E::try_from(&json!(3)).unwrap(), E::C);
+/// assert_eq!(E::try_from(&json!("C")).unwrap(), E::C);
+/// assert!(E::try_from(&json!(1)).is_err());
+/// assert!(E::try_from(&json!(2.5)).is_err());
+/// ```
+#[cfg(feature = "serde_json")]
+pub use enum_discrim_proc::JsonValue;
+
+/// Derives impl `Encode` and `EncodeLike` blocks for [`parity_scale_codec`]
+///
+/// Unlike `parity-scale-codec`'s own derive macro, the discriminant is written with the width of the primitive representation
+/// instead of as a `u8` index.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::ScaleEncode;
+///
+/// #[derive(ScaleEncode)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::ScaleEncode;
+///
+/// #[derive(ScaleEncode)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::{ScaleDecode, ScaleEncode};
+/// use parity_scale_codec::{Decode, Encode};
+///
+/// #[derive(Debug, PartialEq, Eq, ScaleEncode, ScaleDecode)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// assert_eq!(E::B.encode(), [2, 0]);
+/// assert_eq!(E::decode(&mut &[3, 0][..]).unwrap(), E::C);
+/// assert!(E::decode(&mut &[1, 0][..]).is_err());
+/// ```
+#[cfg(feature = "parity-scale-codec")]
+pub use enum_discrim_proc::ScaleEncode;
+
+/// Derives an impl `Decode` block for [`parity_scale_codec`]
+///
+/// Unlike `parity-scale-codec`'s own derive macro, the discriminant is read with the width of the primitive representation
+/// instead of as a `u8` index.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::ScaleDecode;
+///
+/// #[derive(ScaleDecode)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::ScaleDecode;
+///
+/// #[derive(ScaleDecode)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+#[cfg(feature = "parity-scale-codec")]
+pub use enum_discrim_proc::ScaleDecode;
+
+/// Derives an impl `ToSql` block for [`postgres_types`], as used by `tokio-postgres` and `postgres`
+///
+/// The enum is written as its discriminant, in the narrowest PostgreSQL integer type holding every value of the primitive representation:
+/// `SMALLINT` for `u8`, `i8` and `i16`, `INTEGER` for `u16` and `i32`, and `BIGINT` for `u32` and `i64`.
+/// Other primitive representations are not supported.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::ToSql;
+///
+/// #[derive(Debug, ToSql)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::ToSql;
+///
+/// #[derive(Debug, ToSql)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::{FromSql, ToSql};
+/// use postgres_types::{private::BytesMut, FromSql as _, ToSql as _, Type};
+///
+/// #[derive(Debug, PartialEq, Eq, ToSql, FromSql)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// let mut buf = BytesMut::new();
+/// E::B.to_sql(&Type::INT4, &mut buf).unwrap();
+/// assert_eq!(&buf[..], [0, 0, 0, 2]);
+/// assert_eq!(E::from_sql(&Type::INT4, &[0, 0, 0, 3]).unwrap(), E::C);
+/// assert!(E::from_sql(&Type::INT4, &[0, 0, 0, 1]).is_err());
+/// assert!(E::from_sql(&Type::INT4, &[0, 1, 0, 0]).is_err());
+/// ```
+#[cfg(feature = "postgres-types")]
+pub use enum_discrim_proc::ToSql;
+
+/// Derives an impl `FromSql` block for [`postgres_types`], as used by `tokio-postgres` and `postgres`
+///
+/// The enum is read from its discriminant, in the same PostgreSQL integer type as the one written by [`ToSql`](macro@ToSql).
+/// Values out of the range of the primitive representation, and invalid discriminants, are rejected with a [`TryFromError`].
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::FromSql;
+///
+/// #[derive(FromSql)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::FromSql;
+///
+/// #[derive(FromSql)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+#[cfg(feature = "postgres-types")]
+pub use enum_discrim_proc::FromSql;
+
+/// Derives an impl `From<E> for ExitCode` block
+///
+/// The process then exits with the discriminant of the variant as status code,
+/// so that `main` can return a variant with `.into()`.
+/// As only the lowest 8 bits of a status code are portable, the enum *must* have a `#[repr(u8)]` attribute.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::ExitCode;
+///
+/// #[derive(ExitCode)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare the `u8` primitive representation:
+/// ```compile_fail
+/// use enum_discrim::ExitCode;
+///
+/// #[derive(ExitCode)]
+/// // COMPILE ERROR: missing #[repr(u8)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::ExitCode;
+/// use std::process::ExitCode;
+///
+/// #[derive(ExitCode)]
+/// #[repr(u8)]
+/// enum Status {
+///     Success,
+///     ConfigError = 78,
+/// }
+///
+/// fn main() -> ExitCode {
+///     # if false {
+///     return Status::ConfigError.into();
+///     # }
+///     Status::Success.into()
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub use enum_discrim_proc::ExitCode;
+
+/// Derives impl `TryFrom<BigInt>` and `TryFrom<&BigInt>` blocks for [`num_bigint`]
+///
+/// The value is first checked against the range of the primitive representation, then matched against the discriminants.
+/// Values out of that range, and invalid discriminants, are rejected with a [`TryFromError`],
+/// whose [`kind`](TryFromError::kind) tells them apart.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::TryFromBigInt;
+///
+/// #[derive(TryFromBigInt)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::TryFromBigInt;
+///
+/// #[derive(TryFromBigInt)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::{TryFromBigInt, TryFromErrorKind};
+/// use num_bigint::BigInt;
+///
+/// #[derive(Debug, PartialEq, Eq, TryFromBigInt)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// assert_eq!(E::try_from(BigInt::from(3)).ok(), Some(E::C));
+/// assert_eq!(E::try_from(&BigInt::from(1)).unwrap_err().kind(), TryFromErrorKind::Unknown);
+/// assert_eq!(E::try_from(BigInt::from(258)).unwrap_err().kind(), TryFromErrorKind::OutOfRange);
+/// assert_eq!(E::try_from(BigInt::from(-1)).unwrap_err().kind(), TryFromErrorKind::OutOfRange);
+/// ```
+#[cfg(feature = "num-bigint")]
+pub use enum_discrim_proc::TryFromBigInt;
+
+/// Derives impl `ActiveEnum` and `Iterable` blocks for [`sea_orm`]
+///
+/// The enum is stored as its discriminant, in the integer column type matching the primitive representation
+/// (e.g. `TinyUnsigned` for `u8`, `Integer` for `i32`), so that it can be used directly as the type of an entity column.
+/// Primitive representations wider than 64 bits, `usize` and `isize` are not supported.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::ActiveEnum;
+///
+/// #[derive(Debug, Clone, PartialEq, Eq, ActiveEnum)]
+/// #[repr(i16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::ActiveEnum;
+///
+/// #[derive(Debug, Clone, PartialEq, Eq, ActiveEnum)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::ActiveEnum;
+/// use sea_orm::ActiveEnum as _;
+///
+/// #[derive(Debug, Clone, PartialEq, Eq, ActiveEnum)]
+/// #[repr(i16)]
+/// enum Status {
+///     Active = 1,
+///     Suspended,
+///     Deleted = 9,
+/// }
+///
+/// assert_eq!(Status::Suspended.to_value(), 2);
+/// assert_eq!(Status::try_from_value(&9).unwrap(), Status::Deleted);
+/// assert!(Status::try_from_value(&3).is_err());
+/// assert_eq!(Status::values(), [1, 2, 9]);
+/// ```
+#[cfg(feature = "sea-orm")]
+pub use enum_discrim_proc::ActiveEnum;
+
+/// Derives impl `uDisplay` and `uDebug` blocks for [`ufmt`]
+///
+/// `uDisplay` writes the name of the variant, following the `#[discrim(rename_all = "...")]` and `#[discrim(rename = "...")]` options,
+/// and `uDebug` also writes its discriminant in hexadecimal, e.g. `Data(=0x2A)`.
+/// Both strings are computed at compile time, so no formatting machinery is involved at runtime.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::UFmt;
+///
+/// #[derive(UFmt)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::UFmt;
+///
+/// #[derive(UFmt)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::UFmt;
+/// use std::convert::Infallible;
+///
+/// #[derive(UFmt)]
+/// #[repr(u8)]
+/// enum Packet {
+///     Ping = 0x01,
+///     Data = 0x2A,
+/// }
+///
+/// struct Log(String);
+/// impl ufmt::uWrite for Log {
+///     type Error = Infallible;
+///
+///     fn write_str(&mut self, s: &str) -> Result<(), Infallible> {
+///         self.0.push_str(s);
+///         Ok(())
+///     }
+/// }
+///
+/// let mut log = Log(String::new());
+/// ufmt::uwrite!(log, "{} {:?}", Packet::Ping, Packet::Data).unwrap();
+/// assert_eq!(log.0, "Ping Data(=0x2A)");
+/// ```
+#[cfg(feature = "ufmt")]
+pub use enum_discrim_proc::UFmt;
+
+/// Derives an impl `Specifier` block for [`modular_bitfield`]
+///
+/// The number of bits *must* be declared with `#[discrim(bits = N)]`, and all discriminants *must* fit in it,
+/// so that the enum can be used as a field of a `#[bitfield]` struct. Invalid bit patterns are rejected when read.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::Specifier;
+///
+/// #[derive(Specifier)]
+/// #[discrim(bits = 2)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare the number of bits:
+/// ```compile_fail
+/// use enum_discrim::Specifier;
+///
+/// #[derive(Specifier)]
+/// #[repr(u8)]
+/// // COMPILE ERROR: missing #[discrim(bits = N)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::Specifier;
+/// use modular_bitfield::{bitfield, specifiers::B6};
+///
+/// #[derive(Debug, PartialEq, Eq, Specifier)]
+/// #[discrim(bits = 2)]
+/// #[repr(u8)]
+/// enum Kind {
+///     Data,
+///     Ack = 3,
+/// }
+///
+/// #[bitfield]
+/// struct Header {
+///     kind: Kind,
+///     len: B6,
+/// }
+///
+/// let header = Header::new().with_kind(Kind::Ack).with_len(5);
+/// assert_eq!(header.into_bytes(), [0b0001_0111]);
+/// ```
+#[cfg(feature = "modular-bitfield")]
+pub use enum_discrim_proc::Specifier;
+
+/// Derives impl `From<E> for uN` and `TryFrom<uN> for E` blocks for the narrow integer type of [`arbitrary_int`]
+///
+/// The number of bits *must* be declared with `#[discrim(bits = N)]`, and all discriminants *must* fit in it.
+/// The enum is then converted from and into the [`arbitrary_int`] type of that width (e.g. `u4` for `#[discrim(bits = 4)]`),
+/// so that it can be stored in bit-packed structs without a chain of casts.
+/// Widths of primitive types (8, 16, 32, 64 and 128 bits) are covered by the [`Into`](macro@Into) and [`TryFrom`](macro@TryFrom) derive macros instead.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::ArbitraryInt;
+///
+/// #[derive(ArbitraryInt)]
+/// #[discrim(bits = 2)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare the number of bits:
+/// ```compile_fail
+/// use enum_discrim::ArbitraryInt;
+///
+/// #[derive(ArbitraryInt)]
+/// #[repr(u8)]
+/// // COMPILE ERROR: missing #[discrim(bits = N)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use arbitrary_int::u4;
+/// use enum_discrim::ArbitraryInt;
+///
+/// #[derive(Debug, PartialEq, Eq, ArbitraryInt)]
+/// #[discrim(bits = 4)]
+/// #[repr(u8)]
+/// enum Kind {
+///     Data,
+///     Ack = 15,
+/// }
+///
+/// assert_eq!(u4::from(Kind::Ack), u4::new(15));
+/// assert_eq!(Kind::try_from(u4::new(0)).unwrap(), Kind::Data);
+/// assert!(Kind::try_from(u4::new(1)).is_err());
+/// ```
+#[cfg(feature = "arbitrary-int")]
+pub use enum_discrim_proc::ArbitraryInt;
+
+/// Derives an impl `ToRedisArgs` block for [`redis`]
+///
+/// The enum is written as its discriminant.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::ToRedisArgs;
+///
+/// #[derive(ToRedisArgs)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::ToRedisArgs;
+///
+/// #[derive(ToRedisArgs)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::{FromRedisValue, ToRedisArgs};
+/// use redis::{FromRedisValue as _, ToRedisArgs as _, Value};
+///
+/// #[derive(Debug, PartialEq, Eq, ToRedisArgs, FromRedisValue)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// assert_eq!(E::B.to_redis_args(), [b"2"]);
+/// assert_eq!(E::from_redis_value(&Value::Int(3)).unwrap(), E::C);
+/// assert!(E::from_redis_value(&Value::Int(1)).is_err());
+/// ```
+#[cfg(feature = "redis")]
+pub use enum_discrim_proc::ToRedisArgs;
+
+/// Derives an impl `FromRedisValue` block for [`redis`]
+///
+/// The enum is read from its discriminant, rejecting invalid values.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::FromRedisValue;
+///
+/// #[derive(FromRedisValue)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::FromRedisValue;
+///
+/// #[derive(FromRedisValue)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+#[cfg(feature = "redis")]
+pub use enum_discrim_proc::FromRedisValue;
+
+/// Derives an impl `Writable` block for [`speedy`]
+///
+/// The discriminant is written with the width of the primitive representation,
+/// in the byte order of the `speedy` context unless `#[discrim(endianness = "little")]` or `#[discrim(endianness = "big")]` is given.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::Writable;
+///
+/// #[derive(Writable)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::Writable;
+///
+/// #[derive(Writable)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::{Readable, Writable};
+/// use speedy::{Endianness, Readable as _, Writable as _};
+///
+/// #[derive(Debug, PartialEq, Eq, Readable, Writable)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// #[derive(Debug, PartialEq, Eq, Readable, Writable)]
+/// #[repr(u16)]
+/// #[discrim(endianness = "big")]
+/// enum F {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// assert_eq!(E::B.write_to_vec_with_ctx(Endianness::LittleEndian).unwrap(), [2, 0]);
+/// assert_eq!(F::B.write_to_vec_with_ctx(Endianness::LittleEndian).unwrap(), [0, 2]);
+/// assert_eq!(E::read_from_buffer_with_ctx(Endianness::LittleEndian, &[3, 0]).unwrap(), E::C);
+/// assert!(E::read_from_buffer_with_ctx(Endianness::LittleEndian, &[1, 0]).is_err());
+/// ```
+#[cfg(feature = "speedy")]
+pub use enum_discrim_proc::Writable;
+
+/// Derives an impl `Readable` block for [`speedy`]
+///
+/// The discriminant is read with the width of the primitive representation,
+/// in the byte order of the `speedy` context unless `#[discrim(endianness = "little")]` or `#[discrim(endianness = "big")]` is given.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::Readable;
+///
+/// #[derive(Readable)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::Readable;
+///
+/// #[derive(Readable)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+#[cfg(feature = "speedy")]
+pub use enum_discrim_proc::Readable;
+
+/// Derives impl `From<enum> for Bson` and `TryFrom<Bson> for enum` blocks for [`bson`]
+///
+/// The discriminant is stored as an `Int32` if the primitive representation fits in 32 bits, and as an `Int64` otherwise.
+/// Both are accepted when converting back, so that the enum can be read from documents written by other drivers.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::Bson;
+///
+/// #[derive(Bson)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::Bson;
+///
+/// #[derive(Bson)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use bson::Bson;
+///
+/// #[derive(Debug, PartialEq, Eq, enum_discrim::Bson)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// #[derive(Debug, PartialEq, Eq, enum_discrim::Bson)]
+/// #[repr(u64)]
+/// enum F {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// assert_eq!(Bson::from(E::B), Bson::Int32(2));
+/// assert_eq!(Bson::from(F::B), Bson::Int64(2));
+/// assert_eq!(E::try_from(Bson::Int64(3)).unwrap(), E::C);
+/// assert!(E::try_from(Bson::Int32(1)).is_err());
+/// assert!(E::try_from(Bson::String("B".to_owned())).is_err());
+/// ```
+#[cfg(feature = "bson")]
+pub use enum_discrim_proc::Bson;
+
+/// Derives an impl block containing functions to read and write the discriminant with [`bytes`]
+///
+/// The generated `get_from` function reads the discriminant from any [`Buf`](bytes::Buf) and returns a [`TryFromError`] if it is invalid,
+/// and the generated `put_to` function writes the discriminant to any [`BufMut`](bytes::BufMut).
+/// The discriminant is written with the width of the primitive representation,
+/// in big-endian unless `#[discrim(endianness = "little")]` is given.
+/// As with the `get_*` and `put_*` functions of [`bytes`], both functions panic if the buffer is too short.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::BytesCodec;
+///
+/// #[derive(BytesCodec)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a fixed-width [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::BytesCodec;
+///
+/// #[derive(BytesCodec)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::BytesCodec;
+///
+/// #[derive(Debug, PartialEq, Eq, BytesCodec)]
+/// #[repr(u16)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// #[derive(Debug, PartialEq, Eq, BytesCodec)]
+/// #[repr(u16)]
+/// #[discrim(endianness = "little")]
+/// enum F {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// let mut buf = Vec::new();
+/// E::B.put_to(&mut buf);
+/// F::B.put_to(&mut buf);
+/// assert_eq!(buf, [0, 2, 2, 0]);
+///
+/// let mut buf = &[0_u8, 3, 3, 0, 0, 1][..];
+/// assert_eq!(E::get_from(&mut buf).unwrap(), E::C);
+/// assert_eq!(F::get_from(&mut buf).unwrap(), F::C);
+/// assert!(E::get_from(&mut buf).is_err());
+/// ```
+#[cfg(feature = "bytes")]
+pub use enum_discrim_proc::BytesCodec;
+
+/// Derives an entry of the [`registry`], describing the enum and its discriminants at runtime
+///
+/// # Usage
+/// You may use this macro on enums with a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations):
+/// ```
+/// use enum_discrim::Register;
+///
+/// #[derive(Register)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B(u8) = 2,
+///     C { n: usize },
+/// }
+/// ```
+/// ```compile_fail
+/// use enum_discrim::Register;
+///
+/// #[derive(Register)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::{registry, Register};
+///
+/// #[derive(Register)]
+/// #[repr(i8)]
+/// enum E {
+///     A = -1,
+///     B = 2,
+///     C,
+/// }
+///
+/// let info = registry::find("E").unwrap();
+/// assert_eq!(info.variants().len(), 3);
+/// assert_eq!(info.name_of(-1), Some("A"));
+/// assert_eq!(info.name_of(3), Some("C"));
+/// ```
+#[cfg(feature = "inventory")]
+pub use enum_discrim_proc::Register;
+
+/// Derives impl `Enum` and `EnumArray` blocks for [`enum_map`]
+///
+/// Variants are indexed by their position in the enum declaration, like [`Ordinal`](macro@Ordinal) does,
+/// so that the enum can be used as the key of an `EnumMap`.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::EnumMap;
+///
+/// #[derive(EnumMap)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// ```compile_fail
+/// use enum_discrim::EnumMap;
+///
+/// #[derive(EnumMap)]
+/// // COMPILE ERROR: Not all variants are unit
+/// enum E {
+///     A,
+///     B(u8),
+///     C { n: usize },
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::EnumMap;
+///
+/// #[derive(Debug, EnumMap)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// let mut map = enum_map::EnumMap::<E, u32>::default();
+/// map[E::C] = 3;
+/// assert_eq!(map[E::A], 0);
+/// assert_eq!(map[E::C], 3);
+/// ```
+#[cfg(feature = "enum-map")]
+pub use enum_discrim_proc::EnumMap;
+
+/// Derives impl `Valuable` and `Enumerable` blocks for [`valuable`]
+///
+/// Each variant is exposed with its name and a single unnamed field holding its discriminant,
+/// so that `tracing` records structured data instead of a [`Debug`](std::fmt::Debug) string.
+///
+/// # Usage
+/// You may use this macro on enums with *only* unit variants:
+/// ```
+/// use enum_discrim::Valuable;
+///
+/// #[derive(Valuable)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+/// You also *need* to declare a [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations) for your enum:
+/// ```compile_fail
+/// use enum_discrim::Valuable;
+///
+/// #[derive(Valuable)]
+/// // COMPILE ERROR: missing #[repr]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// use enum_discrim::Valuable;
+/// use valuable::{Enumerable, Valuable as _, Value};
+///
+/// #[derive(Valuable)]
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// assert_eq!(E::B.variant().name(), "B");
+/// assert!(matches!(E::B.as_value(), Value::Enumerable(_)));
+/// ```
+#[cfg(feature = "valuable")]
+pub use enum_discrim_proc::Valuable;
+
+pub use enum_discrim_core::{
+	Describe, Discriminant, EnumInfo, FromStrError, Ordinal, RawValue, TryFromError,
+	TryFromErrorKind, TryFromFloatError, TryFromReprExt, VariantInfo, VariantMarker,
+};
+
+/// Re-exports used by the generated code, not part of the public API
+#[doc(hidden)]
+pub mod __private {
+	pub use alloc;
+	#[cfg(feature = "arbitrary-int")]
+	pub use arbitrary_int;
+	#[cfg(feature = "async-graphql")]
+	pub use async_graphql;
+	#[cfg(feature = "bevy_reflect")]
+	pub use bevy_reflect;
+	#[cfg(feature = "borsh")]
+	pub use borsh;
+	#[cfg(feature = "bson")]
+	pub use bson;
+	#[cfg(feature = "bytes")]
+	pub use bytes;
+	#[cfg(feature = "enum-map")]
+	pub use enum_map;
+	#[cfg(feature = "inventory")]
+	pub use inventory;
+	#[cfg(feature = "minicbor")]
+	pub use minicbor;
+	#[cfg(feature = "modular-bitfield")]
+	pub use modular_bitfield;
+	#[cfg(feature = "num-bigint")]
+	pub use num_bigint;
+	#[cfg(feature = "parity-scale-codec")]
+	pub use parity_scale_codec;
+	#[cfg(feature = "postgres-types")]
+	pub use postgres_types;
+	#[cfg(feature = "redis")]
+	pub use redis;
+	#[cfg(feature = "sea-orm")]
+	pub use sea_orm;
+	#[cfg(feature = "serde")]
+	pub use serde;
+	#[cfg(feature = "serde_json")]
+	pub use serde_json;
+	#[cfg(feature = "speedy")]
+	pub use speedy;
+	#[cfg(feature = "std")]
+	pub use std;
+	#[cfg(feature = "ts-rs")]
+	pub use ts_rs;
+	#[cfg(feature = "ufmt")]
+	pub use ufmt;
+	#[cfg(feature = "utoipa")]
+	pub use utoipa;
+	#[cfg(feature = "valuable")]
+	pub use valuable;
+}
+
+pub mod atomic;
+pub mod bulk;
+pub mod c_header;
+pub mod counter;
+pub mod group;
+mod macros;
+#[cfg(feature = "inventory")]
+pub mod registry;
+pub mod slice;
+pub mod tables;
+pub mod varint;
+
+// #[cfg(doc)]
+pub mod examples;
+
+#[cfg(test)]
+mod tests {
+	#![allow(dead_code)]
+
+	use self::enum_discrim::*;
+	use crate as enum_discrim;
+
+	#[test]
+	fn discriminants() {
+		#[derive(Debug, Discriminants)]
+		#[repr(u8)]
+		enum E<B, C>
+		where
+			B: Copy,
+		{
+			A,
+			B(B) = 2,
+			C { c: C },
+		}
+
+		type MyE = E<&'static str, i32>;
+		assert_eq!(MyE::A_D, 0_u8);
+		assert_eq!(MyE::B_D, 2_u8);
+		assert_eq!(MyE::C_D, 3_u8);
+		assert_eq!(MyE::A.discriminant(), 0_u8);
+		assert_eq!(MyE::B("hello").discriminant(), 2_u8);
+		assert_eq!(MyE::C { c: 42 }.discriminant(), 3_u8);
+		assert_eq!(MyE::name_of(2), Some("B"));
+		assert_eq!(MyE::name_of(1), None);
+	}
+
+	#[test]
+	fn discriminants_generics() {
+		#[derive(Discriminants)]
+		#[repr(u8)]
+		enum E<'a, const N: usize, T: Copy = u8> {
+			A(&'a str),
+			B([T; N]) = 2,
+		}
+
+		assert_eq!(E::<'static, 2, u8>::B_D, 2_u8);
+		assert_eq!(E::<'_, 1, u8>::A("a").discriminant(), 0_u8);
+		assert_eq!(E::<'_, 2, i32>::B([1, 2]).discriminant(), 2_u8);
+	}
+
+	#[test]
+	fn into() {
+		#[derive(Debug, PartialEq, Eq, Into)]
+		#[repr(u8)]
+		enum E {
+			A,
+			B = 2,
+			C,
+		}
+
+		assert_eq!(<E as Into<u8>>::into(E::A), 0_u8);
+		assert_eq!(u8::from(E::B), 2_u8);
+		assert_eq!(u8::from(E::C), 3_u8);
+	}
+
+	#[test]
+	fn debug() {
+		#[derive(Discriminants)]
+		#[discrim(debug)]
+		#[repr(i16)]
+		enum E<T> {
+			A(T) = -2,
+			B { r#type: T },
+			C = 0x100,
+		}
+
+		assert_eq!(format!("{:?}", E::A(1)), "A(=-0x2)(1)");
+		assert_eq!(
+			format!("{:?}", E::B { r#type: "b" }),
+			"B(=-0x1) { type: \"b\" }"
+		);
+		assert_eq!(format!("{:?}", E::<()>::C), "C(=0x100)");
+	}
+
+	#[test]
+	fn kind() {
+		#[derive(Discriminants)]
+		#[discrim(logical_repr = "i16", kind)]
+		enum E<T> {
+			A(T),
+			#[discrim(value = "-0x100")]
+			B {
+				value: T,
+			},
+			#[discrim(value = 7)]
+			C,
+		}
+
+		assert_eq!(EKind::from(E::A(())), EKind::A);
+		assert_eq!(EKind::from(&E::B { value: 1 }), EKind::B);
+		assert_eq!(EKind::B.discriminant(), -0x100_i16);
+		assert_eq!(EKind::C.discriminant(), E::<()>::C_D);
+		assert!(EKind::A < EKind::C);
+	}
+
+	#[test]
+	fn kind_repr_c() {
+		#[derive(Discriminants)]
+		#[discrim(kind = "Kind")]
+		#[repr(C)]
+		enum E {
+			A(u8),
+			B { value: u16 },
+			C,
+		}
+
+		assert_eq!(Kind::from(E::A(0)), Kind::A);
+		assert_eq!(Kind::from(&E::B { value: 1 }), Kind::B);
+		assert_eq!(Kind::C.discriminant(), 2 as core::ffi::c_int);
+		assert_eq!(Kind::C as core::ffi::c_int, E::C_D);
+	}
+
+	#[test]
+	fn markers() {
+		#[derive(Discriminants)]
+		#[discrim(markers)]
+		#[repr(u8)]
+		enum E {
+			A,
+			B = 2,
+		}
+		#[derive(Discriminants)]
+		#[discrim(markers)]
+		#[repr(i16)]
+		enum F {
+			A = -1,
+		}
+
+		assert_eq!(EAVariant::DISCRIMINANT, 0_u8);
+		assert_eq!(<EBVariant as VariantMarker>::DISCRIMINANT, 2_u8);
+		assert_eq!(FAVariant::DISCRIMINANT, -1_i16);
+	}
+
+	#[test]
+	fn newtype() {
+		#[derive(Debug, PartialEq, Eq, Newtype)]
+		#[discrim(valid = "-10..=-1", valid = "0x7F")]
+		#[discrim(consts(MIN = "-10", MAX = 0x7F))]
+		struct Code(i8);
+
+		assert_eq!(Code::try_from(-10).unwrap(), Code::MIN);
+		assert_eq!(Code::try_from(127).unwrap(), Code::MAX);
+		assert_eq!(i8::from(Code::MAX), 127);
+		assert_eq!(Code::try_from(0).unwrap_err().enum_name(), "Code");
+	}
+
+	#[test]
+	fn bits() {
+		#[derive(Debug, PartialEq, Eq, Bits)]
+		#[discrim(bits = 9)]
+		#[repr(i32)]
+		enum E {
+			A = 0x1FF,
+			B = 0,
+		}
+
+		const A: u16 = E::A.into_bits();
+		assert_eq!(E::BITS, 9);
+		assert_eq!(A, 0x1FF_u16);
+		assert_eq!(E::from_bits(0), E::B);
+	}
+
+	#[test]
+	fn narrow() {
+		#[derive(Debug, PartialEq, Eq, Into)]
+		#[discrim(narrow = "i8", narrow = "i16")]
+		#[repr(i64)]
+		enum E {
+			A = -128,
+			B = 127,
+		}
+
+		assert_eq!(i8::from(E::A), -128_i8);
+		assert_eq!(i8::from(E::B), 127_i8);
+		assert_eq!(i16::from(E::B), 127_i16);
+		assert_eq!(i64::from(E::A), -128_i64);
+	}
+
+	#[test]
+	fn try_from() {
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[repr(u8)]
+		enum E {
+			A,
+			B = 2,
+			C,
+		}
+
+		assert_eq!(E::try_from(0).unwrap(), E::A);
+		assert_eq!(E::try_from(2).unwrap(), E::B);
+		assert_eq!(E::try_from(3).unwrap(), E::C);
+		assert!(E::try_from(1).is_err());
+	}
+
+	#[test]
+	fn try_from_ref() {
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[repr(u8)]
+		enum E {
+			A,
+			B = 2,
+			C,
+		}
+
+		assert_eq!(E::try_from(&2_u8).unwrap(), E::B);
+		assert_eq!(E::try_from(&mut 3_u8).unwrap(), E::C);
+		assert!(E::try_from(&1_u8).is_err());
+	}
+
+	#[test]
+	fn try_from_any() {
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[repr(u8)]
+		enum E {
+			A,
+			B = 2,
+			C,
+		}
+
+		assert_eq!(E::try_from_any(3_i64).unwrap(), E::C);
+		assert_eq!(E::try_from_any(-1_i64).unwrap_err().enum_name(), "E");
+		assert!(E::try_from_any(1_u64).is_err());
+	}
+
+	#[test]
+	fn error_kind() {
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[repr(u8)]
+		enum E {
+			A,
+			B = 2,
+		}
+
+		assert_eq!(
+			E::try_from(1).unwrap_err().kind(),
+			TryFromErrorKind::Unknown
+		);
+		assert_eq!(
+			E::try_from_any(-1_i64).unwrap_err().kind(),
+			TryFromErrorKind::OutOfRange
+		);
+	}
+
+	#[test]
+	fn to_enum() {
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[repr(u8)]
+		enum E {
+			A,
+			B = 2,
+		}
+
+		assert_eq!(2_u8.to_enum::<E>().unwrap(), E::B);
+		assert!(1_u8.to_enum::<E>().is_err());
+	}
+
+	#[test]
+	fn error_enum_name() {
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[repr(u8)]
+		enum E {
+			A,
+		}
+
+		assert_eq!(E::try_from(1).unwrap_err().enum_name(), "E");
 	}
-}
-impl Display for TryFromError {
-	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-		write!(f, "Tried to convert an invalid value into a {}", self.ident)
+
+	#[test]
+	fn reserved() {
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[discrim(reserved = "-8..-4", reserved = "0x10")]
+		#[repr(i8)]
+		enum E {
+			A = -2,
+			B,
+		}
+
+		assert_eq!(E::try_from(-1).unwrap(), E::B);
+		assert!(E::try_from(-8).unwrap_err().is_reserved());
+		assert!(E::try_from(-5).unwrap_err().is_reserved());
+		assert!(!E::try_from(-4).unwrap_err().is_reserved());
+		assert!(E::try_from(16).unwrap_err().is_reserved());
 	}
-}
-impl Error for TryFromError {
-	#[inline]
-	fn source(&self) -> Option<&(dyn Error + 'static)> {
-		None
+
+	#[test]
+	fn lookup() {
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[discrim(lookup = "range")]
+		#[repr(i8)]
+		enum Range {
+			A = -1,
+			B,
+			C,
+		}
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[discrim(lookup = "table", reserved = "0")]
+		#[repr(i8)]
+		enum Table {
+			A = -128,
+			B = 127,
+		}
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[discrim(lookup = "binary_search")]
+		#[repr(u64)]
+		enum BinarySearch {
+			A = 18_446_744_073_709_551_615,
+			B = 3,
+			C = 1_099_511_627_776,
+		}
+
+		assert_eq!(Range::try_from(-1).unwrap(), Range::A);
+		assert_eq!(Range::try_from(1).unwrap(), Range::C);
+		assert!(Range::try_from(2).is_err());
+		assert_eq!(Table::try_from(-128).unwrap(), Table::A);
+		assert_eq!(Table::try_from(127).unwrap(), Table::B);
+		assert!(Table::try_from(0).unwrap_err().is_reserved());
+		assert!(!Table::try_from(1).unwrap_err().is_reserved());
+		assert_eq!(BinarySearch::try_from(u64::MAX).unwrap(), BinarySearch::A);
+		assert_eq!(BinarySearch::try_from(1 << 40).unwrap(), BinarySearch::C);
+		assert!(BinarySearch::try_from(4).is_err());
 	}
-}
 
-// #[cfg(doc)]
-pub mod examples;
+	#[test]
+	fn const_time() {
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[discrim(const_time, reserved = "-128..=-100")]
+		#[repr(i8)]
+		enum E {
+			A = -1,
+			B = 0,
+			C = 127,
+		}
 
-#[cfg(test)]
-mod tests {
-	#![allow(dead_code)]
+		assert_eq!(E::try_from(-1).unwrap(), E::A);
+		assert_eq!(E::try_from(0).unwrap(), E::B);
+		assert_eq!(E::try_from(127).unwrap(), E::C);
+		assert!(E::try_from(-100).unwrap_err().is_reserved());
+		assert!(!E::try_from(1).unwrap_err().is_reserved());
+	}
 
-	use self::enum_discrim::*;
-	use crate as enum_discrim;
+	#[test]
+	fn msrv() {
+		#[derive(Debug, PartialEq, Eq, Discriminants, TryFrom)]
+		#[discrim(msrv = "1.81", c_uint)]
+		#[repr(C)]
+		enum E {
+			A,
+			B = 2,
+		}
+
+		assert_eq!(E::B_D, 2 as core::ffi::c_int);
+		assert_eq!(E::try_from(2 as core::ffi::c_uint).unwrap(), E::B);
+		assert!(E::try_from(1).is_err());
+	}
 
 	#[test]
-	fn discriminants() {
-		#[derive(Debug, Discriminants)]
+	fn logical_repr() {
+		#[derive(Debug, PartialEq, Eq, Discriminants, Into, TryFrom)]
+		#[discrim(logical_repr = "i16")]
+		enum E {
+			A = -1,
+			B = 300,
+			C,
+		}
+
+		assert_eq!(E::B_D, 300_i16);
+		assert_eq!(E::C.discriminant(), 301_i16);
+		assert_eq!(i16::from(E::A), -1_i16);
+		assert_eq!(E::try_from(301).unwrap(), E::C);
+		assert!(E::try_from(0).is_err());
+	}
+
+	#[test]
+	fn error_value() {
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[discrim(reserved = "0xF0..=0xFF", lookup = "binary_search")]
 		#[repr(u8)]
-		enum E<B, C>
-		where
-			B: Copy,
-		{
+		enum E {
+			A = 1,
+			#[discrim(skip)]
+			B,
+		}
+
+		let err = E::try_from(0).unwrap_err();
+		assert_eq!(err.value(), Some(RawValue::Unsigned(0)));
+		assert!(err.to_string().contains("(got 0)"));
+		assert_eq!(
+			E::try_from(0xF1).unwrap_err().value(),
+			Some(RawValue::Unsigned(0xF1))
+		);
+		assert_eq!(
+			E::try_from(2).unwrap_err().value(),
+			Some(RawValue::Unsigned(2))
+		);
+		assert_eq!(E::try_from_any(-1).unwrap_err().value(), None);
+	}
+
+	#[test]
+	fn typed_error() {
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[discrim(typed_error, try_from_f64)]
+		#[repr(u8)]
+		enum E {
 			A,
-			B(B) = 2,
-			C { c: C },
+			B = 2,
 		}
 
-		type MyE = E<&'static str, i32>;
-		assert_eq!(MyE::A_D, 0_u8);
-		assert_eq!(MyE::B_D, 2_u8);
-		assert_eq!(MyE::C_D, 3_u8);
-		assert_eq!(MyE::A.discriminant(), 0_u8);
-		assert_eq!(MyE::B("hello").discriminant(), 2_u8);
-		assert_eq!(MyE::C { c: 42 }.discriminant(), 3_u8);
+		let err: TryFromError<E> = E::try_from(1_u8).unwrap_err();
+		assert_eq!(err.kind(), TryFromErrorKind::Unknown);
+		assert_eq!(err.enum_name(), "E");
+		let err: TryFromError = err.into_untyped();
+		assert_eq!(err.value(), Some(RawValue::Unsigned(1)));
+		assert_eq!(
+			err.expected(),
+			&[RawValue::Unsigned(0), RawValue::Unsigned(2)]
+		);
+		let err: TryFromError<E> = E::try_from_any(-1).unwrap_err();
+		assert_eq!(err.kind(), TryFromErrorKind::OutOfRange);
+		assert!(matches!(
+			E::try_from(1.0),
+			Err(TryFromFloatError::Invalid(_))
+		));
+		assert_eq!(E::try_from(2.0).unwrap(), E::B);
 	}
 
 	#[test]
-	fn into() {
+	fn error_expected_signed() {
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[repr(i8)]
+		enum E {
+			#[discrim(alias = "-128")]
+			A = -1,
+			B = 0x7F,
+		}
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[repr(i128)]
+		enum F {
+			A = i128::MIN,
+			B = i128::MAX,
+		}
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[repr(u128)]
+		enum G {
+			A = u128::MAX,
+		}
+
+		assert_eq!(
+			E::try_from(0).unwrap_err().expected(),
+			&[
+				RawValue::Signed(-1),
+				RawValue::Signed(127),
+				RawValue::Signed(-128)
+			]
+		);
+		assert!(E::try_from(0)
+			.unwrap_err()
+			.to_string()
+			.ends_with("expected one of -1, 127, -128"));
+		assert_eq!(
+			F::try_from(0).unwrap_err().expected(),
+			&[RawValue::Signed(i128::MIN), RawValue::Signed(i128::MAX)]
+		);
+		assert_eq!(
+			G::try_from(0).unwrap_err().expected(),
+			&[RawValue::Unsigned(u128::MAX)]
+		);
+		assert!(G::try_from(0)
+			.unwrap_err()
+			.to_string()
+			.ends_with(&format!("expected one of {}", u128::MAX)));
+	}
+
+	#[test]
+	fn error_expected() {
+		const SEVEN: u8 = 7;
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[repr(u8)]
+		enum E {
+			#[discrim(alias = 10)]
+			A = 1,
+			#[discrim(skip)]
+			B,
+			C = SEVEN,
+			D = 0x10,
+		}
+
+		let expected = [1, 7, 16, 10].map(RawValue::Unsigned);
+		let err = E::try_from(0).unwrap_err();
+		assert_eq!(err.expected(), &expected);
+		assert!(err
+			.to_string()
+			.ends_with("(got 0), expected one of 1, 7, 16, 10"));
+		assert_eq!(E::try_from(2).unwrap_err().expected(), &expected);
+		assert_eq!(E::try_from_any(-1).unwrap_err().expected(), &expected);
+	}
+
+	#[test]
+	fn custom_error() {
+		#[derive(Debug, PartialEq, Eq)]
+		enum Error {
+			Unknown,
+			Other,
+		}
+		fn classify(err: TryFromError) -> Error {
+			match err.kind() {
+				TryFromErrorKind::Unknown => Error::Unknown,
+				_ => Error::Other,
+			}
+		}
+
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[discrim(
+			error = "Error",
+			map_err = "classify",
+			reserved = "3",
+			c_uint,
+			lookup = "table"
+		)]
+		#[repr(u16)]
+		enum E {
+			A = 1,
+			B = 2,
+		}
+
+		assert_eq!(E::try_from(2_u16), Ok(E::B));
+		assert_eq!(E::try_from(&0_u16), Err(Error::Unknown));
+		assert_eq!(E::try_from(3_u16), Err(Error::Other));
+		assert_eq!(
+			E::try_from(0x1_0000 as core::ffi::c_uint),
+			Err(Error::Other)
+		);
+		assert_eq!(E::try_from_any(-1), Err(Error::Other));
+	}
+
+	#[cfg(feature = "minicbor")]
+	#[test]
+	fn cbor_round_trip() {
+		#[derive(Debug, PartialEq, Eq, CborEncode, CborDecode)]
+		#[repr(i8)]
+		enum E {
+			A = -3,
+			B = 0,
+			C = 100,
+		}
+
+		for variant in [E::A, E::B, E::C] {
+			let mut buf = [0; 2];
+			minicbor::encode(&variant, &mut buf[..]).unwrap();
+			assert_eq!(minicbor::decode::<E>(&buf).unwrap(), variant);
+		}
+		let mut buf = [0; 1];
+		minicbor::encode(E::A, &mut buf[..]).unwrap();
+		assert_eq!(buf, [0x22]);
+		assert!(minicbor::decode::<E>(&[0x01]).is_err());
+	}
+
+	#[test]
+	fn custom_error_from() {
+		#[derive(Debug, PartialEq, Eq)]
+		struct Error(TryFromErrorKind);
+		impl From<TryFromError> for Error {
+			fn from(err: TryFromError) -> Self {
+				Self(err.kind())
+			}
+		}
+
+		#[derive(Debug, PartialEq, Eq, TryFrom)]
+		#[discrim(error = "Error", reserved = "3")]
+		#[repr(u8)]
+		enum E {
+			A = 1,
+			B = 2,
+		}
+
+		assert_eq!(E::try_from(2_u8), Ok(E::B));
+		assert_eq!(E::try_from(0_u8), Err(Error(TryFromErrorKind::Unknown)));
+		assert_eq!(E::try_from(3_u8), Err(Error(TryFromErrorKind::Reserved)));
+		assert_eq!(
+			E::try_from_any(-1),
+			Err(Error(TryFromErrorKind::OutOfRange))
+		);
+	}
+
+	#[test]
+	fn try_from_aliases() {
+		#[derive(Debug, PartialEq, Eq, Discriminants, Into, TryFrom)]
+		#[discrim(lookup = "range", next_free)]
+		#[repr(i8)]
+		enum E {
+			#[discrim(alias = "-0x10", alias = 5)]
+			A = -1,
+			B,
+			#[discrim(alias = 6)]
+			C,
+		}
+
+		assert_eq!(E::try_from(-16).unwrap(), E::A);
+		assert_eq!(E::try_from(5).unwrap(), E::A);
+		assert_eq!(E::try_from(6).unwrap(), E::C);
+		assert_eq!(E::try_from(0).unwrap(), E::B);
+		assert!(E::try_from(2).is_err());
+		assert_eq!(i8::from(E::A), -1);
+		assert_eq!(E::NEXT_FREE_DISCRIMINANT, 7);
+	}
+
+	#[test]
+	fn default() {
+		#[derive(Debug, PartialEq, Eq, Into, TryFrom)]
+		#[discrim(lookup = "binary_search", reserved = "5", try_from_f64)]
+		#[repr(i16)]
+		enum E {
+			A = -1,
+			#[discrim(default)]
+			B = 0,
+			C = 0x100,
+		}
+
+		assert_eq!(E::from(-1), E::A);
+		assert_eq!(E::from(&0x100), E::C);
+		assert_eq!(E::from(5), E::B);
+		assert_eq!(E::from(i16::MAX), E::B);
+		assert_eq!(E::try_from_any(7_u64).unwrap(), E::B);
+		assert!(E::try_from_any(u64::MAX).is_err());
+		assert_eq!(E::try_from(256.0).unwrap(), E::C);
+	}
+
+	#[test]
+	fn skip() {
+		#[derive(Debug, PartialEq, Eq, Discriminants, TryFrom)]
+		#[discrim(lookup = "table")]
+		#[repr(i8)]
+		enum E {
+			A = -2,
+			#[discrim(skip)]
+			B,
+			C,
+		}
+
+		assert_eq!(E::C_D, 0);
+		assert_eq!(E::B.discriminant(), -1);
+		assert_eq!(E::name_of(-1), None);
+		assert_eq!(E::try_from(0).unwrap(), E::C);
+		assert_eq!(
+			E::try_from(-1).unwrap_err().kind(),
+			TryFromErrorKind::Skipped
+		);
+		assert_eq!(
+			E::try_from(1).unwrap_err().kind(),
+			TryFromErrorKind::Unknown
+		);
+	}
+
+	#[test]
+	fn literal_radixes() {
+		#[derive(Debug, PartialEq, Eq, Discriminants, Into, TryFrom)]
+		#[repr(u16)]
+		enum E {
+			Hex = 0x01,
+			Bin = 0b0000_0010,
+			Oct = 0o17,
+			Suffixed = 0xFF_u16,
+			Grouped = 1_000,
+		}
+
+		assert_eq!(E::Hex_D, 1);
+		assert_eq!(E::Bin.discriminant(), 2);
+		assert_eq!(u16::from(E::Oct), 15);
+		assert_eq!(E::try_from(255).unwrap(), E::Suffixed);
+		assert_eq!(E::name_of(1000), Some("Grouped"));
+	}
+
+	#[test]
+	fn const_expressions() {
+		#[derive(Debug, PartialEq, Eq, Discriminants, Into, TryFrom)]
+		#[repr(i32)]
+		enum E {
+			A = -(1 << 4),
+			B,
+			C = 0x10 - 0b1 + (0o7 | 8),
+			D = 1 << 30 >> 2,
+		}
+
+		assert_eq!(E::A_D, -16);
+		assert_eq!(E::B.discriminant(), -15);
+		assert_eq!(i32::from(E::C), 30);
+		assert_eq!(E::try_from(1 << 28).unwrap(), E::D);
+	}
+
+	#[test]
+	fn path_discriminants() {
+		const BASE: i16 = -0x100;
+		const LAST: i16 = i16::MAX;
+
+		#[derive(Debug, PartialEq, Eq, Discriminants, Into, TryFrom)]
+		#[repr(i16)]
+		enum E {
+			A = BASE,
+			B = 0,
+			C,
+			D = LAST,
+		}
+
+		assert_eq!(E::A_D, -0x100);
+		assert_eq!(E::D.discriminant(), i16::MAX);
+		assert_eq!(E::name_of(LAST), Some("D"));
+		assert_eq!(i16::from(E::C), 1);
+		assert_eq!(E::try_from(BASE).unwrap(), E::A);
+		assert!(E::try_from(2).is_err());
+	}
+
+	#[test]
+	fn char_conversions() {
+		#[derive(Debug, PartialEq, Eq, Into, TryFrom)]
+		#[discrim(char)]
+		#[repr(u8)]
+		enum Key {
+			Escape = 0x1B,
+			Eacute = 0xE9,
+		}
+
+		assert_eq!(char::from(Key::Escape), '\u{1b}');
+		assert_eq!(char::from(Key::Eacute), 'é');
+		assert_eq!(Key::try_from('é').unwrap(), Key::Eacute);
+		assert!(Key::try_from('a').is_err());
+		assert_eq!(
+			Key::try_from('\u{1e9}').unwrap_err().kind(),
+			TryFromErrorKind::OutOfRange
+		);
+	}
+
+	#[test]
+	fn next_free() {
+		#[derive(Discriminants)]
+		#[discrim(next_free, reserved = "-1..=0")]
+		#[repr(i8)]
+		enum Max {
+			A = -3,
+			B = -2,
+		}
+
+		#[derive(Discriminants)]
+		#[discrim(next_free = "gap")]
+		#[repr(i8)]
+		enum Gap {
+			A = -3,
+			B = -2,
+			C = 0,
+		}
+
+		assert_eq!(Max::NEXT_FREE_DISCRIMINANT, 1_i8);
+		assert_eq!(Gap::NEXT_FREE_DISCRIMINANT, -1_i8);
+	}
+
+	#[test]
+	fn c_types() {
+		use std::ffi::{c_int, c_uint};
+
+		#[derive(Debug, PartialEq, Eq, Into, TryFrom)]
+		#[discrim(c_int, c_uint)]
+		#[repr(i64)]
+		enum E {
+			A = 0,
+			B = 70_000,
+		}
+
+		assert_eq!(c_int::from(E::B), 70_000);
+		assert_eq!(c_uint::from(E::A), 0);
+		assert_eq!(E::try_from(70_000 as c_int).unwrap(), E::B);
+		assert_eq!(E::try_from(0 as c_uint).unwrap(), E::A);
+		assert!(E::try_from(1 as c_int).is_err());
+	}
+
+	#[test]
+	fn allow_aliases() {
+		#[derive(Debug, PartialEq, Eq, Discriminants, Into, TryFrom)]
+		#[discrim(logical_repr = "i8", allow_aliases, newtype)]
+		enum E {
+			#[discrim(value = "-1")]
+			A,
+			#[discrim(value = "-1", canonical)]
+			B,
+			#[discrim(value = "-0x1")]
+			C,
+			#[discrim(value = 0)]
+			D,
+		}
+
+		assert_eq!(E::C.discriminant(), -1_i8);
+		assert_eq!(i8::from(E::A), -1_i8);
+		assert_eq!(E::try_from(-1).unwrap(), E::B);
+		assert_eq!(E::try_from(EDiscriminant(-1)).unwrap(), E::B);
+		assert_eq!(E::name_of(-1), Some("B"));
+		assert_eq!(E::try_from(0).unwrap(), E::D);
+	}
+
+	#[test]
+	fn hash_values() {
+		#[derive(Debug, PartialEq, Eq, Discriminants, Into, TryFrom)]
+		#[discrim(
+			logical_repr = "u64",
+			hash_values = "fnv1a32",
+			rename_all = "lowercase"
+		)]
+		enum E {
+			Ping,
+			Data,
+			#[discrim(rename = "stop")]
+			Close = 7,
+		}
+
+		assert_eq!(E::Ping_D, 375_255_177_u64);
+		assert_eq!(E::Data.discriminant(), 3_631_407_781_u64);
+		assert_eq!(E::Close_D, 7_u64);
+		assert_eq!(u64::from(E::Close), 7);
+		assert_eq!(E::try_from(3_631_407_781).unwrap(), E::Data);
+		assert_eq!(E::try_from(7).unwrap(), E::Close);
+		assert!(E::try_from(1).is_err());
+	}
+
+	#[test]
+	fn from_str() {
+		#[derive(Debug, PartialEq, Eq, FromStr)]
+		#[discrim(rename_all = "snake_case")]
+		enum E {
+			NotFound,
+			#[discrim(rename = "gone")]
+			NoLongerAvailable,
+			Ok,
+		}
+
+		assert_eq!("not_found".parse::<E>().unwrap(), E::NotFound);
+		assert_eq!("gone".parse::<E>().unwrap(), E::NoLongerAvailable);
+		assert_eq!("ok".parse::<E>().unwrap(), E::Ok);
+		assert!("OK".parse::<E>().is_err());
+
+		#[derive(Debug, PartialEq, Eq, FromStr)]
+		#[discrim(ascii_case_insensitive)]
+		enum F {
+			Error,
+		}
+
+		assert_eq!("eRrOr".parse::<F>().unwrap(), F::Error);
+		assert!("Err".parse::<F>().is_err());
+	}
+
+	#[test]
+	fn cfg() {
 		#[derive(Debug, PartialEq, Eq, Into)]
+		#[discrim(cfg(any()))]
+		#[repr(u8)]
+		enum E {
+			A,
+		}
+		impl From<E> for u8 {
+			fn from(_value: E) -> Self {
+				42
+			}
+		}
+
+		// The derived impl would conflict if it were not gated
+		assert_eq!(u8::from(E::A), 42);
+	}
+
+	#[test]
+	fn zero_variants() {
+		#[derive(Debug, Discriminants, Into, TryFrom, Ordinal)]
+		#[discrim(logical_repr = "u8")]
+		enum E {}
+
+		// Must compile without reading the uninhabited value
+		fn discriminant(value: &E) -> u8 {
+			value.discriminant()
+		}
+
+		assert_eq!(E::COUNT, 0);
+		assert!(E::from_ordinal(0).is_none());
+		assert!(E::try_from(0).is_err());
+		assert_eq!(E::name_of(0), None);
+	}
+
+	#[test]
+	fn ordinal() {
+		#[derive(Debug, PartialEq, Eq, Ordinal)]
 		#[repr(u8)]
 		enum E {
 			A,
@@ -320,14 +4399,19 @@ mod tests {
 			C,
 		}
 
-		assert_eq!(<E as Into<u8>>::into(E::A), 0_u8);
-		assert_eq!(u8::from(E::B), 2_u8);
-		assert_eq!(u8::from(E::C), 3_u8);
+		assert_eq!(E::COUNT, 3);
+		assert_eq!(E::A.ordinal(), 0);
+		assert_eq!(E::B.ordinal(), 1);
+		assert_eq!(E::C.ordinal(), 2);
+		assert_eq!(E::from_ordinal(1), Some(E::B));
+		assert_eq!(E::from_ordinal(3), None);
 	}
 
 	#[test]
-	fn try_from() {
-		#[derive(Debug, PartialEq, Eq, TryFrom)]
+	fn atomic() {
+		use std::sync::atomic::Ordering;
+
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, Into, TryFrom)]
 		#[repr(u8)]
 		enum E {
 			A,
@@ -335,9 +4419,83 @@ mod tests {
 			C,
 		}
 
-		assert_eq!(E::try_from(0).unwrap(), E::A);
-		assert_eq!(E::try_from(2).unwrap(), E::B);
-		assert_eq!(E::try_from(3).unwrap(), E::C);
-		assert!(E::try_from(1).is_err());
+		let cell = atomic::AtomicEnum::<E, u8>::new(E::A);
+		assert_eq!(cell.load(Ordering::Relaxed).unwrap(), E::A);
+		cell.store(E::B, Ordering::Relaxed);
+		assert_eq!(cell.swap(E::C, Ordering::Relaxed).unwrap(), E::B);
+		assert_eq!(
+			cell.compare_exchange(E::A, E::B, Ordering::Relaxed, Ordering::Relaxed)
+				.unwrap(),
+			Err(E::C)
+		);
+		assert_eq!(
+			cell.compare_exchange(E::C, E::A, Ordering::Relaxed, Ordering::Relaxed)
+				.unwrap(),
+			Ok(E::C)
+		);
+		assert_eq!(cell.into_inner().unwrap(), E::A);
+	}
+
+	#[test]
+	fn assert_discriminants() {
+		#[derive(Discriminants)]
+		#[repr(i16)]
+		enum E {
+			A = -1,
+			B = 300,
+			C,
+		}
+
+		assert_discriminants!(E {
+			A = -1,
+			B = 300,
+			C = E::C_D,
+		});
+	}
+
+	#[test]
+	fn enum_table() {
+		#[derive(Debug, Ordinal)]
+		enum E {
+			A,
+			B,
+			C,
+		}
+
+		static NAMES: [&str; 3] = enum_table!(E => &str {
+			A => "a",
+			B => "b",
+			C => "c",
+		});
+
+		assert_eq!(NAMES[E::A.ordinal()], "a");
+		assert_eq!(NAMES[E::C.ordinal()], "c");
+	}
+
+	#[test]
+	fn map_enums() {
+		#[derive(Debug, PartialEq, Eq)]
+		enum A {
+			X,
+			Y,
+		}
+		#[derive(Debug, PartialEq, Eq)]
+		enum B {
+			X,
+			Y,
+			Z,
+		}
+
+		map_enums! {
+			A => B {
+				A::X => B::Y,
+				A::Y => B::X,
+			}
+		}
+
+		assert_eq!(B::from(A::X), B::Y);
+		assert_eq!(B::from(A::Y), B::X);
+		assert_eq!(A::try_from(B::X).unwrap(), A::Y);
+		assert_eq!(A::try_from(B::Z).unwrap_err().enum_name(), "A");
 	}
 }
@@ -0,0 +1,282 @@
+//! Provides the declarative macros of this crate
+
+/// Generates checked conversions between two enums from a list of variant pairs
+///
+/// The mapping is written as `Left => Right { Left::X => Right::Y, ... }` and generates:
+/// - a [`From<Left>`](From) impl block for `Right`, which fails to compile if a variant of `Left` is not mapped;
+/// - a [`TryFrom<Right>`](TryFrom) impl block for `Left`, which returns a [`TryFromError`](crate::TryFromError) for unmapped variants of `Right`.
+///
+/// # Example
+/// ```
+/// use enum_discrim::map_enums;
+///
+/// #[derive(Debug, PartialEq, Eq)]
+/// enum Internal {
+///     Ping,
+///     Data,
+/// }
+///
+/// #[derive(Debug, PartialEq, Eq)]
+/// enum Public {
+///     Ping,
+///     Payload,
+///     Unknown,
+/// }
+///
+/// map_enums! {
+///     Internal => Public {
+///         Internal::Ping => Public::Ping,
+///         Internal::Data => Public::Payload,
+///     }
+/// }
+///
+/// assert_eq!(Public::from(Internal::Data), Public::Payload);
+/// assert_eq!(Internal::try_from(Public::Ping).unwrap(), Internal::Ping);
+/// assert!(Internal::try_from(Public::Unknown).is_err());
+/// ```
+/// ```compile_fail
+/// use enum_discrim::map_enums;
+///
+/// enum Internal {
+///     Ping,
+///     Data,
+/// }
+///
+/// enum Public {
+///     Ping,
+///     Payload,
+/// }
+///
+/// map_enums! {
+///     Internal => Public {
+///         // COMPILE ERROR: Internal::Data is not mapped
+///         Internal::Ping => Public::Ping,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! map_enums {
+	($left:ty => $right:ty { $( $left_variant:path => $right_variant:path ),* $(,)? }) => {
+		#[automatically_derived]
+		impl ::core::convert::From<$left> for $right {
+			#[inline]
+			fn from(value: $left) -> Self {
+				match value {
+					$( $left_variant => $right_variant, )*
+				}
+			}
+		}
+
+		#[automatically_derived]
+		impl ::core::convert::TryFrom<$right> for $left {
+			type Error = $crate::TryFromError;
+
+			#[inline]
+			fn try_from(value: $right) -> ::core::result::Result<Self, Self::Error> {
+				#[allow(unreachable_patterns)]
+				match value {
+					$( $right_variant => ::core::result::Result::Ok($left_variant), )*
+					_ => ::core::result::Result::Err(Self::Error::new(::core::stringify!($left))),
+				}
+			}
+		}
+	};
+}
+
+/// Builds an array holding a value for each variant of an enum, indexable by [ordinal](crate::Ordinal)
+///
+/// The table is written as `Enum => Value { Variant => value, ... }`, listing the variants in declaration order,
+/// and fails to compile if a variant is missing or out of order.
+/// It can be evaluated in const contexts, so that exhaustive static tables need neither a map nor unchecked indices.
+///
+/// This macro supports enums deriving [`Ordinal`](macro@crate::Ordinal).
+///
+/// # Example
+/// ```
+/// use enum_discrim::{enum_table, Ordinal};
+///
+/// #[derive(Clone, Copy, Ordinal)]
+/// enum Priority {
+///     Low,
+///     Normal,
+///     High,
+/// }
+///
+/// const TIMEOUTS: [u32; 3] = enum_table!(Priority => u32 {
+///     Low => 10,
+///     Normal => 20,
+///     High => 30,
+/// });
+///
+/// assert_eq!(TIMEOUTS[Priority::Normal.ordinal()], 20);
+/// ```
+/// ```compile_fail
+/// use enum_discrim::{enum_table, Ordinal};
+///
+/// #[derive(Ordinal)]
+/// enum Priority {
+///     Low,
+///     Normal,
+///     High,
+/// }
+///
+/// // COMPILE ERROR: High is missing
+/// const TIMEOUTS: [u32; 3] = enum_table!(Priority => u32 { Low => 10, Normal => 20 });
+/// ```
+/// ```compile_fail
+/// use enum_discrim::{enum_table, Ordinal};
+///
+/// #[derive(Ordinal)]
+/// enum Priority {
+///     Low,
+///     Normal,
+///     High,
+/// }
+///
+/// // COMPILE ERROR: Normal is not declared first
+/// const TIMEOUTS: [u32; 3] = enum_table!(Priority => u32 { Normal => 20, Low => 10, High => 30 });
+/// ```
+#[macro_export]
+macro_rules! enum_table {
+	($enum:ident => $value:ty { $( $variant:ident => $entry:expr ),* $(,)? }) => {{
+		const _: () = {
+			#[allow(unused_mut)]
+			let mut ordinal = 0_usize;
+			$(
+				::core::assert!(
+					$enum::$variant.__ordinal() == ordinal,
+					::core::concat!(
+						::core::stringify!($enum),
+						"::",
+						::core::stringify!($variant),
+						" is not declared at this position",
+					),
+				);
+				ordinal += 1;
+			)*
+			let _ = ordinal;
+		};
+		const _: fn(&$enum) = |value| match *value {
+			$( $enum::$variant => {} )*
+		};
+
+		let table: [$value; <$enum as $crate::Ordinal>::COUNT] = [$( $entry ),*];
+		table
+	}};
+}
+
+/// Asserts at compile time that the variants of an enum have the given discriminants
+///
+/// The assertion also fails if a variant of the enum is not listed,
+/// so that wire formats cannot change unnoticed.
+///
+/// This macro supports enums with *only* unit variants, whose discriminants can be cast with `as`.
+///
+/// # Example
+/// ```
+/// use enum_discrim::assert_discriminants;
+///
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// assert_discriminants!(E { A = 0, B = 2, C = 3 });
+/// ```
+/// ```compile_fail
+/// use enum_discrim::assert_discriminants;
+///
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// // COMPILE ERROR: C has the discriminant 3
+/// assert_discriminants!(E { A = 0, B = 2, C = 4 });
+/// ```
+/// ```compile_fail
+/// use enum_discrim::assert_discriminants;
+///
+/// #[repr(u8)]
+/// enum E {
+///     A,
+///     B = 2,
+///     C,
+/// }
+///
+/// // COMPILE ERROR: C is not listed
+/// assert_discriminants!(E { A = 0, B = 2 });
+/// ```
+#[macro_export]
+macro_rules! assert_discriminants {
+	($enum:ident { $( $variant:ident = $value:expr ),* $(,)? }) => {
+		const _: () = {
+			$(
+				::core::assert!(
+					$enum::$variant as i128 == $value as i128,
+					::core::concat!(
+						"Discriminant of ",
+						::core::stringify!($enum),
+						"::",
+						::core::stringify!($variant),
+						" is not ",
+						::core::stringify!($value),
+					),
+				);
+			)*
+		};
+		const _: fn(&$enum) = |value| match *value {
+			$( $enum::$variant => {} )*
+		};
+	};
+}
+
+/// Defines enums along with their [`Discriminants`](macro@crate::Discriminants), [`Into`](macro@crate::Into) and [`TryFrom`](macro@crate::TryFrom) impl blocks
+///
+/// Each enum is written as usual, with its attributes (including `#[repr(inttype)]` and `#[discrim(...)]` options),
+/// so that modules declaring many protocol enums need not repeat the derive macros on each of them.
+///
+/// This macro supports enums with *only* unit variants, as required by the [`Into`](macro@crate::Into) and [`TryFrom`](macro@crate::TryFrom) derive macros.
+///
+/// # Example
+/// ```
+/// use enum_discrim::define_discrim_enums;
+///
+/// define_discrim_enums! {
+///     /// Kind of a message
+///     #[derive(Debug, PartialEq, Eq)]
+///     #[repr(u8)]
+///     pub enum Kind {
+///         Ping = 1,
+///         Data,
+///     }
+///
+///     #[derive(Debug, PartialEq, Eq)]
+///     #[discrim(reserved = "0xFF")]
+///     #[repr(u16)]
+///     enum Status {
+///         Ok = 200,
+///         NotFound = 404,
+///     }
+/// }
+///
+/// assert_eq!(Kind::Data_D, 2);
+/// assert_eq!(u8::from(Kind::Ping), 1);
+/// assert_eq!(Status::try_from(404).unwrap(), Status::NotFound);
+/// assert!(Status::try_from(0xFF).unwrap_err().is_reserved());
+/// ```
+#[macro_export]
+macro_rules! define_discrim_enums {
+	($(
+		$( #[$attr:meta] )*
+		$vis:vis enum $enum:ident { $( $body:tt )* }
+	)*) => {$(
+		#[derive($crate::Discriminants, $crate::Into, $crate::TryFrom)]
+		$( #[$attr] )*
+		$vis enum $enum { $( $body )* }
+	)*};
+}
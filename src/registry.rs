@@ -0,0 +1,72 @@
+//! Provides a process-wide registry of the enums deriving [`Register`](macro@crate::Register)
+//!
+//! This is meant for tooling that needs to decode discriminants of arbitrary enums at runtime,
+//! e.g. a debugging console, without compiling a lookup for each enum into it.
+
+use crate::EnumInfo;
+
+/// Entry of the registry, describing an enum deriving [`Register`](macro@crate::Register)
+#[derive(Debug, Clone, Copy)]
+pub struct RegisteredEnum {
+	/// Path of the module declaring the enum
+	module_path: &'static str,
+	/// Metadata of the enum
+	info: &'static EnumInfo,
+}
+impl RegisteredEnum {
+	#[doc(hidden)]
+	#[inline]
+	pub const fn new(module_path: &'static str, info: &'static EnumInfo) -> Self {
+		Self { module_path, info }
+	}
+
+	/// Returns the path of the module declaring the enum
+	#[inline]
+	pub const fn module_path(&self) -> &'static str {
+		self.module_path
+	}
+
+	/// Returns the metadata of the enum
+	#[inline]
+	pub const fn info(&self) -> &'static EnumInfo {
+		self.info
+	}
+}
+
+inventory::collect!(RegisteredEnum);
+
+/// Returns an iterator over all registered enums, in no particular order
+///
+/// # Example
+/// ```
+/// use enum_discrim::{registry, Register};
+///
+/// #[derive(Register)]
+/// #[repr(u8)]
+/// enum Status {
+///     Ok,
+///     NotFound = 4,
+/// }
+///
+/// let status = registry::iter()
+///     .map(|entry| entry.info())
+///     .find(|info| info.name() == "Status")
+///     .unwrap();
+/// assert_eq!(status.repr(), "u8");
+/// assert_eq!(status.name_of(4), Some("NotFound"));
+/// assert_eq!(status.name_of(1), None);
+/// ```
+#[inline]
+pub fn iter() -> impl Iterator<Item = &'static RegisteredEnum> {
+	inventory::iter::<RegisteredEnum>.into_iter()
+}
+
+/// Returns the metadata of the registered enum with the given name, if any
+///
+/// If several registered enums share the name, any of them may be returned.
+#[inline]
+pub fn find(name: &str) -> Option<&'static EnumInfo> {
+	iter()
+		.map(RegisteredEnum::info)
+		.find(|info| info.name() == name)
+}
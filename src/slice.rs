@@ -0,0 +1,67 @@
+//! Provides [`sort_by_discriminant`] and [`binary_search_discriminant`], sorting and searching slices of enum variants by discriminant
+//!
+//! As in the [`group`](crate::group) module, the discriminant of each variant is returned by a given function,
+//! such as the `discriminant` function generated by the [`Discriminants`](macro@crate::Discriminants) derive macro.
+
+/// Sorts the given slice of variants by discriminant
+///
+/// The sort is stable: variants with the same discriminant keep their relative order.
+///
+/// # Example
+/// ```
+/// use enum_discrim::{slice::sort_by_discriminant, Discriminants};
+///
+/// #[derive(Debug, PartialEq, Eq, Discriminants)]
+/// #[repr(u8)]
+/// enum Message {
+///     Ping = 1,
+///     Data(Vec<u8>) = 2,
+/// }
+///
+/// let mut messages = [Message::Data(vec![1]), Message::Ping, Message::Data(vec![2])];
+/// sort_by_discriminant(&mut messages, Message::discriminant);
+/// assert_eq!(messages, [Message::Ping, Message::Data(vec![1]), Message::Data(vec![2])]);
+/// ```
+#[inline]
+pub fn sort_by_discriminant<E, R, F>(variants: &mut [E], discriminant: F)
+where
+	F: FnMut(&E) -> R,
+	R: Ord,
+{
+	variants.sort_by_key(discriminant);
+}
+
+/// Binary searches the given slice of variants, sorted by discriminant, for a variant with the given discriminant
+///
+/// # Errors
+/// Like [`slice::binary_search`], returns the index where a matching variant could be inserted
+/// if there is no variant with the given discriminant.
+///
+/// # Example
+/// ```
+/// use enum_discrim::{slice::binary_search_discriminant, Discriminants};
+///
+/// #[derive(Debug, PartialEq, Eq, Discriminants)]
+/// #[repr(u8)]
+/// enum Message {
+///     Ping = 1,
+///     Data(Vec<u8>) = 2,
+///     Close = 4,
+/// }
+///
+/// let messages = [Message::Ping, Message::Data(vec![1]), Message::Close];
+/// assert_eq!(binary_search_discriminant(&messages, Message::discriminant, &2), Ok(1));
+/// assert_eq!(binary_search_discriminant(&messages, Message::discriminant, &3), Err(2));
+/// ```
+#[inline]
+pub fn binary_search_discriminant<E, R, F>(
+	variants: &[E],
+	mut discriminant: F,
+	value: &R,
+) -> Result<usize, usize>
+where
+	F: FnMut(&E) -> R,
+	R: Ord,
+{
+	variants.binary_search_by(|variant| discriminant(variant).cmp(value))
+}
@@ -0,0 +1,75 @@
+//! Provides [`dense_contains`] and [`sorted_contains`], the lookups called by the code generated by the [`TryFrom`](macro@crate::TryFrom) derive macro
+//!
+//! With `#[discrim(lookup = "table")]` or `#[discrim(lookup = "binary_search")]`,
+//! the generated code only holds a static table of the discriminants and calls into these functions.
+//! As they are generic over the primitive representation rather than over the enum,
+//! they are instantiated once for every representation, however many enums use them.
+
+/// Primitive representation which may index a dense table
+///
+/// This trait is implemented for every [primitive representation](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations).
+pub trait TableRepr: Copy + Ord {
+	/// Returns the offset of `self` from `min`, if `self` is not lower than `min` and the offset fits in a [`usize`]
+	fn offset_from(self, min: Self) -> Option<usize>;
+}
+/// Generates an impl [`TableRepr`] block for each given type
+macro_rules! impl_table_repr {
+	($( $ty:ty ),* $(,)?) => {
+		$(
+			impl TableRepr for $ty {
+				#[inline]
+				fn offset_from(self, min: Self) -> Option<usize> {
+					if self < min {
+						None
+					} else {
+						usize::try_from(self.abs_diff(min)).ok()
+					}
+				}
+			}
+		)*
+	};
+}
+impl_table_repr![u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize];
+
+/// Checks whether the given value is a discriminant, in a table of booleans indexed by the offset from the lowest discriminant
+///
+/// # Example
+/// ```
+/// use enum_discrim::tables::dense_contains;
+///
+/// // Discriminants 0x10, 0x12 and 0x13
+/// static TABLE: [bool; 4] = [true, false, true, true];
+///
+/// assert!(dense_contains(&TABLE, 0x10_u8, 0x12));
+/// assert!(!dense_contains(&TABLE, 0x10_u8, 0x11));
+/// assert!(!dense_contains(&TABLE, 0x10_u8, 0x0F));
+/// assert!(!dense_contains(&TABLE, 0x10_u8, 0xFF));
+/// ```
+pub fn dense_contains<R>(table: &[bool], min: R, value: R) -> bool
+where
+	R: TableRepr,
+{
+	value
+		.offset_from(min)
+		.and_then(|offset| table.get(offset))
+		.copied()
+		.unwrap_or(false)
+}
+
+/// Checks whether the given value is a discriminant, in a sorted slice of the discriminants
+///
+/// # Example
+/// ```
+/// use enum_discrim::tables::sorted_contains;
+///
+/// static VALUES: [i32; 3] = [-100, 0, 1 << 20];
+///
+/// assert!(sorted_contains(&VALUES, 0));
+/// assert!(!sorted_contains(&VALUES, 1));
+/// ```
+pub fn sorted_contains<R>(values: &[R], value: R) -> bool
+where
+	R: Ord,
+{
+	values.binary_search(&value).is_ok()
+}
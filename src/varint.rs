@@ -0,0 +1,100 @@
+//! Provides the LEB128 varint encoding used by the [`Varint`](macro@crate::Varint) derive macro
+
+use crate::TryFromError;
+use std::{
+	error::Error,
+	fmt::{self, Display, Formatter},
+};
+
+/// Maximum number of bytes of an encoded varint
+pub const MAX_LEN: usize = 10;
+
+/// Error returned when decoding a varint
+#[derive(Debug, Clone, Copy)]
+pub enum VarintError {
+	/// The buffer ends before the last byte of the varint
+	Truncated,
+	/// The varint does not fit in a [`u64`]
+	Overflow,
+	/// The varint is not a valid discriminant
+	Invalid(TryFromError),
+}
+impl Display for VarintError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Truncated => write!(f, "Tried to decode a truncated varint"),
+			Self::Overflow => write!(f, "Tried to decode a varint overflowing 64 bits"),
+			Self::Invalid(err) => Display::fmt(err, f),
+		}
+	}
+}
+impl Error for VarintError {
+	#[inline]
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::Truncated | Self::Overflow => None,
+			Self::Invalid(err) => Some(err),
+		}
+	}
+}
+
+/// Encodes the given value as a varint into the given buffer, returning the number of bytes written
+///
+/// # Panics
+/// Panics if the buffer is too short to hold the varint, which is never the case with [`MAX_LEN`] bytes.
+///
+/// # Example
+/// ```
+/// use enum_discrim::varint;
+///
+/// let mut buf = [0; varint::MAX_LEN];
+/// assert_eq!(varint::encode(300, &mut buf), 2);
+/// assert_eq!(buf[..2], [0xAC, 0x02]);
+/// ```
+pub fn encode(mut value: u64, buf: &mut [u8]) -> usize {
+	let mut len = 0;
+	loop {
+		#[allow(clippy::cast_possible_truncation)]
+		let byte = (value & 0x7F) as u8;
+		value >>= 7;
+		if value == 0 {
+			buf[len] = byte;
+			return len + 1;
+		}
+		buf[len] = byte | 0x80;
+		len += 1;
+	}
+}
+
+/// Decodes a varint from the start of the given buffer, returning its value and the number of bytes read
+///
+/// # Errors
+/// Returns [`VarintError::Truncated`] if the buffer ends before the varint,
+/// or [`VarintError::Overflow`] if the varint does not fit in a [`u64`].
+///
+/// # Example
+/// ```
+/// use enum_discrim::varint;
+///
+/// assert_eq!(varint::decode(&[0xAC, 0x02, 0xFF]).unwrap(), (300, 2));
+/// assert!(varint::decode(&[0xAC]).is_err());
+/// ```
+pub fn decode(buf: &[u8]) -> Result<(u64, usize), VarintError> {
+	let mut value = 0;
+	for (i, byte) in buf.iter().enumerate().take(MAX_LEN) {
+		let bits = u64::from(byte & 0x7F);
+		if i == MAX_LEN - 1 && bits > 1 {
+			return Err(VarintError::Overflow);
+		}
+		value |= bits << (7 * i);
+		if byte & 0x80 == 0 {
+			return Ok((value, i + 1));
+		}
+	}
+
+	if buf.len() >= MAX_LEN {
+		Err(VarintError::Overflow)
+	} else {
+		Err(VarintError::Truncated)
+	}
+}